@@ -0,0 +1,115 @@
+// examples/raid_benchmark.rs
+//
+// 大規模な飽和攻撃シナリオをヘッドレス（出力なし）で実行し、
+// 1秒あたりのステップ数を計測するベンチマーク。
+//
+// 実行例: cargo run --release --example raid_benchmark -- <ミサイル数> <迎撃ミサイル数> <ステップ数>
+
+use std::error::Error;
+use std::time::Instant;
+
+use mslsim::config::parameters::{InterceptorParameters, MissileParameters, RadarParameters};
+use mslsim::config::scenario::{Scenario, ScenarioBuilder};
+use mslsim::models;
+use mslsim::models::gravity::GravityModel;
+use mslsim::simulation::framework::{execute_simulation_step, initialize_simulation_state};
+
+const DT: f64 = 0.1;
+
+/// N発のミサイルとM発の迎撃ミサイルからなるシナリオを生成
+fn raid_scenario(n_missiles: usize, n_interceptors: usize) -> Scenario {
+    let mut builder = ScenarioBuilder::new().radar("radar", [50_000.0, 0.0, 0.0]);
+    for i in 0..n_missiles {
+        // 横方向に100m間隔で並べる
+        let y = i as f64 * 100.0;
+        builder = builder.missile(&format!("m{}", i), [0.0, y, 0.0], [300.0, 0.0, 300.0], 45.0);
+    }
+    for i in 0..n_interceptors {
+        let y = i as f64 * 100.0;
+        builder = builder.interceptor(&format!("i{}", i), [80_000.0, y, 0.0], [0.0, 0.0, 10.0], 90.0);
+    }
+    builder.build()
+}
+
+/// シナリオを出力なしで指定ステップ実行し、実行したステップ数を返す
+fn run_headless(n_missiles: usize, n_interceptors: usize, steps: usize) -> Result<usize, Box<dyn Error>> {
+    let missile_params = MissileParameters {
+        mass_initial: 5000.0,
+        fuel_consumption_rate: 10.0,
+        drag_coefficient: 0.3,
+        area: 1.0,
+        thrust: [60_000.0, 0.0, 60_000.0],
+        ..Default::default()
+    };
+    let radar_params = RadarParameters {
+        azimuth_min: 0.0,
+        azimuth_max: 360.0,
+        elevation_min: -90.0,
+        elevation_max: 90.0,
+        detection_range: 200_000.0,
+    };
+    let interceptor_params = InterceptorParameters {
+        mass_initial: 2000.0,
+        navigation_coefficient: 3.0,
+    };
+
+    let mut state = initialize_simulation_state(
+        missile_params.clone(),
+        radar_params,
+        interceptor_params.clone(),
+        raid_scenario(n_missiles, n_interceptors),
+    )?;
+
+    let gravity = GravityModel::default();
+    for _ in 0..steps {
+        state = execute_simulation_step(&state, &missile_params, &interceptor_params, &gravity, DT)?;
+        // 探知処理も実行するが結果は出力しない
+        let _ = models::motion::detect_all_radars(&state.radars, &state.missiles);
+    }
+
+    Ok(steps)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<usize> = std::env::args()
+        .skip(1)
+        .map(|a| a.parse())
+        .collect::<Result<_, _>>()?;
+    let n_missiles = args.first().copied().unwrap_or(1000);
+    let n_interceptors = args.get(1).copied().unwrap_or(1000);
+    let steps = args.get(2).copied().unwrap_or(1000);
+
+    let start = Instant::now();
+    let executed = run_headless(n_missiles, n_interceptors, steps)?;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    println!(
+        "missiles={} interceptors={} steps={} elapsed={:.3} s steps/sec={:.1}",
+        n_missiles,
+        n_interceptors,
+        executed,
+        elapsed,
+        executed as f64 / elapsed.max(f64::EPSILON)
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_raid_runs_to_completion() {
+        let executed = run_headless(10, 5, 50).unwrap();
+        assert_eq!(executed, 50);
+    }
+
+    #[test]
+    fn test_raid_scenario_entity_counts() {
+        let scenario = raid_scenario(3, 2);
+        assert_eq!(scenario.missiles.len(), 3);
+        assert_eq!(scenario.interceptors.len(), 2);
+        assert_eq!(scenario.radars.len(), 1);
+    }
+}
@@ -2,5 +2,6 @@
 
 pub mod parameters;
 pub mod scenario;
+pub mod randomize;
 
 pub use parameters::*;
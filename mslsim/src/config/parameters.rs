@@ -1,17 +1,29 @@
 // src/config/parameters.rs
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
 pub struct MissileParameters {
     pub mass_initial: f64, // 初期質量 (kg)
     pub fuel_consumption_rate: f64, // 燃料消費率 (kg/s)
     pub drag_coefficient: f64, // 抗力係数
     pub area: f64, // 投影面積 (m²)
     pub thrust: [f64; 3], // 推進力ベクトル (N)
+    #[serde(default)]
+    pub thrust_offset: [f64; 3], // 重心から推力作用点までのオフセット (m)
+    #[serde(default)]
+    pub pitch_inertia: f64, // y軸まわりの慣性モーメント (kg·m²)。thrust_offset によるピッチ回転に使う（0で回転なし）
+    #[serde(default)]
+    pub thrust_noise_sigma: f64, // 推力のステップごとの乗法ノイズの標準偏差（0で無効）
+    // 燃焼終了質量 (kg)。質量がこれ以下になると推力を停止する（0 < burnout_mass <= mass_initial）。
+    // 既定値は設けない: 従来のファイルには乾燥質量の情報がなく、推測した値（0 など）では燃料を使い切っても
+    // 推力が止まらず質量が0以下になって加速度が発散するため、省略したファイルは読み込み時に解析エラーとする。
+    pub burnout_mass: f64,
+    #[serde(default)]
+    pub base_drag_delta: f64, // 燃焼終了後（推力ゼロ）に抗力係数へ加える底面抗力の増分
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct RadarParameters {
     pub azimuth_min: f64, // 方位角最小 (度)
     pub azimuth_max: f64, // 方位角最大 (度)
@@ -20,7 +32,7 @@ pub struct RadarParameters {
     pub detection_range: f64, // 探知距離 (m)
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct InterceptorParameters {
     pub mass_initial: f64,                // 初期質量（kg）
     pub navigation_coefficient: f64, // 比例航法係数   
@@ -0,0 +1,181 @@
+// src/config/randomize.rs
+
+use serde::Deserialize;
+
+use crate::config::scenario::Scenario;
+use crate::math::SeededRng;
+
+/// シナリオ摂動の範囲（各値は ± の幅）
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScenarioRanges {
+    pub position: [f64; 3], // 初期位置の摂動幅 (m)
+    pub velocity: [f64; 3], // 初期速度の摂動幅 (m/s)
+    pub heading: f64,       // 方位角の摂動幅 (度)
+}
+
+/// 基準シナリオのミサイル初期状態を乱数で摂動する純粋関数
+///
+/// 位置・速度は各成分に一様乱数を加え、方位は速度ベクトルをZ軸まわりに回転させる。
+/// 同じシードの乱数生成器からは常に同じシナリオが得られる。
+/// 各ミサイルの摂動は `rng` から引いたシードとミサイルIDによる独立な系列で決まるため、
+/// ミサイルを追加しても他のミサイルの摂動は変わらない。
+///
+/// # 引数
+/// - `base`: 基準シナリオ
+/// - `ranges`: 摂動の範囲
+/// - `rng`: シード付き乱数生成器
+///
+/// # 戻り値
+/// - 摂動後のシナリオ
+pub fn randomize_scenario(base: &Scenario, ranges: &ScenarioRanges, rng: &mut SeededRng) -> Scenario {
+    let mut scenario = base.clone();
+    let master_seed = rng.next_u64();
+    for missile in scenario.missiles.iter_mut() {
+        let rng = &mut SeededRng::for_entity(master_seed, &missile.id);
+        for i in 0..3 {
+            missile.initial_position[i] += rng.uniform(-ranges.position[i], ranges.position[i]);
+        }
+        for i in 0..3 {
+            missile.initial_velocity[i] += rng.uniform(-ranges.velocity[i], ranges.velocity[i]);
+        }
+        let heading = rng.uniform(-ranges.heading, ranges.heading).to_radians();
+        let [vx, vy, vz] = missile.initial_velocity;
+        missile.initial_velocity = [
+            vx * heading.cos() - vy * heading.sin(),
+            vx * heading.sin() + vy * heading.cos(),
+            vz,
+        ];
+    }
+    scenario
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::scenario::MissileInstance;
+
+    fn base_scenario() -> Scenario {
+        Scenario {
+            missiles: vec![
+                MissileInstance {
+                    id: "missile1".to_string(),
+                    initial_position: [0.0, 0.0, 0.0],
+                    initial_velocity: [100.0, 0.0, 50.0],
+                    initial_pitch: 45.0,
+                    mass: None,
+                    thrust: None,
+                    thrust_magnitude: None,
+                    metadata: Default::default(),
+                    integrator: Default::default(),
+                    drag_scale: None,
+                    thrust_scale: None,
+                    spawn_time: None,
+                },
+                MissileInstance {
+                    id: "missile2".to_string(),
+                    initial_position: [1000.0, 500.0, 0.0],
+                    initial_velocity: [0.0, 100.0, 50.0],
+                    initial_pitch: 45.0,
+                    mass: None,
+                    thrust: None,
+                    thrust_magnitude: None,
+                    metadata: Default::default(),
+                    integrator: Default::default(),
+                    drag_scale: None,
+                    thrust_scale: None,
+                    spawn_time: None,
+                },
+            ],
+            radars: vec![],
+            interceptors: vec![],
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn test_randomize_scenario_is_deterministic() {
+        let base = base_scenario();
+        let ranges = ScenarioRanges {
+            position: [100.0, 100.0, 0.0],
+            velocity: [5.0, 5.0, 5.0],
+            heading: 10.0,
+        };
+
+        let a = randomize_scenario(&base, &ranges, &mut SeededRng::new(1234));
+        let b = randomize_scenario(&base, &ranges, &mut SeededRng::new(1234));
+
+        for (ma, mb) in a.missiles.iter().zip(b.missiles.iter()) {
+            assert_eq!(ma.initial_position, mb.initial_position);
+            assert_eq!(ma.initial_velocity, mb.initial_velocity);
+        }
+        // 摂動が実際に加わっていること
+        assert_ne!(a.missiles[0].initial_position, base.missiles[0].initial_position);
+    }
+
+    #[test]
+    fn test_randomize_scenario_position_within_bounds() {
+        let base = base_scenario();
+        let ranges = ScenarioRanges {
+            position: [100.0, 50.0, 0.0],
+            velocity: [5.0, 5.0, 5.0],
+            heading: 0.0,
+        };
+        let mut rng = SeededRng::new(99);
+
+        for _ in 0..100 {
+            let s = randomize_scenario(&base, &ranges, &mut rng);
+            for (m, b) in s.missiles.iter().zip(base.missiles.iter()) {
+                for i in 0..3 {
+                    assert!((m.initial_position[i] - b.initial_position[i]).abs() <= ranges.position[i]);
+                    assert!((m.initial_velocity[i] - b.initial_velocity[i]).abs() <= ranges.velocity[i]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_randomize_scenario_heading_within_bounds() {
+        let base = base_scenario();
+        let ranges = ScenarioRanges {
+            position: [0.0, 0.0, 0.0],
+            velocity: [0.0, 0.0, 0.0],
+            heading: 10.0,
+        };
+        let mut rng = SeededRng::new(5);
+
+        for _ in 0..100 {
+            let s = randomize_scenario(&base, &ranges, &mut rng);
+            for (m, b) in s.missiles.iter().zip(base.missiles.iter()) {
+                let heading = m.initial_velocity[1].atan2(m.initial_velocity[0]);
+                let base_heading = b.initial_velocity[1].atan2(b.initial_velocity[0]);
+                assert!((heading - base_heading).to_degrees().abs() <= ranges.heading + 1e-9);
+                // 水平速度の大きさと鉛直速度は保存される
+                let speed = m.initial_velocity[0].hypot(m.initial_velocity[1]);
+                let base_speed = b.initial_velocity[0].hypot(b.initial_velocity[1]);
+                assert!((speed - base_speed).abs() < 1e-9);
+                assert_eq!(m.initial_velocity[2], b.initial_velocity[2]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adding_missile_keeps_other_dispersions() {
+        let base = base_scenario();
+        let mut extended = base.clone();
+        extended.missiles.push(MissileInstance {
+            id: "missile3".to_string(),
+            ..base.missiles[0].clone()
+        });
+        let ranges = ScenarioRanges {
+            position: [100.0, 100.0, 10.0],
+            velocity: [5.0, 5.0, 5.0],
+            heading: 10.0,
+        };
+
+        let a = randomize_scenario(&base, &ranges, &mut SeededRng::new(77));
+        let b = randomize_scenario(&extended, &ranges, &mut SeededRng::new(77));
+
+        assert_eq!(b.missiles[..2], a.missiles[..]);
+        assert_ne!(b.missiles[2].initial_position, b.missiles[0].initial_position);
+    }
+}
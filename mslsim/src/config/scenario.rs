@@ -1,32 +1,152 @@
 // src/config/scenario.rs
 
-use serde::Deserialize;
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize)]
+use serde::{Deserialize, Serialize};
+
+use crate::math::IntegratorKind;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Scenario {
     pub missiles: Vec<MissileInstance>,
     pub radars: Vec<RadarInstance>,
     pub interceptors: Vec<InterceptorInstance>,
+    #[serde(default)]
+    pub seed: u64, // 推力ノイズなど確率的要素の乱数シード
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct MissileInstance {
     pub id: String,
     pub initial_position: [f64; 3],
     pub initial_velocity: [f64; 3],
     pub initial_pitch: f64,
+    pub mass: Option<f64>, // 初期質量 (kg)。未指定時はパラメータの mass_initial
+    pub thrust: Option<[f64; 3]>, // 推進力ベクトル (N)。未指定時はパラメータの thrust
+    pub thrust_magnitude: Option<f64>, // 推進力の大きさ (N)。パラメータの thrust の向きのまま大きさを変える
+    #[serde(default)]
+    pub metadata: HashMap<String, String>, // 出力に引き継ぐ任意属性 (team, type など)
+    #[serde(default)]
+    pub integrator: IntegratorKind, // 速度の積分方式 (euler / ab2 / rk4)
+    #[serde(default)]
+    pub drag_scale: Option<f64>, // 空気抵抗の倍率（感度解析用）。未指定時は 1.0
+    #[serde(default)]
+    pub thrust_scale: Option<f64>, // 推進力の倍率（感度解析用）。未指定時は 1.0
+    #[serde(default)]
+    pub spawn_time: Option<f64>, // 出現時刻 (s)。それまでは更新・探知されない。未指定時は開始時から存在
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct RadarInstance {
     pub id: String,
     pub position: [f64; 3],
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct InterceptorInstance {
     pub id: String,
     pub initial_position: [f64; 3],
     pub initial_velocity: [f64; 3],
     pub initial_pitch: f64,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>, // 出力に引き継ぐ任意属性 (team, type など)
+    #[serde(default)]
+    pub spawn_time: Option<f64>, // 出現時刻 (s)。それまでは更新されない。未指定時は開始時から存在
+}
+
+/// シナリオをプログラムから組み立てるためのビルダー
+///
+/// # 使用例
+/// ```
+/// use mslsim::config::scenario::ScenarioBuilder;
+///
+/// let scenario = ScenarioBuilder::new()
+///     .missile("m1", [0.0, 0.0, 0.0], [100.0, 0.0, 50.0], 45.0)
+///     .radar("r1", [0.0, 0.0, 0.0])
+///     .interceptor("i1", [10000.0, 0.0, 0.0], [0.0, 0.0, 0.0], 90.0)
+///     .build();
+/// assert_eq!(scenario.missiles.len(), 1);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ScenarioBuilder {
+    missiles: Vec<MissileInstance>,
+    radars: Vec<RadarInstance>,
+    interceptors: Vec<InterceptorInstance>,
+    seed: u64,
+}
+
+impl ScenarioBuilder {
+    /// 空のビルダーを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ミサイルを追加（初期質量はパラメータの mass_initial）
+    pub fn missile(
+        mut self,
+        id: &str,
+        initial_position: [f64; 3],
+        initial_velocity: [f64; 3],
+        initial_pitch: f64,
+    ) -> Self {
+        self.missiles.push(MissileInstance {
+            id: id.to_string(),
+            initial_position,
+            initial_velocity,
+            initial_pitch,
+            mass: None,
+            thrust: None,
+            thrust_magnitude: None,
+            metadata: Default::default(),
+            integrator: IntegratorKind::default(),
+            drag_scale: None,
+            thrust_scale: None,
+            spawn_time: None,
+        });
+        self
+    }
+
+    /// レーダを追加
+    pub fn radar(mut self, id: &str, position: [f64; 3]) -> Self {
+        self.radars.push(RadarInstance {
+            id: id.to_string(),
+            position,
+        });
+        self
+    }
+
+    /// 迎撃ミサイルを追加
+    pub fn interceptor(
+        mut self,
+        id: &str,
+        initial_position: [f64; 3],
+        initial_velocity: [f64; 3],
+        initial_pitch: f64,
+    ) -> Self {
+        self.interceptors.push(InterceptorInstance {
+            id: id.to_string(),
+            initial_position,
+            initial_velocity,
+            initial_pitch,
+            metadata: Default::default(),
+            spawn_time: None,
+        });
+        self
+    }
+
+    /// 乱数シードを設定
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// シナリオを生成
+    pub fn build(self) -> Scenario {
+        Scenario {
+            missiles: self.missiles,
+            radars: self.radars,
+            interceptors: self.interceptors,
+            seed: self.seed,
+        }
+    }
 }
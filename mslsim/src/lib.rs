@@ -0,0 +1,10 @@
+// src/lib.rs
+
+pub mod simulation;
+pub mod models;
+pub mod math;
+pub mod config;
+
+pub use models::missile::Missile;
+pub use models::radar::Radar;
+pub use models::interceptor::Interceptor;
@@ -1,62 +1,80 @@
 // src/main.rs
 
 use std::error::Error;
-use std::io::Write;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 
-use simulation::load_parameters::*;
-use simulation::csv::*;
-use simulation::framework::*;
-use models::missile::Missile;
-use models::radar::Radar;
-use models::interceptor::Interceptor;
-
-
-mod simulation;
-mod models;
-mod math;
-mod config;
+use mslsim::simulation::load_parameters::*;
+use mslsim::simulation::csv::*;
+use mslsim::simulation::framework::*;
+use mslsim::simulation::guard::{apply_step_distance_guard, StepDistanceGuard};
+use mslsim::models;
+use mslsim::models::gravity::GravityModel;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // 設定とシナリオの読み込み
-    let missile_params = load_missile_parameters("config/missile_parameters.yaml")?;
-    let radar_params = load_radar_parameters("config/radar_parameters.yaml")?;
-    let interceptor_params = load_interceptor_parameters("config/interceptor_parameters.yaml")?;
-    let scenario = load_scenario("config/scenario.yaml")?;
+    // シナリオのエンティティ数の上限（環境変数 MSLSIM_MAX_ENTITIES で変更可能）
+    let max_entities = match std::env::var("MSLSIM_MAX_ENTITIES") {
+        Ok(value) => value.parse()?,
+        Err(_) => DEFAULT_MAX_ENTITIES,
+    };
+
+    // 設定とシナリオの読み込み（上限を超えるシナリオは解析の途中で打ち切る）
+    let SimConfig {
+        missile_params,
+        radar_params,
+        interceptor_params,
+        scenario,
+    } = load_config_dir_with_limit("config", max_entities)?;
 
     // エンティティの初期化
-    let mut state = initialize_simulation_state(
+    let mut state = initialize_simulation_state_with_limit(
         missile_params.clone(),
         radar_params,
         interceptor_params.clone(),
         scenario,
-    );
+        max_entities,
+    )?;
+
+    // CSV出力の設定（追加で出力する列と座標系の基準は config/output.yaml で選択）
+    let csv_output = load_csv_output("config/output.yaml")?;
+    let mut writer: Box<dyn Write> = setup_csv_output("output/simulation_results.csv", &state, &csv_output)?;
 
-    // CSV出力の設定
-    let mut writer: Box<dyn Write> = setup_csv_output("output/simulation_results.csv", &state)?;
+    // エンティティの任意属性（結果CSVとは別ファイルに一度だけ書き込む）
+    let mut metadata_writer = BufWriter::new(File::create("output/entity_metadata.csv")?);
+    write_metadata_csv(&mut metadata_writer, &state)?;
+    metadata_writer.flush()?;
 
-    // 重力加速度の定義
-    let gravity = [0.0, 0.0, -9.81];
+    // 重力モデルの定義（-z方向の標準重力）
+    let gravity = GravityModel::default();
     let dt = 0.1;
     let cycles = 1000;
 
+    // 数値発散による異常な移動の検出（1ステップあたりの上限距離）
+    let step_guard = StepDistanceGuard {
+        max_step_distance: 10_000.0,
+        clamp: false,
+    };
+
     // シミュレーションのメインループ
     for cycle in 0..cycles {
         let time = cycle as f64 * dt;
 
         // シミュレーションステップの実行
-        state = execute_simulation_step(&state, &missile_params, &interceptor_params, gravity, dt)?;
+        let next_state = execute_simulation_step(&state, &missile_params, &interceptor_params, &gravity, dt)?;
+        let (next_state, warnings) = apply_step_distance_guard(&state, next_state, &step_guard);
+        for warning in &warnings {
+            eprintln!(
+                "警告: t={:.1} s で {} が1ステップに {:.1} m 移動しました",
+                time, warning.id, warning.distance
+            );
+        }
+        state = next_state;
 
-        // レーダーの探知処理
-        let radar_detections = models::motion::detect_all_radars(&state.radars, &state.missiles);
+        // レーダーの探知処理（出現前のミサイルは探知しない）
+        let radar_detections = models::motion::detect_all_radars(&state.radars, &state.spawned_missiles());
 
         // CSV行の作成と書き込み
-        let row = create_csv_row(
-            &time,
-            &state.missiles,
-            &state.interceptors,
-            &state.radars,
-            &radar_detections,
-        );
+        let row = create_state_csv_row(&time, &state, &radar_detections, &csv_output);
         writer.write_all(row.as_bytes())?;
     }
 
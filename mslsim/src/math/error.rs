@@ -6,5 +6,9 @@ use thiserror::Error;
 pub enum MathError {
     #[error("atan2 の入力がゼロに近すぎます。")]
     Atan2ZeroInput,
+    #[error("フィルタ係数 alpha={0} が [0, 1] の範囲外です。")]
+    InvalidAlpha(f64),
+    #[error("積分器の入力が有限値ではありません (NaN または無限大)。")]
+    NonFinite,
     // 他の数値計算エラーを追加可能
 }
@@ -1,31 +1,68 @@
 // src/math/filter.rs
 
+use crate::math::error::MathError;
+
 /// 一階ローパスフィルタの状態
 #[derive(Debug, Clone, PartialEq)]
 pub struct LowPassFilterState {
     pub previous: f64,
 }
 
+/// 各軸の初期値から3軸分のフィルタの状態を作成する
+///
+/// 速度のフィルタは初期速度で初期化する（0で初期化すると初回ステップで速度が半減したままになる）。
+///
+/// # 引数
+/// - `initial`: 各軸の初期値 [x, y, z]
+///
+/// # 戻り値
+/// - x, y, z 軸のフィルタの状態
+pub fn low_pass_filter_states(initial: [f64; 3]) -> [LowPassFilterState; 3] {
+    initial.map(|previous| LowPassFilterState { previous })
+}
+
+/// フィルタ係数を検証する
+///
+/// alpha が [0, 1] の範囲外（NaN を含む）だとフィルタが発散・増幅するため拒否する。
+///
+/// # 引数
+/// - `alpha`: フィルタ係数
+///
+/// # 戻り値
+/// - 範囲内ならそのままの `alpha`
+/// - 範囲外なら `MathError::InvalidAlpha`
+pub fn validate_alpha(alpha: f64) -> Result<f64, MathError> {
+    if (0.0..=1.0).contains(&alpha) {
+        Ok(alpha)
+    } else {
+        Err(MathError::InvalidAlpha(alpha))
+    }
+}
+
 /// 一階ローパスフィルタ
 ///
 /// # 引数
 /// - `state`: 現在のフィルタの状態
 /// - `input`: 入力値
-/// - `alpha`: フィルタ係数
+/// - `alpha`: フィルタ係数（[0, 1] の範囲外は範囲内に丸める）
 ///
 /// # 戻り値
-/// - 更新後のフィルタの状態
-/// - フィルタ後の値
+/// - 更新後のフィルタの状態とフィルタ後の値
+/// - `alpha` が NaN の場合は丸められないため `MathError::InvalidAlpha`
 pub fn low_pass_filter(
     state: LowPassFilterState,
     input: f64,
     alpha: f64,
-) -> (LowPassFilterState, f64) {
+) -> Result<(LowPassFilterState, f64), MathError> {
+    if alpha.is_nan() {
+        return Err(MathError::InvalidAlpha(alpha));
+    }
+    let alpha = alpha.clamp(0.0, 1.0);
     let filtered = alpha * input + (1.0 - alpha) * state.previous;
     let new_state = LowPassFilterState {
         previous: filtered,
     };
-    (new_state, filtered)
+    Ok((new_state, filtered))
 }
 
 #[cfg(test)]
@@ -37,7 +74,7 @@ mod tests {
         let initial_state = LowPassFilterState { previous: 0.0 };
         let input = 10.0;
         let alpha = 0.5;
-        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha);
+        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha).unwrap();
         
         let expected_filtered = 0.5 * 10.0 + 0.5 * 0.0; // 5.0
         let expected_state = LowPassFilterState { previous: 5.0 };
@@ -51,7 +88,7 @@ mod tests {
         let initial_state = LowPassFilterState { previous: 5.0 };
         let input = 15.0;
         let alpha = 0.3;
-        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha);
+        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha).unwrap();
         
         let expected_filtered = 0.3 * 15.0 + 0.7 * 5.0; // 4.5 + 3.5 = 8.0
         let expected_state = LowPassFilterState { previous: 8.0 };
@@ -65,7 +102,7 @@ mod tests {
         let initial_state = LowPassFilterState { previous: 2.0 };
         let input = 10.0;
         let alpha = 0.0;
-        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha);
+        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha).unwrap();
         
         let expected_filtered = 0.0 * 10.0 + 1.0 * 2.0; // 2.0
         let expected_state = LowPassFilterState { previous: 2.0 };
@@ -79,7 +116,7 @@ mod tests {
         let initial_state = LowPassFilterState { previous: 3.0 };
         let input = 7.0;
         let alpha = 1.0;
-        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha);
+        let (new_state, filtered) = low_pass_filter(initial_state.clone(), input, alpha).unwrap();
         
         let expected_filtered = 1.0 * 7.0 + 0.0 * 3.0; // 7.0
         let expected_state = LowPassFilterState { previous: 7.0 };
@@ -87,4 +124,25 @@ mod tests {
         assert_eq!(filtered, expected_filtered);
         assert_eq!(new_state, expected_state);
     }
+
+    #[test]
+    fn test_validate_alpha_rejects_out_of_range() {
+        assert_eq!(validate_alpha(0.0).unwrap(), 0.0);
+        assert_eq!(validate_alpha(0.3).unwrap(), 0.3);
+        assert_eq!(validate_alpha(1.0).unwrap(), 1.0);
+        for alpha in [-0.1, 1.5, f64::NAN] {
+            assert!(matches!(validate_alpha(alpha), Err(MathError::InvalidAlpha(_))));
+        }
+    }
+
+    #[test]
+    fn test_low_pass_filter_clamps_out_of_range_alpha() {
+        let state = LowPassFilterState { previous: 10.0 };
+        // alpha > 1 は 1（入力をそのまま通す）、alpha < 0 は 0（前回値を保持）に丸める
+        assert_eq!(low_pass_filter(state.clone(), 20.0, 1.5).unwrap().1, 20.0);
+        assert_eq!(low_pass_filter(state.clone(), 20.0, -0.5).unwrap().1, 10.0);
+        assert_eq!(low_pass_filter(state.clone(), 20.0, 0.3).unwrap().1, 0.3 * 20.0 + 0.7 * 10.0);
+        // NaN は丸められないため拒否する
+        assert!(matches!(low_pass_filter(state, 20.0, f64::NAN), Err(MathError::InvalidAlpha(_))));
+    }
 }
@@ -0,0 +1,163 @@
+// src/math/geodetic.rs
+
+use serde::{Deserialize, Serialize};
+
+/// WGS84 楕円体の長半径（m）
+pub const WGS84_A: f64 = 6_378_137.0;
+/// WGS84 楕円体の扁平率
+pub const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// 第一離心率の2乗
+const WGS84_E2: f64 = WGS84_F * (2.0 - WGS84_F);
+
+/// 測地座標（WGS84）
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub struct GeodeticPosition {
+    pub latitude: f64,  // 緯度（度）
+    pub longitude: f64, // 経度（度）
+    pub altitude: f64,  // 楕円体高（m）
+}
+
+/// 測地座標を地心直交座標（ECEF）に変換する
+///
+/// # 引数
+/// - `position`: 測地座標
+///
+/// # 戻り値
+/// - ECEF座標 [X, Y, Z]（m）
+pub fn geodetic_to_ecef(position: &GeodeticPosition) -> [f64; 3] {
+    let (sin_lat, cos_lat) = position.latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = position.longitude.to_radians().sin_cos();
+    let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + position.altitude) * cos_lat * cos_lon,
+        (n + position.altitude) * cos_lat * sin_lon,
+        (n * (1.0 - WGS84_E2) + position.altitude) * sin_lat,
+    ]
+}
+
+/// 地心直交座標（ECEF）を測地座標に変換する
+///
+/// 緯度は固定点反復で求める（極の近傍を除き数回で収束する）。
+///
+/// # 引数
+/// - `ecef`: ECEF座標 [X, Y, Z]（m）
+///
+/// # 戻り値
+/// - 測地座標
+pub fn ecef_to_geodetic(ecef: &[f64; 3]) -> GeodeticPosition {
+    let p = ecef[0].hypot(ecef[1]);
+    let longitude = ecef[1].atan2(ecef[0]);
+    let mut latitude = ecef[2].atan2(p * (1.0 - WGS84_E2));
+    let mut altitude = 0.0;
+    for _ in 0..10 {
+        let sin_lat = latitude.sin();
+        let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+        altitude = p / latitude.cos() - n;
+        latitude = ecef[2].atan2(p * (1.0 - WGS84_E2 * n / (n + altitude)));
+    }
+    GeodeticPosition {
+        latitude: latitude.to_degrees(),
+        longitude: longitude.to_degrees(),
+        altitude,
+    }
+}
+
+/// 原点 `origin` の局所東北上（ENU）座標を測地座標に変換する
+///
+/// シミュレーション座標 [x, y, z] を原点における東・北・上とみなす場合に用いる。
+///
+/// # 引数
+/// - `enu`: ENU座標 [東, 北, 上]（m）
+/// - `origin`: ENU座標の原点の測地座標
+///
+/// # 戻り値
+/// - 測地座標
+pub fn enu_to_geodetic(enu: &[f64; 3], origin: &GeodeticPosition) -> GeodeticPosition {
+    let (sin_lat, cos_lat) = origin.latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = origin.longitude.to_radians().sin_cos();
+    let [e, n, u] = *enu;
+    let origin_ecef = geodetic_to_ecef(origin);
+    ecef_to_geodetic(&[
+        origin_ecef[0] - sin_lon * e - sin_lat * cos_lon * n + cos_lat * cos_lon * u,
+        origin_ecef[1] + cos_lon * e - sin_lat * sin_lon * n + cos_lat * sin_lon * u,
+        origin_ecef[2] + cos_lat * n + sin_lat * u,
+    ])
+}
+
+/// 測地座標を原点 `origin` の局所東北上（ENU）座標に変換する（`enu_to_geodetic` の逆変換）
+///
+/// # 引数
+/// - `position`: 測地座標
+/// - `origin`: ENU座標の原点の測地座標
+///
+/// # 戻り値
+/// - ENU座標 [東, 北, 上]（m）
+pub fn geodetic_to_enu(position: &GeodeticPosition, origin: &GeodeticPosition) -> [f64; 3] {
+    let (sin_lat, cos_lat) = origin.latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = origin.longitude.to_radians().sin_cos();
+    let ecef = geodetic_to_ecef(position);
+    let origin_ecef = geodetic_to_ecef(origin);
+    let d = [ecef[0] - origin_ecef[0], ecef[1] - origin_ecef[1], ecef[2] - origin_ecef[2]];
+    [
+        -sin_lon * d[0] + cos_lon * d[1],
+        -sin_lat * cos_lon * d[0] - sin_lat * sin_lon * d[1] + cos_lat * d[2],
+        cos_lat * cos_lon * d[0] + cos_lat * sin_lon * d[1] + sin_lat * d[2],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin() -> GeodeticPosition {
+        GeodeticPosition {
+            latitude: 35.0,
+            longitude: 139.0,
+            altitude: 50.0,
+        }
+    }
+
+    #[test]
+    fn test_ecef_round_trip() {
+        let position = GeodeticPosition {
+            latitude: -33.5,
+            longitude: -70.25,
+            altitude: 12000.0,
+        };
+        let back = ecef_to_geodetic(&geodetic_to_ecef(&position));
+        assert!((back.latitude - position.latitude).abs() < 1e-9);
+        assert!((back.longitude - position.longitude).abs() < 1e-9);
+        assert!((back.altitude - position.altitude).abs() < 1e-6);
+
+        // 赤道・本初子午線上は (a + h, 0, 0)
+        let equator = geodetic_to_ecef(&GeodeticPosition {
+            latitude: 0.0,
+            longitude: 0.0,
+            altitude: 100.0,
+        });
+        assert!((equator[0] - (WGS84_A + 100.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_enu_to_geodetic_known_offsets() {
+        // 真上は緯度経度が変わらず高度のみ増える
+        let up = enu_to_geodetic(&[0.0, 0.0, 1000.0], &origin());
+        assert!((up.latitude - 35.0).abs() < 1e-9);
+        assert!((up.longitude - 139.0).abs() < 1e-9);
+        assert!((up.altitude - 1050.0).abs() < 1e-6);
+
+        // 北へ 1 km は子午線曲率半径 M ≈ 6.3575e6 m で約 0.00901 度
+        let north = enu_to_geodetic(&[0.0, 1000.0, 0.0], &origin());
+        let m = WGS84_A * (1.0 - WGS84_E2) / (1.0 - WGS84_E2 * 35.0_f64.to_radians().sin().powi(2)).powf(1.5);
+        assert!((north.latitude - 35.0 - (1000.0 / m).to_degrees()).abs() < 1e-6);
+        assert!((north.longitude - 139.0).abs() < 1e-9);
+
+        // 往復変換
+        let enu = [12345.0, -6789.0, 3000.0];
+        let back = geodetic_to_enu(&enu_to_geodetic(&enu, &origin()), &origin());
+        for k in 0..3 {
+            assert!((back[k] - enu[k]).abs() < 1e-6);
+        }
+    }
+}
@@ -1,9 +1,60 @@
 // src/math/integrator.rs
 
+use serde::{Deserialize, Serialize};
+
 use crate::math::error::MathError;
 
+/// 積分方式（シナリオの `integrator: "rk4"` などで指定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegratorKind {
+    /// 前進 Euler 法
+    Euler,
+    /// Adams-Bashforth 2段法（既定、状態は `AdamsBashforth2State` に保持する）
+    #[default]
+    Ab2,
+    /// 4次 Runge-Kutta 法
+    Rk4,
+}
+
+/// 常微分方程式 dy/dt = f(y) を1ステップ進める積分器
+pub trait Integrator {
+    /// # 引数
+    /// - `y`: 現在の値
+    /// - `f`: 導関数 f(y)
+    /// - `dt`: 時間刻み
+    ///
+    /// # 戻り値
+    /// - 次の値
+    fn step(&mut self, y: f64, f: &dyn Fn(f64) -> f64, dt: f64) -> f64;
+}
+
+/// 前進 Euler 法: y_next = y + f(y) dt
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Euler;
+
+impl Integrator for Euler {
+    fn step(&mut self, y: f64, f: &dyn Fn(f64) -> f64, dt: f64) -> f64 {
+        y + f(y) * dt
+    }
+}
+
+/// 4次 Runge-Kutta 法
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RungeKutta4;
+
+impl Integrator for RungeKutta4 {
+    fn step(&mut self, y: f64, f: &dyn Fn(f64) -> f64, dt: f64) -> f64 {
+        let k1 = f(y);
+        let k2 = f(y + 0.5 * dt * k1);
+        let k3 = f(y + 0.5 * dt * k2);
+        let k4 = f(y + dt * k3);
+        y + dt / 6.0 * (k1 + 2.0 * k2 + 2.0 * k3 + k4)
+    }
+}
+
 /// Adams-Bashforth 2段法の積分器の状態
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct AdamsBashforth2State {
     pub prev_f: Option<f64>,
 }
@@ -18,11 +69,16 @@ pub struct AdamsBashforth2State {
 /// # 戻り値
 /// - 更新後の積分器の状態
 /// - 次のyの値
+/// - `current_y` または `current_f` が NaN/無限大の場合は `MathError::NonFinite`
 pub fn adams_bashforth_2(
     state: AdamsBashforth2State,
     current_y: f64,
     current_f: f64,
 ) -> Result<(AdamsBashforth2State, f64), MathError> {
+    // 発散した値を後段へ伝播させず、数値計算の境界でエラーとして検出する
+    if !current_y.is_finite() || !current_f.is_finite() {
+        return Err(MathError::NonFinite);
+    }
     match state.prev_f {
         Some(prev_f) => {
             let y_next = current_y + (0.1 / 2.0) * (3.0 * current_f - prev_f);
@@ -92,4 +148,41 @@ mod tests {
         
         assert!(result.is_ok());
     }
+
+    /// test_adams_bashforth_2_non_finite
+    /// NaN の加速度や無限大の y を与えると NonFinite エラーとなり、有限値では従来通り成功します。
+    #[test]
+    fn test_adams_bashforth_2_non_finite() {
+        let state = AdamsBashforth2State { prev_f: Some(1.0) };
+        assert!(matches!(
+            adams_bashforth_2(state.clone(), 1.0, f64::NAN),
+            Err(MathError::NonFinite)
+        ));
+        assert!(matches!(
+            adams_bashforth_2(AdamsBashforth2State { prev_f: None }, f64::INFINITY, 1.0),
+            Err(MathError::NonFinite)
+        ));
+        let (next_state, y) = adams_bashforth_2(state, 1.0, 3.0).unwrap();
+        assert_eq!(next_state.prev_f, Some(3.0));
+        assert!((y - (1.0 + 0.05 * (9.0 - 1.0))).abs() < 1e-12);
+    }
+
+    /// dy/dt = -y を1ステップ積分すると、RK4 は解析解 exp(-dt) に Euler より近い
+    #[test]
+    fn test_euler_and_rk4_against_exponential_decay() {
+        let f = |y: f64| -y;
+        let dt: f64 = 0.5;
+        let exact = (-dt).exp();
+        let euler = Euler.step(1.0, &f, dt);
+        let rk4 = RungeKutta4.step(1.0, &f, dt);
+        assert_eq!(euler, 0.5);
+        assert!((rk4 - exact).abs() < 1e-3);
+        assert!((rk4 - exact).abs() < (euler - exact).abs());
+    }
+
+    #[test]
+    fn test_integrator_kind_from_yaml() {
+        let kinds: Vec<IntegratorKind> = serde_yaml::from_str("[euler, ab2, rk4]").unwrap();
+        assert_eq!(kinds, vec![IntegratorKind::Euler, IntegratorKind::Ab2, IntegratorKind::Rk4]);
+    }
 }
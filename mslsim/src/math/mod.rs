@@ -3,9 +3,18 @@
 pub mod integrator;
 pub mod filter;
 pub mod error;
+pub mod random;
+pub mod vector;
+pub mod richardson;
+pub mod geodetic;
 
 pub use integrator::adams_bashforth_2;
 pub use integrator::AdamsBashforth2State;
-pub use filter::low_pass_filter;
+pub use integrator::{Euler, Integrator, IntegratorKind, RungeKutta4};
+pub use filter::{low_pass_filter, low_pass_filter_states, validate_alpha};
 pub use filter::LowPassFilterState;
+pub use random::SeededRng;
+pub use vector::Vec3;
+pub use richardson::{richardson_error, RichardsonEstimate};
+pub use geodetic::{enu_to_geodetic, geodetic_to_enu, GeodeticPosition};
 
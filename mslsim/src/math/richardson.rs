@@ -0,0 +1,79 @@
+// src/math/richardson.rs
+
+/// リチャードソン補外による誤差推定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichardsonEstimate {
+    pub error: f64,    // 刻み dt/2 の解に含まれる誤差の推定値
+    pub improved: f64, // 補外により改善された解
+}
+
+/// リチャードソン補外による積分誤差の推定
+///
+/// 刻み `dt` と `dt/2` で同じ区間を積分し、精度次数 `order` (p) を用いて
+/// E = (y_{dt/2} - y_{dt}) / (2^p - 1)、改善値 = y_{dt/2} + E を求める。
+///
+/// # 引数
+/// - `step_fn`: 初期値と刻み幅を受け取り、固定区間を積分した結果を返す関数
+/// - `y0`: 初期値
+/// - `dt`: 刻み幅
+/// - `order`: 積分法の精度次数
+///
+/// # 戻り値
+/// - 誤差の推定値と改善された解
+pub fn richardson_error<F>(mut step_fn: F, y0: f64, dt: f64, order: u32) -> RichardsonEstimate
+where
+    F: FnMut(f64, f64) -> f64,
+{
+    let y_full = step_fn(y0, dt);
+    let y_half = step_fn(y0, dt / 2.0);
+    let error = (y_half - y_full) / (2f64.powi(order as i32) - 1.0);
+    RichardsonEstimate {
+        error,
+        improved: y_half + error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// dy/dt = -y を区間 [0, 1] で Adams-Bashforth 2段法（初回は Euler 法）により積分する
+    fn integrate_ab2(y0: f64, dt: f64) -> f64 {
+        let steps = (1.0 / dt).round() as usize;
+        let mut y = y0;
+        let mut prev_f: Option<f64> = None;
+        for _ in 0..steps {
+            let f = -y;
+            y += match prev_f {
+                Some(pf) => dt / 2.0 * (3.0 * f - pf),
+                None => dt * f,
+            };
+            prev_f = Some(f);
+        }
+        y
+    }
+
+    #[test]
+    fn test_richardson_error_ab2_is_second_order() {
+        let exact = (-1.0f64).exp();
+        let coarse = richardson_error(integrate_ab2, 1.0, 0.02, 2);
+        let fine = richardson_error(integrate_ab2, 1.0, 0.01, 2);
+
+        // 刻みを半分にすると推定誤差は約 1/4 になる
+        let ratio = coarse.error / fine.error;
+        assert!((3.5..4.5).contains(&ratio), "ratio = {}", ratio);
+
+        // 推定誤差は実際の誤差をよく近似し、補外値はより正確になる
+        let actual_error = exact - integrate_ab2(1.0, 0.005);
+        assert!((fine.error - actual_error).abs() < 0.1 * actual_error.abs());
+        assert!((fine.improved - exact).abs() < actual_error.abs());
+    }
+
+    #[test]
+    fn test_richardson_error_exact_integrator_is_zero() {
+        // 解析解を返す関数では誤差推定は0になる
+        let estimate = richardson_error(|y0, _dt| y0 * (-1.0f64).exp(), 1.0, 0.1, 2);
+        assert_eq!(estimate.error, 0.0);
+        assert_eq!(estimate.improved, (-1.0f64).exp());
+    }
+}
@@ -0,0 +1,408 @@
+// src/models/force.rs
+
+use crate::config::MissileParameters;
+use crate::math::Vec3;
+use crate::models::gravity::GravityModel;
+use crate::models::motion::{standard_atmosphere_density, VACUUM_DENSITY_THRESHOLD};
+use crate::Missile;
+
+/// 力モデルの評価に使う環境
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForceEnvironment {
+    pub air_density: f64,  // 大気密度（kg/m³）
+    pub thrust: [f64; 3], // このステップの推進力ベクトル（推力ノイズ・倍率適用後）[Fx, Fy, Fz]
+    pub drag_scale: f64,  // 空気抵抗の倍率（感度解析用、通常は 1.0）
+}
+
+impl ForceEnvironment {
+    /// ミサイルの高度から大気密度を求めて環境を作成する
+    ///
+    /// # 引数
+    /// - `missile`: ミサイルのデータ
+    /// - `thrust`: このステップの推進力ベクトル
+    ///
+    /// # 戻り値
+    /// - 力モデルの評価環境
+    pub fn at(missile: &Missile, thrust: [f64; 3]) -> Self {
+        Self {
+            air_density: standard_atmosphere_density(missile.position[2].max(0.0)),
+            thrust,
+            drag_scale: 1.0,
+        }
+    }
+}
+
+/// ミサイルごとの力の倍率
+///
+/// 基本パラメータを変更せずに、エンティティ単位で空気抵抗と推進力を拡大・縮小する（感度解析用）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForceScale {
+    pub drag: f64,   // 空気抵抗の倍率
+    pub thrust: f64, // 推進力の倍率
+}
+
+impl Default for ForceScale {
+    fn default() -> Self {
+        Self { drag: 1.0, thrust: 1.0 }
+    }
+}
+
+/// ミサイルに働く力のモデル
+///
+/// 各モデルは状態と環境から1つの力を返し、`net_force` でパイプライン全体の合計を求める。
+pub trait ForceModel {
+    /// ミサイルに働く力 [Fx, Fy, Fz]（N）
+    fn force(&self, missile: &Missile, environment: &ForceEnvironment) -> [f64; 3];
+}
+
+/// 力モデルのパイプラインによる合計力を計算する
+///
+/// # 引数
+/// - `models`: 力モデルの列
+/// - `missile`: ミサイルのデータ
+/// - `environment`: 評価環境
+///
+/// # 戻り値
+/// - 各モデルの力の合計 [Fx, Fy, Fz]
+pub fn net_force(models: &[Box<dyn ForceModel>], missile: &Missile, environment: &ForceEnvironment) -> [f64; 3] {
+    models
+        .iter()
+        .fold(Vec3::default(), |sum, model| sum + Vec3::from(model.force(missile, environment)))
+        .into()
+}
+
+/// 既定のパイプライン（推進力 + 空気抵抗 + 重力）
+///
+/// 推進力・空気抵抗・重力をそれぞれ `calculate_thrust`・`calculate_drag_force`・`GravityModel::force` で求めた和になる。
+///
+/// # 引数
+/// - `missile_params`: ミサイルのパラメータ
+/// - `gravity`: 重力モデル
+///
+/// # 戻り値
+/// - 力モデルの列
+pub fn default_force_models(missile_params: &MissileParameters, gravity: &GravityModel) -> Vec<Box<dyn ForceModel>> {
+    vec![
+        Box::new(Thrust),
+        Box::new(Drag {
+            drag_coefficient: missile_params.drag_coefficient,
+            area: missile_params.area,
+            base_drag_delta: missile_params.base_drag_delta,
+        }),
+        Box::new(Gravity(*gravity)),
+    ]
+}
+
+/// 推進力（環境の推進力ベクトルをそのまま返す）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thrust;
+
+impl ForceModel for Thrust {
+    fn force(&self, _missile: &Missile, environment: &ForceEnvironment) -> [f64; 3] {
+        environment.thrust
+    }
+}
+
+/// 空気抵抗（大気密度が `VACUUM_DENSITY_THRESHOLD` 未満では0、環境の `drag_scale` 倍）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drag {
+    pub drag_coefficient: f64,
+    pub area: f64, // 空気抵抗面積（m²）
+    pub base_drag_delta: f64, // 推力ゼロのときに抗力係数へ加える底面抗力の増分
+}
+
+impl Drag {
+    /// 大気に対する速度 `airspeed` で受ける空気抵抗
+    ///
+    /// # 引数
+    /// - `airspeed`: 対気速度ベクトル [vx, vy, vz]（m/s）
+    /// - `environment`: 評価環境
+    ///
+    /// # 戻り値
+    /// - 空気抵抗力ベクトル [Fx, Fy, Fz]
+    pub fn force_at_airspeed(&self, airspeed: &[f64; 3], environment: &ForceEnvironment) -> [f64; 3] {
+        if environment.air_density < VACUUM_DENSITY_THRESHOLD {
+            return [0.0, 0.0, 0.0];
+        }
+        crate::models::missile::calculate_drag_force(
+            airspeed,
+            environment.air_density,
+            self.drag_coefficient * environment.drag_scale,
+            self.area,
+            &environment.thrust,
+            self.base_drag_delta * environment.drag_scale,
+        )
+    }
+}
+
+impl ForceModel for Drag {
+    fn force(&self, missile: &Missile, environment: &ForceEnvironment) -> [f64; 3] {
+        self.force_at_airspeed(&missile.velocity, environment)
+    }
+}
+
+/// 重力
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gravity(pub GravityModel);
+
+impl ForceModel for Gravity {
+    fn force(&self, missile: &Missile, _environment: &ForceEnvironment) -> [f64; 3] {
+        self.0.force(missile.mass)
+    }
+}
+
+/// 揚力
+///
+/// 大きさは 0.5 ρ |v|² C_L A。向きは速度に垂直で、鉛直上向き成分を持つ方向。
+/// 速度が0または鉛直の場合は0とする。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lift {
+    pub lift_coefficient: f64,
+    pub area: f64, // 揚力面積（m²）
+}
+
+impl ForceModel for Lift {
+    fn force(&self, missile: &Missile, environment: &ForceEnvironment) -> [f64; 3] {
+        let velocity = Vec3::from(missile.velocity);
+        let speed = velocity.norm();
+        if speed == 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+        // 上向き単位ベクトルから速度方向成分を除いた向き
+        let v_hat = velocity * (1.0 / speed);
+        let up = Vec3::new(0.0, 0.0, 1.0);
+        let perpendicular = up - v_hat * v_hat.dot(&up);
+        let norm = perpendicular.norm();
+        if norm < 1e-9 {
+            return [0.0, 0.0, 0.0];
+        }
+        let magnitude = 0.5 * environment.air_density * speed.powi(2) * self.lift_coefficient * self.area;
+        (perpendicular * (magnitude / norm)).into()
+    }
+}
+
+/// コリオリ力 F = -2 m (Ω × v)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coriolis {
+    pub angular_velocity: [f64; 3], // 座標系の角速度ベクトル Ω（rad/s）
+}
+
+impl ForceModel for Coriolis {
+    fn force(&self, missile: &Missile, _environment: &ForceEnvironment) -> [f64; 3] {
+        let omega_cross_v = Vec3::from(self.angular_velocity).cross(&Vec3::from(missile.velocity));
+        (omega_cross_v * (-2.0 * missile.mass)).into()
+    }
+}
+
+/// 風による空気抵抗の補正
+///
+/// 対気速度 (v - 風速) に対する空気抵抗と、対地速度に対する空気抵抗の差を返す。
+/// 空気抵抗のパラメータはパイプラインの `Drag` と共有し、
+/// 組み合わせると合計が対気速度に対する空気抵抗になる。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wind {
+    pub velocity: [f64; 3], // 風速ベクトル（m/s）
+    pub drag: Drag,         // 補正に使う空気抵抗のモデル
+}
+
+impl ForceModel for Wind {
+    fn force(&self, missile: &Missile, environment: &ForceEnvironment) -> [f64; 3] {
+        let airspeed: [f64; 3] = (Vec3::from(missile.velocity) - Vec3::from(self.velocity)).into();
+        let relative = Vec3::from(self.drag.force_at_airspeed(&airspeed, environment));
+        (relative - Vec3::from(self.drag.force(missile, environment))).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::missile::{calculate_drag_force, calculate_net_force};
+
+    /// 推進力・空気抵抗・重力を個別の関数で求めた合計力（パイプラインの検証用）
+    fn fixed_force_sum(missile: &Missile, params: &MissileParameters, thrust: &[f64; 3], gravity: &GravityModel) -> [f64; 3] {
+        let drag = calculate_drag_force(
+            &missile.velocity,
+            standard_atmosphere_density(missile.position[2].max(0.0)),
+            params.drag_coefficient,
+            params.area,
+            thrust,
+            params.base_drag_delta,
+        );
+        calculate_net_force(thrust, &drag, &gravity.force(missile.mass))
+    }
+
+    fn missile_params() -> MissileParameters {
+        MissileParameters {
+            mass_initial: 5000.0,
+            fuel_consumption_rate: 10.0,
+            drag_coefficient: 0.3,
+            area: 1.0,
+            thrust: [5000.0, 0.0, 2000.0],
+            ..Default::default()
+        }
+    }
+
+    fn missile() -> Missile {
+        Missile {
+            id: "missile1".to_string(),
+            position: [0.0, 0.0, 1000.0],
+            velocity: [300.0, 0.0, 100.0],
+            pitch: 45.0,
+            pitch_rate: 0.0,
+            mass: 5000.0,
+            thrust: [5000.0, 0.0, 2000.0],
+            metadata: Default::default(),
+        }
+    }
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        for k in 0..3 {
+            assert!((a[k] - b[k]).abs() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_default_pipeline_matches_fixed_force_sum() {
+        let missile = missile();
+        let gravity = GravityModel::default();
+        let environment = ForceEnvironment::at(&missile, missile.thrust);
+        let models = default_force_models(&missile_params(), &gravity);
+        assert_close(
+            net_force(&models, &missile, &environment),
+            fixed_force_sum(&missile, &missile_params(), &missile.thrust, &gravity),
+        );
+    }
+
+    #[test]
+    fn test_pipeline_sums_individual_contributions() {
+        let missile = missile();
+        // 大気密度 1.0、推力 [5000, 0, 2000] の環境
+        let environment = ForceEnvironment {
+            air_density: 1.0,
+            thrust: missile.thrust,
+            drag_scale: 1.0,
+        };
+        let drag = Drag { drag_coefficient: 0.3, area: 1.0, base_drag_delta: 0.0 };
+        let models: Vec<Box<dyn ForceModel>> = vec![
+            Box::new(Thrust),
+            Box::new(drag),
+            Box::new(Gravity(GravityModel::default())),
+            Box::new(Lift { lift_coefficient: 0.5, area: 2.0 }),
+            Box::new(Coriolis { angular_velocity: [0.0, 0.0, 7.29e-5] }),
+            Box::new(Wind { velocity: [-20.0, 10.0, 0.0], drag }),
+        ];
+
+        // 各寄与を力モデルとは独立に計算する（v = [300, 0, 100]、|v|² = 100000）
+        let quadratic_drag = |v: [f64; 3]| {
+            let speed = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+            [-0.15 * speed * v[0], -0.15 * speed * v[1], -0.15 * speed * v[2]]
+        };
+        let air_drag = quadratic_drag([320.0, -10.0, 100.0]);
+        let ground_drag = quadratic_drag(missile.velocity);
+        // 揚力 0.5 * 1.0 * 100000 * 0.5 * 2.0 = 50000 N を速度に垂直な上向き [-1, 0, 3] / √10 に
+        let lift = 50000.0 / 10f64.sqrt();
+        let contributions = [
+            [5000.0, 0.0, 2000.0],
+            ground_drag,
+            [0.0, 0.0, -5000.0 * crate::models::gravity::STANDARD_GRAVITY],
+            [-lift, 0.0, 3.0 * lift],
+            [0.0, -2.0 * 5000.0 * 7.29e-5 * 300.0, 0.0],
+            [air_drag[0] - ground_drag[0], air_drag[1] - ground_drag[1], air_drag[2] - ground_drag[2]],
+        ];
+        for (model, expected) in models.iter().zip(&contributions) {
+            assert_close(model.force(&missile, &environment), *expected);
+        }
+        let expected = contributions
+            .iter()
+            .fold([0.0; 3], |sum, f| [sum[0] + f[0], sum[1] + f[1], sum[2] + f[2]]);
+        assert_close(net_force(&models, &missile, &environment), expected);
+        assert_eq!(net_force(&[], &missile, &environment), [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_lift_is_perpendicular_to_velocity_and_upward() {
+        let missile = missile();
+        let environment = ForceEnvironment::at(&missile, [0.0; 3]);
+        let lift = Lift { lift_coefficient: 0.5, area: 2.0 }.force(&missile, &environment);
+        let v = missile.velocity;
+        assert!((lift[0] * v[0] + lift[1] * v[1] + lift[2] * v[2]).abs() < 1e-6);
+        assert!(lift[2] > 0.0);
+    }
+
+    #[test]
+    fn test_coriolis_and_wind_directions() {
+        let missile = missile();
+        let environment = ForceEnvironment::at(&missile, [0.0; 3]);
+        // Ω = +z、v = +x のとき -2m(Ω × v) は -y 方向
+        let coriolis = Coriolis { angular_velocity: [0.0, 0.0, 1e-4] }.force(&missile, &environment);
+        assert!(coriolis[1] < 0.0);
+        assert_eq!(coriolis[0], 0.0);
+
+        // 追い風は抵抗を減らす（+x 方向の補正）
+        let drag = Drag { drag_coefficient: 0.3, area: 1.0, base_drag_delta: 0.0 };
+        let tailwind = Wind { velocity: [50.0, 0.0, 0.0], drag };
+        assert!(tailwind.force(&missile, &environment)[0] > 0.0);
+        let calm = Wind { velocity: [0.0; 3], drag };
+        assert_eq!(calm.force(&missile, &environment), [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_drag_scale_multiplies_drag_exactly() {
+        let missile = missile();
+        let drag = Drag { drag_coefficient: 0.3, area: 1.0, base_drag_delta: 0.0 };
+        let nominal = ForceEnvironment::at(&missile, missile.thrust);
+        let scaled = ForceEnvironment { drag_scale: 1.5, ..nominal };
+        let base = drag.force(&missile, &nominal);
+        assert_close(drag.force(&missile, &scaled), [base[0] * 1.5, base[1] * 1.5, base[2] * 1.5]);
+        // 既定の倍率は 1.0 で、推進力・重力には影響しない
+        assert_eq!(nominal.drag_scale, 1.0);
+        assert_eq!(Thrust.force(&missile, &scaled), missile.thrust);
+    }
+
+    #[test]
+    fn test_base_drag_delta_after_burnout_matches_fixed_force_sum() {
+        let params = MissileParameters {
+            base_drag_delta: 0.15,
+            ..missile_params()
+        };
+        let missile = missile();
+        let gravity = GravityModel::default();
+        let models = default_force_models(&params, &gravity);
+        let drag = Drag {
+            drag_coefficient: params.drag_coefficient,
+            area: params.area,
+            base_drag_delta: params.base_drag_delta,
+        };
+        // 推力ゼロ（燃焼終了後）の抗力は推力飛行中より大きい
+        let boost = drag.force(&missile, &ForceEnvironment::at(&missile, missile.thrust));
+        let coast = drag.force(&missile, &ForceEnvironment::at(&missile, [0.0; 3]));
+        assert!(coast[0] < boost[0] && boost[0] < 0.0);
+        assert!((coast[0] / boost[0] - 0.45 / 0.3).abs() < 1e-9);
+
+        let environment = ForceEnvironment::at(&missile, [0.0; 3]);
+        assert_close(
+            net_force(&models, &missile, &environment),
+            fixed_force_sum(&missile, &params, &[0.0; 3], &gravity),
+        );
+    }
+
+    #[test]
+    fn test_wind_correction_includes_base_drag_after_burnout() {
+        let missile = missile();
+        let coast = ForceEnvironment {
+            air_density: 1.0,
+            thrust: [0.0; 3],
+            drag_scale: 1.0,
+        };
+        let drag = Drag { drag_coefficient: 0.3, area: 1.0, base_drag_delta: 0.15 };
+        let models: Vec<Box<dyn ForceModel>> = vec![
+            Box::new(drag),
+            Box::new(Wind { velocity: [-20.0, 10.0, 0.0], drag }),
+        ];
+        // 燃焼終了後の合計は、抗力係数 0.3 + 0.15 での対気速度 [320, -10, 100] に対する空気抵抗
+        let airspeed: [f64; 3] = [320.0, -10.0, 100.0];
+        let speed = (airspeed[0] * airspeed[0] + airspeed[1] * airspeed[1] + airspeed[2] * airspeed[2]).sqrt();
+        let k = 0.5 * 0.45 * speed;
+        assert_close(net_force(&models, &missile, &coast), [-k * airspeed[0], -k * airspeed[1], -k * airspeed[2]]);
+    }
+}
@@ -0,0 +1,64 @@
+// src/models/gravity.rs
+
+/// 標準重力加速度（m/s²）
+pub const STANDARD_GRAVITY: f64 = 9.81;
+
+/// 重力モデル
+///
+/// 一定の重力加速度ベクトルを保持する。通常は -z 方向だが、
+/// 回転した座標系での試験用に任意の方向を設定できる。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GravityModel {
+    pub acceleration: [f64; 3], // 重力加速度ベクトル（m/s²）
+}
+
+impl GravityModel {
+    /// 任意の一定重力加速度ベクトルで作成
+    pub fn constant(acceleration: [f64; 3]) -> Self {
+        Self { acceleration }
+    }
+
+    /// 質量に働く重力を計算する
+    ///
+    /// # 引数
+    /// - `mass`: 質量（kg）
+    ///
+    /// # 戻り値
+    /// - 重力ベクトル [Fx, Fy, Fz]（N）
+    pub fn force(&self, mass: f64) -> [f64; 3] {
+        [
+            mass * self.acceleration[0],
+            mass * self.acceleration[1],
+            mass * self.acceleration[2],
+        ]
+    }
+}
+
+impl Default for GravityModel {
+    /// -z 方向の標準重力
+    fn default() -> Self {
+        Self::constant([0.0, 0.0, -STANDARD_GRAVITY])
+    }
+}
+
+impl From<[f64; 3]> for GravityModel {
+    fn from(acceleration: [f64; 3]) -> Self {
+        Self::constant(acceleration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_points_down() {
+        assert_eq!(GravityModel::default().force(2.0), [0.0, 0.0, -2.0 * STANDARD_GRAVITY]);
+    }
+
+    #[test]
+    fn test_arbitrary_vector_force() {
+        let gravity = GravityModel::constant([3.0, -4.0, 0.0]);
+        assert_eq!(gravity.force(10.0), [30.0, -40.0, 0.0]);
+    }
+}
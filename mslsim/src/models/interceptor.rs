@@ -1,6 +1,10 @@
 // src/models/interceptor.rs
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::math::error::MathError;
+use crate::math::Vec3;
 
 /// 迎撃ミサイルの構造体
 #[derive(Debug, Clone, PartialEq)]
@@ -10,6 +14,7 @@ pub struct Interceptor {
     pub velocity: [f64; 3], // [vx, vy, vz] 速度
     pub pitch: f64,         // ピッチ角（度）
     pub mass: f64,          // 質量（kg）
+    pub metadata: Arc<HashMap<String, String>>, // 可視化用の任意属性（陣営、種別など）。ステップ間で共有し複製しない
 }
 
 /// 迎撃ミサイルの状態を更新する純粋な関数
@@ -25,58 +30,42 @@ pub struct Interceptor {
 /// - 更新後の迎撃ミサイルのデータ
 pub fn update_interceptor(
     interceptor: &Interceptor,
-    target_position: &[f64; 3],
-    target_velocity: &[f64; 3],
+    target_position: impl Into<Vec3>,
+    target_velocity: impl Into<Vec3>,
     navigation_coefficient: f64,
     dt: f64,
 ) -> Result<Interceptor, MathError> {
+    let position = Vec3::from(interceptor.position);
+    let velocity = Vec3::from(interceptor.velocity);
+
     // 相対位置と相対速度の計算
-    let rel_position = [
-        target_position[0] - interceptor.position[0],
-        target_position[1] - interceptor.position[1],
-        target_position[2] - interceptor.position[2],
-    ];
-    let rel_velocity = [
-        target_velocity[0] - interceptor.velocity[0],
-        target_velocity[1] - interceptor.velocity[1],
-        target_velocity[2] - interceptor.velocity[2],
-    ];
-
-    let distance = (rel_position[0].powi(2) + rel_position[1].powi(2) + rel_position[2].powi(2)).sqrt();
+    let rel_position = target_position.into() - position;
+    let rel_velocity = target_velocity.into() - velocity;
+
+    let distance = rel_position.norm();
     if distance < 1e-6 {
         return Err(MathError::Atan2ZeroInput);
     }
 
     // 誘導加速度の計算（比例航法）
-    let a_c = [
-        navigation_coefficient * rel_velocity[0] / distance,
-        navigation_coefficient * rel_velocity[1] / distance,
-        navigation_coefficient * rel_velocity[2] / distance,
-    ];
+    let a_c = rel_velocity * (navigation_coefficient / distance);
 
     // 新しい速度の計算
-    let new_velocity = [
-        interceptor.velocity[0] + a_c[0] * dt,
-        interceptor.velocity[1] + a_c[1] * dt,
-        interceptor.velocity[2] + a_c[2] * dt,
-    ];
+    let new_velocity = velocity + a_c * dt;
 
     // 新しい位置の計算
-    let new_position = [
-        interceptor.position[0] + new_velocity[0] * dt,
-        interceptor.position[1] + new_velocity[1] * dt,
-        interceptor.position[2] + new_velocity[2] * dt,
-    ];
+    let new_position = position + new_velocity * dt;
 
     // ピッチ角の更新（簡略化）
     let new_pitch = interceptor.pitch; // 実際のピッチ角更新は推進力や重力に基づく計算が必要
 
     Ok(Interceptor {
         id: interceptor.id.clone(),
-        position: new_position,
-        velocity: new_velocity,
+        position: new_position.into(),
+        velocity: new_velocity.into(),
         pitch: new_pitch,
         mass: interceptor.mass, // 質量変化があれば更新
+        metadata: Arc::clone(&interceptor.metadata),
     })
 }
 
@@ -92,6 +81,7 @@ mod tests {
             velocity: [0.0, 0.0, 0.0],
             pitch: 0.0,
             mass: 2000.0,
+            metadata: Default::default(),
         };
 
         let target_position = [100.0, 0.0, 0.0];
@@ -99,7 +89,7 @@ mod tests {
         let navigation_coefficient = 3.0;
         let dt = 0.1;
 
-        let updated = update_interceptor(&interceptor, &target_position, &target_velocity, navigation_coefficient, dt).unwrap();
+        let updated = update_interceptor(&interceptor, target_position, target_velocity, navigation_coefficient, dt).unwrap();
 
         // 相対位置: [100, 0, 0]
         // 相対速度: [10, 0, 0]
@@ -117,6 +107,32 @@ mod tests {
         assert_eq!(updated.mass, 2000.0);
     }
 
+    /// Vec3 と配列のどちらを渡しても同じ結果になることを確認
+    #[test]
+    fn test_update_interceptor_vec3_and_array_inputs_match() {
+        let interceptor = Interceptor {
+            id: "interceptor1".to_string(),
+            position: [0.0, 0.0, 0.0],
+            velocity: [10.0, 0.0, 0.0],
+            pitch: 0.0,
+            mass: 2000.0,
+            metadata: Default::default(),
+        };
+
+        let from_arrays =
+            update_interceptor(&interceptor, [100.0, 50.0, 20.0], [-10.0, 5.0, 0.0], 3.0, 0.1).unwrap();
+        let from_vec3 = update_interceptor(
+            &interceptor,
+            Vec3::new(100.0, 50.0, 20.0),
+            Vec3::new(-10.0, 5.0, 0.0),
+            3.0,
+            0.1,
+        )
+        .unwrap();
+
+        assert_eq!(from_arrays, from_vec3);
+    }
+
     #[test]
     fn test_update_interceptor_zero_distance() {
         let interceptor = Interceptor {
@@ -125,6 +141,7 @@ mod tests {
             velocity: [0.0, 0.0, 0.0],
             pitch: 0.0,
             mass: 2000.0,
+            metadata: Default::default(),
         };
 
         let target_position = [0.0, 0.0, 0.0];
@@ -132,7 +149,7 @@ mod tests {
         let navigation_coefficient = 3.0;
         let dt = 0.1;
 
-        let result = update_interceptor(&interceptor, &target_position, &target_velocity, navigation_coefficient, dt);
+        let result = update_interceptor(&interceptor, target_position, target_velocity, navigation_coefficient, dt);
 
         assert!(result.is_err());
         match result {
@@ -1,6 +1,9 @@
 // src/models/missile.rs
 
+use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::math::{SeededRng, Vec3};
 
 /// ミサイルの構造体
 #[derive(Debug, Clone, PartialEq)]
@@ -9,7 +12,10 @@ pub struct Missile {
     pub position: [f64; 3], // [x, y, z] 座標
     pub velocity: [f64; 3], // [vx, vy, vz] 速度
     pub pitch: f64,         // ピッチ角（度）
+    pub pitch_rate: f64,    // ピッチ角速度（度/s）
     pub mass: f64,          // 質量（kg）
+    pub thrust: [f64; 3],   // 推進力ベクトル [Fx, Fy, Fz]（N）
+    pub metadata: Arc<HashMap<String, String>>, // 可視化用の任意属性（陣営、種別など）。ステップ間で共有し複製しない
 }
 
 /// ミサイルのパラメータ構造体
@@ -22,13 +28,85 @@ pub struct MissileParameters {
     pub mass_initial: f64,           // 追加
 }
 
+/// ミサイルの飛行段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlightPhase {
+    /// 推力飛行中
+    Boost,
+    /// 燃焼終了後、頂点に達するまで
+    Coast,
+    /// 頂点通過後の降下
+    Descent,
+}
+
+impl FlightPhase {
+    /// CSV出力用のラベル
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlightPhase::Boost => "boost",
+            FlightPhase::Coast => "coast",
+            FlightPhase::Descent => "descent",
+        }
+    }
+}
+
+/// ミサイルが推力を発生しているかを判定する
+///
+/// 推進力ベクトルがゼロでなく、質量が燃焼終了質量 `burnout_mass` を上回る間を推力飛行とする。
+///
+/// # 引数
+/// - `missile`: ミサイルのデータ
+/// - `params`: ミサイルのパラメータ
+///
+/// # 戻り値
+/// - 推力飛行中なら true
+pub fn is_thrusting(missile: &Missile, params: &crate::config::MissileParameters) -> bool {
+    missile.thrust.iter().any(|&f| f != 0.0) && missile.mass > params.burnout_mass
+}
+
+/// ミサイルの飛行段階を1ステップ分更新する
+///
+/// 推力飛行中は Boost。燃焼終了後は、鉛直速度 vz が正から0以下に変わった（頂点を通過した）時点、
+/// または vz が負（燃焼終了時点で既に頂点を過ぎている）の場合に Descent とし、以後は Descent のまま維持する。
+/// それ以外（vz が正、または水平飛行で vz が0のまま）は Coast とする。
+/// 直前の状態と段階だけから判定するため、履歴を保持せずにステップごとに更新できる。
+///
+/// # 引数
+/// - `missile`: 現在のミサイルの状態
+/// - `params`: ミサイルのパラメータ
+/// - `previous`: 前ステップのミサイルの状態と飛行段階（初期状態では `None`）
+///
+/// # 戻り値
+/// - 飛行段階
+pub fn flight_phase(
+    missile: &Missile,
+    params: &crate::config::MissileParameters,
+    previous: Option<(&Missile, FlightPhase)>,
+) -> FlightPhase {
+    if is_thrusting(missile, params) {
+        return FlightPhase::Boost;
+    }
+    let vz = missile.velocity[2];
+    match previous {
+        Some((_, FlightPhase::Descent)) => FlightPhase::Descent,
+        _ if vz < 0.0 => FlightPhase::Descent,
+        Some((previous, _)) if previous.velocity[2] > 0.0 && vz <= 0.0 => FlightPhase::Descent,
+        _ => FlightPhase::Coast,
+    }
+}
+
 /// 空気抵抗力を計算する純粋関数
 ///
+/// 推進力がゼロ（燃焼終了後の慣性飛行）の間は、噴流が底面を満たさなくなることによる
+/// 底面抗力の増加として、抗力係数に `base_drag_delta` を加える。
+///
 /// # 引数
 /// - `velocity`: ミサイルの速度ベクトル [vx, vy, vz]
 /// - `air_density`: 大気密度（kg/m³）
 /// - `drag_coefficient`: 空気抵抗係数
 /// - `area`: 空気抵抗面積（m²）
+/// - `thrust`: 推進力ベクトル [Fx, Fy, Fz]
+/// - `base_drag_delta`: 推力ゼロのときに抗力係数へ加える増分
 ///
 /// # 戻り値
 /// - 空気抵抗力ベクトル [Fx, Fy, Fz]
@@ -37,11 +115,18 @@ pub fn calculate_drag_force(
     air_density: f64,
     drag_coefficient: f64,
     area: f64,
+    thrust: &[f64; 3],
+    base_drag_delta: f64,
 ) -> [f64; 3] {
     let speed = (velocity[0].powi(2) + velocity[1].powi(2) + velocity[2].powi(2)).sqrt();
     if speed == 0.0 {
         return [0.0, 0.0, 0.0];
     }
+    let drag_coefficient = if thrust.iter().all(|&f| f == 0.0) {
+        drag_coefficient + base_drag_delta
+    } else {
+        drag_coefficient
+    };
     let drag_magnitude = 0.5 * air_density * speed.powi(2) * drag_coefficient * area;
     [
         -drag_magnitude * (velocity[0] / speed),
@@ -61,6 +146,46 @@ pub fn calculate_thrust(thrust: &[f64; 3]) -> [f64; 3] {
     [thrust[0], thrust[1], thrust[2]]
 }
 
+/// 乗法ノイズを加えた推進力を計算する
+///
+/// 推力ベクトルに係数 (1 + sigma * n)（n は標準正規乱数）を掛ける。
+/// `sigma` が0以下の場合は乱数を消費せず、公称推力をそのまま返す。
+///
+/// # 引数
+/// - `thrust`: 公称の推進力ベクトル [Fx, Fy, Fz]
+/// - `sigma`: 乗法ノイズの標準偏差
+/// - `rng`: 乱数生成器
+///
+/// # 戻り値
+/// - ノイズを加えた推進力ベクトル [Fx, Fy, Fz]
+pub fn calculate_thrust_with_noise(thrust: &[f64; 3], sigma: f64, rng: &mut SeededRng) -> [f64; 3] {
+    if sigma <= 0.0 {
+        return calculate_thrust(thrust);
+    }
+    let scale = 1.0 + rng.normal(0.0, sigma);
+    [thrust[0] * scale, thrust[1] * scale, thrust[2] * scale]
+}
+
+/// 推力による重心まわりのモーメントを計算する純粋関数
+///
+/// M = r × F_thrust
+///
+/// 並進は3自由度のまま、y軸まわりの成分をピッチ角の回転運動（`calculate_pitch_acceleration`）へ入力する。
+///
+/// # 引数
+/// - `thrust_offset`: 重心から推力作用点までのオフセット [rx, ry, rz]（m）
+/// - `thrust`: 推進力ベクトル [Fx, Fy, Fz]
+///
+/// # 戻り値
+/// - モーメントベクトル [Mx, My, Mz]（N・m）
+pub fn calculate_thrust_moment(thrust_offset: &[f64; 3], thrust: &[f64; 3]) -> [f64; 3] {
+    [
+        thrust_offset[1] * thrust[2] - thrust_offset[2] * thrust[1],
+        thrust_offset[2] * thrust[0] - thrust_offset[0] * thrust[2],
+        thrust_offset[0] * thrust[1] - thrust_offset[1] * thrust[0],
+    ]
+}
+
 /// 合計力を計算する純粋関数
 ///
 /// # 引数
@@ -106,17 +231,13 @@ pub fn calculate_acceleration(net_force: &[f64; 3], mass: f64) -> [f64; 3] {
 /// - `dt`: 時間ステップ（秒）
 ///
 /// # 戻り値
-/// - 更新後の速度ベクトル [vx, vy, vz]
+/// - 更新後の速度ベクトル
 pub fn _update_velocity(
-    current_velocity: &[f64; 3],
-    acceleration: &[f64; 3],
+    current_velocity: impl Into<Vec3>,
+    acceleration: impl Into<Vec3>,
     dt: f64,
-) -> [f64; 3] {
-    [
-        current_velocity[0] + acceleration[0] * dt,
-        current_velocity[1] + acceleration[1] * dt,
-        current_velocity[2] + acceleration[2] * dt,
-    ]
+) -> Vec3 {
+    current_velocity.into() + acceleration.into() * dt
 }
 
 /// 位置を更新する純粋関数
@@ -127,29 +248,45 @@ pub fn _update_velocity(
 /// - `dt`: 時間ステップ（秒）
 ///
 /// # 戻り値
-/// - 更新後の位置ベクトル [x, y, z]
+/// - 更新後の位置ベクトル
 pub fn update_position(
-    current_position: &[f64; 3],
-    velocity: &[f64; 3],
+    current_position: impl Into<Vec3>,
+    velocity: impl Into<Vec3>,
     dt: f64,
-) -> [f64; 3] {
-    [
-        current_position[0] + velocity[0] * dt,
-        current_position[1] + velocity[1] * dt,
-        current_position[2] + velocity[2] * dt,
-    ]
+) -> Vec3 {
+    current_position.into() + velocity.into() * dt
 }
 
-/// ピッチ角を更新する純粋関数（簡略化）
+/// モーメントによるピッチ角加速度を計算する純粋関数
+///
+/// ピッチ角は機首上げを正とするため、y軸まわりの正のモーメント（+x を -z 側へ回す）は機首下げになる。
+///
+/// # 引数
+/// - `moment`: 重心まわりのモーメント [Mx, My, Mz]（N・m）
+/// - `pitch_inertia`: y軸まわりの慣性モーメント（kg・m²）。0以下の場合は回転しない
+///
+/// # 戻り値
+/// - ピッチ角加速度（度/s²）
+pub fn calculate_pitch_acceleration(moment: &[f64; 3], pitch_inertia: f64) -> f64 {
+    if pitch_inertia <= 0.0 {
+        return 0.0;
+    }
+    (-moment[1] / pitch_inertia).to_degrees()
+}
+
+/// ピッチ角とピッチ角速度を更新する純粋関数（半陰的オイラー法）
 ///
 /// # 引数
 /// - `current_pitch`: 現在のピッチ角（度）
-/// - `new_pitch`: 新しいピッチ角（度）
+/// - `pitch_rate`: 現在のピッチ角速度（度/s）
+/// - `pitch_acceleration`: ピッチ角加速度（度/s²）
+/// - `dt`: 時間ステップ（秒）
 ///
 /// # 戻り値
-/// - 更新後のピッチ角（度）
-pub fn update_pitch(_current_pitch: f64, new_pitch: f64) -> f64 {
-    new_pitch // 実際のロジックに基づいて計算することが望ましい
+/// - 更新後の (ピッチ角（度）, ピッチ角速度（度/s）)
+pub fn update_pitch(current_pitch: f64, pitch_rate: f64, pitch_acceleration: f64, dt: f64) -> (f64, f64) {
+    let new_rate = pitch_rate + pitch_acceleration * dt;
+    (current_pitch + new_rate * dt, new_rate)
 }
 
 /// テスト
@@ -157,9 +294,13 @@ pub fn update_pitch(_current_pitch: f64, new_pitch: f64) -> f64 {
 mod tests {
     use crate::Missile;
     use crate::config::MissileParameters;
-    use crate::math::{AdamsBashforth2State, LowPassFilterState};
+    use crate::math::{low_pass_filter_states, IntegratorKind, SeededRng};
     use crate::simulation::SimulationState;
     use crate::models::motion::update_missiles;
+    use crate::models::gravity::GravityModel;
+    use crate::models::force::ForceScale;
+    use super::{calculate_drag_force, calculate_thrust_moment, update_position, _update_velocity};
+    use crate::math::Vec3;
 
     #[test]
     fn test_update_missiles() {
@@ -169,9 +310,10 @@ mod tests {
             area: 1.0,
             fuel_consumption_rate: 10.0, // kg/s
             mass_initial: 5000.0, // 追加
+            ..Default::default()
         };
 
-        let gravity = [0.0, 0.0, -9.81];
+        let gravity = GravityModel::default();
         let dt = 0.1;
 
         let missile = Missile {
@@ -179,22 +321,23 @@ mod tests {
             position: [0.0, 0.0, 0.0],
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [5000.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
 
-        let integrator = AdamsBashforth2State { prev_f: None };
-        let filter = LowPassFilterState { previous: 0.0 };
-
         let state = SimulationState {
             missiles: vec![missile.clone()],
-            radars: vec![],
-            interceptors: vec![],
-            integrators: vec![integrator.clone()],
-            filters: vec![filter.clone()],
-            interceptor_filters: vec![],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states(missile.velocity)],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
         };
 
-        let updated_state = update_missiles(&state, &missile_params, gravity, dt).unwrap();
+        let updated_state = update_missiles(&state, &missile_params, &gravity, dt).unwrap();
 
         // ミサイルの数が1であることを確認
         assert_eq!(updated_state.0.len(), 1);
@@ -207,4 +350,191 @@ mod tests {
         let updated_missile = &updated_state.0[0];
         assert!(updated_missile.mass < missile.mass); // 燃料が減少していること
     }
+
+    /// 配列と Vec3 のどちらでも位置・速度の更新ができること
+    #[test]
+    fn test_update_position_and_velocity_accept_arrays_and_vec3() {
+        let from_arrays = update_position([1.0, 2.0, 3.0], [10.0, 0.0, -10.0], 0.1);
+        let from_vec3 = update_position(Vec3::new(1.0, 2.0, 3.0), Vec3::new(10.0, 0.0, -10.0), 0.1);
+        assert_eq!(from_arrays, from_vec3);
+        let position: [f64; 3] = from_vec3.into();
+        assert_eq!(position, [2.0, 2.0, 2.0]);
+
+        let velocity = _update_velocity([100.0, 0.0, 0.0], [0.0, 0.0, -9.81], 1.0);
+        assert_eq!(velocity, Vec3::new(100.0, 0.0, -9.81));
+    }
+
+    /// 推力作用点が重心より下（-z）にある場合、前方推力は機首上げ（y軸まわり負）のモーメントを生む
+    #[test]
+    fn test_thrust_moment_offset_below_cg_pitches_up() {
+        let thrust = [5000.0, 0.0, 0.0];
+        let offset = [0.0, 0.0, -0.1];
+
+        let moment = calculate_thrust_moment(&offset, &thrust);
+
+        // M = r × F = [0, rz * Fx, 0] = [0, -500, 0]
+        assert_eq!(moment, [0.0, -500.0, 0.0]);
+        assert!(moment[1] < 0.0);
+    }
+
+    /// 推力作用点が重心より上（+z）にある場合は機首下げのモーメントになる
+    #[test]
+    fn test_thrust_moment_offset_above_cg_pitches_down() {
+        let thrust = [5000.0, 0.0, 0.0];
+        let offset = [0.0, 0.0, 0.1];
+
+        let moment = calculate_thrust_moment(&offset, &thrust);
+
+        assert!(moment[1] > 0.0);
+        assert_eq!(moment[0], 0.0);
+        assert_eq!(moment[2], 0.0);
+    }
+
+    /// 推力軸上のオフセットはモーメントを生まない
+    #[test]
+    fn test_thrust_moment_axial_offset_is_zero() {
+        let thrust = [5000.0, 0.0, 0.0];
+        let offset = [-2.0, 0.0, 0.0];
+
+        let moment = calculate_thrust_moment(&offset, &thrust);
+
+        assert_eq!(moment, [0.0, 0.0, 0.0]);
+    }
+
+    /// 鉛直に打ち上げたロフト軌道で boost → coast → descent と遷移する
+    #[test]
+    fn test_flight_phase_lofted_trajectory_transitions() {
+        use super::{flight_phase, FlightPhase};
+
+        // 推力 200 kN、燃料消費 100 kg/s、燃焼終了質量 4000 kg → 10 s で燃焼終了
+        let params = MissileParameters {
+            thrust: [0.0, 0.0, 200_000.0],
+            area: 1.0,
+            fuel_consumption_rate: 100.0,
+            mass_initial: 5000.0,
+            burnout_mass: 4000.0,
+            ..Default::default()
+        };
+        let dt = 0.1;
+        let mut missile = Missile {
+            id: "m1".to_string(),
+            position: [0.0, 0.0, 0.0],
+            velocity: [100.0, 0.0, 0.0],
+            pitch: 90.0,
+            pitch_rate: 0.0,
+            mass: 5000.0,
+            thrust: params.thrust,
+            metadata: Default::default(),
+        };
+        let mut history = vec![missile.clone()];
+        let mut phases = vec![flight_phase(&missile, &params, None)];
+        for _ in 1..1000 {
+            let previous = missile.clone();
+            let thrusting = super::is_thrusting(&missile, &params);
+            let thrust_z = if thrusting { params.thrust[2] } else { 0.0 };
+            missile.velocity[2] += (thrust_z / missile.mass - 9.81) * dt;
+            missile.position[2] += missile.velocity[2] * dt;
+            if thrusting {
+                missile.mass -= params.fuel_consumption_rate * dt;
+            }
+            let phase = flight_phase(&missile, &params, Some((&previous, phases[phases.len() - 1])));
+            phases.push(phase);
+            history.push(missile.clone());
+        }
+
+        let burnout = phases.iter().position(|&p| p != FlightPhase::Boost).unwrap();
+        let descent = phases.iter().position(|&p| p == FlightPhase::Descent).unwrap();
+        // 燃焼終了は質量が 4000 kg 以下になった時点（約 10 s）
+        assert!((burnout as f64 * dt - 10.0).abs() < 0.15, "burnout at step {}", burnout);
+        assert!(history[burnout].mass <= params.burnout_mass);
+        assert!(phases[burnout..descent].iter().all(|&p| p == FlightPhase::Coast));
+        assert!(phases[descent..].iter().all(|&p| p == FlightPhase::Descent));
+        // 頂点: 降下判定時に鉛直速度が正から0以下に変わる
+        assert!(history[descent - 1].velocity[2] > 0.0);
+        assert!(history[descent].velocity[2] <= 0.0);
+    }
+
+    /// 頂点の判定は高度の変化ではなく鉛直速度の符号の変化による
+    #[test]
+    fn test_flight_phase_apogee_uses_vertical_velocity_sign() {
+        use super::{flight_phase, FlightPhase};
+
+        let params = MissileParameters { burnout_mass: 4000.0, ..Default::default() };
+        let coasting = |z: f64, vz: f64| Missile {
+            id: "m1".to_string(),
+            position: [0.0, 0.0, z],
+            velocity: [100.0, 0.0, vz],
+            pitch: 0.0,
+            pitch_rate: 0.0,
+            mass: 4000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
+        };
+        let previous = coasting(1000.0, 10.0);
+        let after = |missile: &Missile| flight_phase(missile, &params, Some((&previous, FlightPhase::Coast)));
+
+        // 高度が下がっても上昇中（vz > 0）なら頂点前
+        assert_eq!(after(&coasting(999.0, 5.0)), FlightPhase::Coast);
+        // vz が正から0以下に変われば頂点通過
+        assert_eq!(after(&coasting(1001.0, 0.0)), FlightPhase::Descent);
+        assert_eq!(after(&coasting(1001.0, -1.0)), FlightPhase::Descent);
+        // 水平飛行（vz が0のまま）は頂点に達しない
+        let level = coasting(1000.0, 0.0);
+        assert_eq!(flight_phase(&level, &params, Some((&level, FlightPhase::Coast))), FlightPhase::Coast);
+        // 燃焼終了時点で既に降下中なら最初から Descent
+        assert_eq!(flight_phase(&coasting(1000.0, -5.0), &params, None), FlightPhase::Descent);
+    }
+
+    /// 燃焼終了後は推力も燃料消費も止まる
+    #[test]
+    fn test_update_missiles_stops_thrust_after_burnout() {
+        let params = MissileParameters {
+            thrust: [5000.0, 0.0, 0.0],
+            area: 1.0,
+            fuel_consumption_rate: 10.0,
+            mass_initial: 5000.0,
+            burnout_mass: 4000.0,
+            ..Default::default()
+        };
+        let missile = Missile {
+            id: "m1".to_string(),
+            position: [0.0, 0.0, 20000.0],
+            velocity: [100.0, 0.0, 0.0],
+            pitch: 0.0,
+            pitch_rate: 0.0,
+            mass: 4000.0,
+            thrust: params.thrust,
+            metadata: Default::default(),
+        };
+        let state = SimulationState {
+            filters: vec![low_pass_filter_states(missile.velocity)],
+            missiles: vec![missile],
+            integrators: vec![Default::default()],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+        let (missiles, _, _, _) = update_missiles(&state, &params, &GravityModel::default(), 0.1).unwrap();
+        assert_eq!(missiles[0].mass, 4000.0);
+        assert_eq!(missiles[0].velocity[0], 100.0);
+    }
+
+    #[test]
+    fn test_base_drag_rises_after_burnout() {
+        let velocity = [300.0, 0.0, 100.0];
+        let thrusting = calculate_drag_force(&velocity, 1.0, 0.3, 1.0, &[5000.0, 0.0, 0.0], 0.1);
+        let coasting = calculate_drag_force(&velocity, 1.0, 0.3, 1.0, &[0.0; 3], 0.1);
+        // 同じ速度・大気密度でも、推力ゼロでは抗力係数が 0.3 → 0.4 になる
+        for k in 0..3 {
+            assert!((coasting[k] - thrusting[k] * 0.4 / 0.3).abs() < 1e-9);
+        }
+        assert!(coasting[0].abs() > thrusting[0].abs());
+
+        // 増分0では推力の有無によらない
+        assert_eq!(
+            calculate_drag_force(&velocity, 1.0, 0.3, 1.0, &[0.0; 3], 0.0),
+            calculate_drag_force(&velocity, 1.0, 0.3, 1.0, &[5000.0, 0.0, 0.0], 0.0),
+        );
+    }
 }
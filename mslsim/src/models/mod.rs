@@ -4,3 +4,5 @@ pub mod motion;
 pub mod missile;
 pub mod radar;
 pub mod interceptor;
+pub mod gravity;
+pub mod force;
@@ -1,101 +1,175 @@
 // src/models/motion.rs
 
 use std::error::Error;
+use std::sync::Arc;
 use crate::config::MissileParameters;
-use crate::math::{adams_bashforth_2, AdamsBashforth2State, low_pass_filter, LowPassFilterState};
+use crate::math::error::MathError;
+use crate::math::{
+    adams_bashforth_2, AdamsBashforth2State, Euler, Integrator, IntegratorKind, low_pass_filter, LowPassFilterState,
+    RungeKutta4, SeededRng,
+};
 use crate::{Missile, Interceptor,Radar};
-use crate::models::radar::detect;
+use crate::models::radar::{detect, detect_position, Affiliation, RadarContact};
+use crate::models::gravity::GravityModel;
+use crate::models::force::{default_force_models, net_force, ForceEnvironment, ForceModel};
 use crate::simulation::SimulationState;
 use crate::config::parameters::InterceptorParameters;
 
-/// ミサイルの更新処理
+/// 空気抵抗を無視する大気密度の閾値（kg/m³）
+pub const VACUUM_DENSITY_THRESHOLD: f64 = 1e-5;
+
+/// ミサイル更新結果（ミサイル、積分器状態、フィルタ状態、推力ノイズ用乱数生成器）
+pub type MissileUpdate = (
+    Vec<Missile>,
+    Vec<[AdamsBashforth2State; 3]>,
+    Vec<[LowPassFilterState; 3]>,
+    Vec<SeededRng>,
+);
+
+/// ミサイルの更新処理（推進力 + 空気抵抗 + 重力の既定の力モデル）
 pub fn update_missiles(
     state: &SimulationState,
     missile_params: &MissileParameters,
-    gravity: [f64; 3],
+    gravity: &GravityModel,
     dt: f64,
-) -> Result<(Vec<Missile>, Vec<AdamsBashforth2State>, Vec<LowPassFilterState>), Box<dyn Error>> {
-    let (missiles, integrators, filters) = state
+) -> Result<MissileUpdate, Box<dyn Error>> {
+    let forces = default_force_models(missile_params, gravity);
+    update_missiles_with_forces(state, missile_params, &forces, dt)
+}
+
+/// 力モデルのパイプラインを指定したミサイルの更新処理
+///
+/// 合計力は `forces` の各モデルの力の和とする。
+///
+/// # 引数
+/// - `state`: シミュレーション状態
+/// - `missile_params`: ミサイルのパラメータ
+/// - `forces`: 力モデルの列
+/// - `dt`: 時間刻み（s）
+///
+/// # 戻り値
+/// - 更新後のミサイル、積分器状態、フィルタ状態、乱数生成器
+pub fn update_missiles_with_forces(
+    state: &SimulationState,
+    missile_params: &MissileParameters,
+    forces: &[Box<dyn ForceModel>],
+    dt: f64,
+) -> Result<MissileUpdate, Box<dyn Error>> {
+    let (missiles, integrators, filters, thrust_rngs) = state
         .missiles
         .iter()
         .zip(state.integrators.iter())
         .zip(state.filters.iter())
-        .map(|((missile, integrator), filter)| {
-            // 高度に依存する大気密度の計算（簡略化）
-            let altitude = missile.position[2].max(0.0);
-            let air_density = standard_atmosphere_density(altitude);
-
-            // 空気抵抗力の計算
-            let drag = crate::models::missile::calculate_drag_force(
-                &missile.velocity,
-                air_density,
-                missile_params.drag_coefficient,
-                missile_params.area,
-            );
-
-            // 推進力の計算
-            let thrust = crate::models::missile::calculate_thrust(&missile_params.thrust);
-
-            // 重力力の計算
-            let gravity_force = [
-                0.0,
-                0.0,
-                missile.mass * gravity[2],
-            ];
-
-            // 合計力の計算
-            let net_force = crate::models::missile::calculate_net_force(&thrust, &drag, &gravity_force);
+        .zip(state.thrust_rngs.iter())
+        .zip(state.integrator_kinds.iter())
+        .zip(state.force_scales.iter())
+        .enumerate()
+        .map(|(index, (((((missile, integrator), filter), thrust_rng), integrator_kind), scale))| {
+            // 出現前のミサイルは更新しない
+            if !state.missile_spawned(index) {
+                return Ok((missile.clone(), integrator.clone(), filter.clone(), thrust_rng.clone()));
+            }
+
+            // 推進力の計算（ステップごとの乗法ノイズ）。燃焼終了後は推力なし
+            let mut thrust_rng = thrust_rng.clone();
+            let thrusting = crate::models::missile::is_thrusting(missile, missile_params);
+            let thrust = if thrusting {
+                let thrust = crate::models::missile::calculate_thrust_with_noise(
+                    &missile.thrust,
+                    missile_params.thrust_noise_sigma,
+                    &mut thrust_rng,
+                );
+                [thrust[0] * scale.thrust, thrust[1] * scale.thrust, thrust[2] * scale.thrust]
+            } else {
+                [0.0, 0.0, 0.0]
+            };
+
+            // 合計力の計算（空気抵抗はミサイルごとの倍率を掛ける）
+            let environment = ForceEnvironment {
+                drag_scale: scale.drag,
+                ..ForceEnvironment::at(missile, thrust)
+            };
+            let net_force = net_force(forces, missile, &environment);
 
             // 加速度の計算
             let acceleration = crate::models::missile::calculate_acceleration(&net_force, missile.mass);
 
-            // Adams-Bashforth 2段法による積分
-            let (new_integrator, new_velocity_component) =
-                match adams_bashforth_2(integrator.clone(), missile.velocity[0], acceleration[0]) {
-                    Ok(result) => result,
-                    Err(_) => (integrator.clone(), missile.velocity[0]),
+            // 速度の積分とローパスフィルタ（x, y, z 軸ごと、ミサイルごとの積分方式）
+            let mut new_integrator = integrator.clone();
+            let mut new_filter = filter.clone();
+            let mut new_velocity = missile.velocity;
+            for axis in 0..3 {
+                let velocity_component = match integrator_kind {
+                    // Adams-Bashforth 2段法（前回の加速度を積分器状態に保持）。非有限の状態はエラーとする
+                    IntegratorKind::Ab2 => {
+                        let (integrator_axis, velocity_component) =
+                            adams_bashforth_2(integrator[axis].clone(), missile.velocity[axis], acceleration[axis])?;
+                        new_integrator[axis] = integrator_axis;
+                        velocity_component
+                    }
+                    // Euler 法・RK4 法（速度に依存する力を評価し直す）
+                    IntegratorKind::Euler | IntegratorKind::Rk4 => {
+                        let acceleration_axis = |v: f64| {
+                            let mut velocity = missile.velocity;
+                            velocity[axis] = v;
+                            let probe = Missile { velocity, ..missile.clone() };
+                            crate::models::force::net_force(forces, &probe, &environment)[axis] / missile.mass
+                        };
+                        match integrator_kind {
+                            IntegratorKind::Euler => Euler.step(missile.velocity[axis], &acceleration_axis, dt),
+                            _ => RungeKutta4.step(missile.velocity[axis], &acceleration_axis, dt),
+                        }
+                    }
                 };
-
-            // ローパスフィルタの適用
-            let (new_filter, filtered_velocity) =
-                low_pass_filter(filter.clone(), new_velocity_component, 0.5);
-
-            // 新しい速度の計算
-            let new_velocity = [
-                filtered_velocity,
-                missile.velocity[1], // Y軸も同様に更新する場合、別途計算が必要
-                missile.velocity[2], // Z軸も同様に更新する場合、別途計算が必要
-            ];
+                let (filter_axis, filtered_velocity) = low_pass_filter(filter[axis].clone(), velocity_component, 0.5)?;
+                new_filter[axis] = filter_axis;
+                new_velocity[axis] = filtered_velocity;
+            }
 
             // 新しい位置の計算
-            let new_position = crate::models::missile::update_position(&missile.position, &new_velocity, dt);
+            let new_position = crate::models::missile::update_position(missile.position, new_velocity, dt).into();
 
-            // ピッチ角の更新（簡略化）
-            let new_pitch = crate::models::missile::update_pitch(missile.pitch, missile.pitch); // 実際のピッチ角更新は推進力や重力に基づく計算が必要
+            // ピッチ角の更新（重心からずれた推力のモーメントによる回転）
+            let moment = crate::models::missile::calculate_thrust_moment(&missile_params.thrust_offset, &thrust);
+            let pitch_acceleration =
+                crate::models::missile::calculate_pitch_acceleration(&moment, missile_params.pitch_inertia);
+            let (new_pitch, new_pitch_rate) =
+                crate::models::missile::update_pitch(missile.pitch, missile.pitch_rate, pitch_acceleration, dt);
 
-            (
+            Ok((
                 Missile {
                     id: missile.id.clone(),
                     position: new_position,
                     velocity: new_velocity,
                     pitch: new_pitch,
-                    mass: missile.mass - missile_params.fuel_consumption_rate * dt,
+                    pitch_rate: new_pitch_rate,
+                    mass: if thrusting {
+                        missile.mass - missile_params.fuel_consumption_rate * dt
+                    } else {
+                        missile.mass
+                    },
+                    thrust: missile.thrust,
+                    metadata: Arc::clone(&missile.metadata),
                 },
                 new_integrator,
                 new_filter,
-            )
+                thrust_rng,
+            ))
         })
+        .collect::<Result<Vec<_>, MathError>>()?
+        .into_iter()
         .fold(
-            (Vec::new(), Vec::new(), Vec::new()),
-            |(mut missiles, mut integrators, mut filters), (m, i, f)| {
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new()),
+            |(mut missiles, mut integrators, mut filters, mut thrust_rngs), (m, i, f, r)| {
                 missiles.push(m);
                 integrators.push(i);
                 filters.push(f);
-                (missiles, integrators, filters)
+                thrust_rngs.push(r);
+                (missiles, integrators, filters, thrust_rngs)
             },
         );
 
-    Ok((missiles, integrators, filters))
+    Ok((missiles, integrators, filters, thrust_rngs))
 }
 
 /// 迎撃ミサイルの更新処理
@@ -108,28 +182,38 @@ pub fn update_interceptors(
         .interceptors
         .iter()
         .zip(state.interceptor_filters.iter())
-        .map(|(interceptor, filter)| {
-            // ターゲットミサイルの選定（例として最初のミサイルをターゲット）
-            if let Some(target) = state.missiles.first() {
+        .enumerate()
+        .map(|(index, (interceptor, filter))| {
+            // 出現前の迎撃ミサイルは更新しない
+            if !state.interceptor_spawned(index) {
+                return Ok((interceptor.clone(), filter.clone()));
+            }
+            // ターゲットミサイルの選定（例として出現済みの最初のミサイルをターゲット）
+            let target = (0..state.missiles.len())
+                .find(|&i| state.missile_spawned(i))
+                .map(|i| &state.missiles[i]);
+            if let Some(target) = target {
                 match crate::models::interceptor::update_interceptor(
                     interceptor,
-                    &target.position,
-                    &target.velocity,
+                    target.position,
+                    target.velocity,
                     interceptor_params.navigation_coefficient,
                     dt,
                 ) {
                     Ok(updated_interceptor) => {
                         // ローパスフィルタの適用
                         let (new_filter, _) =
-                            low_pass_filter(filter.clone(), updated_interceptor.velocity[0], 0.5);
-                        (updated_interceptor, new_filter)
+                            low_pass_filter(filter.clone(), updated_interceptor.velocity[0], 0.5)?;
+                        Ok((updated_interceptor, new_filter))
                     }
-                    Err(_) => (interceptor.clone(), filter.clone()),
+                    Err(_) => Ok((interceptor.clone(), filter.clone())),
                 }
             } else {
-                (interceptor.clone(), filter.clone())
+                Ok((interceptor.clone(), filter.clone()))
             }
         })
+        .collect::<Result<Vec<_>, MathError>>()?
+        .into_iter()
         .fold(
             (Vec::new(), Vec::new()),
             |(mut interceptors, mut interceptor_filters), (m, f)| {
@@ -142,13 +226,31 @@ pub fn update_interceptors(
     Ok((interceptors, interceptor_filters))
 }
 
+/// レーダをIDの辞書順に並べたインデックス列
+///
+/// 同じIDのレーダは入力順を保つ。探知結果とCSV列の順序をレーダの入力順に依存させないために使う。
+///
+/// # 引数
+/// - `radars`: レーダの列
+///
+/// # 戻り値
+/// - IDの辞書順に並べたレーダのインデックス
+pub fn radar_order(radars: &[Radar]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..radars.len()).collect();
+    order.sort_by(|&a, &b| radars[a].id.cmp(&radars[b].id));
+    order
+}
+
 /// レーダーによる全探知処理
+///
+/// 結果はレーダIDの辞書順（`radar_order`）に並べる。
 pub fn detect_all_radars(
-    radars: &Vec<Radar>,
-    missiles: &Vec<Missile>,
+    radars: &[Radar],
+    missiles: &[Missile],
 ) -> Vec<(bool, [f64; 3])> {
-    radars
-        .iter()
+    radar_order(radars)
+        .into_iter()
+        .map(|i| &radars[i])
         .map(|radar| {
             let detection = missiles.iter().any(|missile| detect(radar, missile));
             if detection {
@@ -161,6 +263,46 @@ pub fn detect_all_radars(
         .collect()
 }
 
+/// レーダーによる全探知処理（迎撃ミサイルを含む、敵味方識別付き）
+///
+/// `detect_all_radars` と異なり、各レーダが探知した全目標を返す。ミサイルは敵、迎撃ミサイルは味方として識別する。
+/// 結果はレーダIDの辞書順（`radar_order`）、同じレーダ内ではミサイル、迎撃ミサイルの入力順に並べる。
+///
+/// # 引数
+/// - `radars`: レーダの列
+/// - `missiles`: ミサイルの列
+/// - `interceptors`: 探知対象に含める迎撃ミサイルの列（空ならミサイルのみを探知）
+///
+/// # 戻り値
+/// - 探知結果の列
+pub fn detect_all_entities(
+    radars: &[Radar],
+    missiles: &[Missile],
+    interceptors: &[Interceptor],
+) -> Vec<RadarContact> {
+    let entities: Vec<(&String, Affiliation, [f64; 3])> = missiles
+        .iter()
+        .map(|m| (&m.id, Affiliation::Hostile, m.position))
+        .chain(interceptors.iter().map(|i| (&i.id, Affiliation::Friendly, i.position)))
+        .collect();
+    radar_order(radars)
+        .into_iter()
+        .map(|i| &radars[i])
+        .flat_map(|radar| {
+            entities
+                .iter()
+                .filter(|(_, _, position)| detect_position(radar, position))
+                .map(|(id, affiliation, position)| RadarContact {
+                    radar_id: radar.id.clone(),
+                    entity_id: (*id).clone(),
+                    affiliation: *affiliation,
+                    position: *position,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 /// 標準大気モデルによる高度に依存する大気密度の計算（簡略化）
 pub fn standard_atmosphere_density(altitude: f64) -> f64 {
     // 簡易的なモデル（実際の標準大気モデルを適用することを推奨）
@@ -170,3 +312,353 @@ pub fn standard_atmosphere_density(altitude: f64) -> f64 {
         0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::low_pass_filter_states;
+    use crate::models::force::ForceScale;
+
+    fn missile_params() -> MissileParameters {
+        MissileParameters {
+            mass_initial: 5000.0,
+            fuel_consumption_rate: 10.0,
+            drag_coefficient: 0.3,
+            area: 1.0,
+            thrust: [5000.0, 0.0, 2000.0],
+            ..Default::default()
+        }
+    }
+
+    /// 既定の力モデル（推進力 + 空気抵抗 + 重力）による合計力
+    fn missile_net_force(missile: &Missile, params: &MissileParameters, gravity: &GravityModel) -> [f64; 3] {
+        net_force(&default_force_models(params, gravity), missile, &ForceEnvironment::at(missile, missile.thrust))
+    }
+
+    fn missile_at(altitude: f64) -> Missile {
+        Missile {
+            id: "missile1".to_string(),
+            position: [0.0, 0.0, altitude],
+            velocity: [2000.0, 0.0, 500.0],
+            pitch: 45.0,
+            pitch_rate: 0.0,
+            mass: 5000.0,
+            thrust: [5000.0, 0.0, 2000.0],
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_net_force_in_vacuum_has_no_drag() {
+        let params = missile_params();
+        let missile = missile_at(20000.0);
+
+        let net_force = missile_net_force(&missile, &params, &GravityModel::default());
+
+        assert_eq!(net_force, [5000.0, 0.0, 2000.0 - 5000.0 * 9.81]);
+    }
+
+    #[test]
+    fn test_net_force_in_atmosphere_includes_drag() {
+        let params = missile_params();
+        let missile = missile_at(1000.0);
+
+        let net_force = missile_net_force(&missile, &params, &GravityModel::default());
+
+        assert!(net_force[0] < 5000.0);
+        assert!(net_force[2] < 2000.0 - 5000.0 * 9.81);
+    }
+
+    #[test]
+    fn test_net_force_uses_full_gravity_vector() {
+        let params = missile_params();
+        let mut missile = missile_at(20000.0);
+        missile.thrust = [0.0, 0.0, 0.0];
+
+        let net_force = missile_net_force(&missile, &params, &GravityModel::constant([0.0, 9.81, 0.0]));
+
+        assert_eq!(net_force, [0.0, 5000.0 * 9.81, 0.0]);
+    }
+
+    #[test]
+    fn test_horizontal_gravity_deflects_coasting_projectile() {
+        use crate::simulation::SimulationState;
+
+        // 推力・抵抗・燃料消費なしで水平方向の重力のみを受ける
+        let params = MissileParameters {
+            mass_initial: 5000.0,
+            area: 1.0,
+            ..Default::default()
+        };
+        let mut state = SimulationState {
+            missiles: vec![Missile {
+                id: "missile1".to_string(),
+                position: [0.0, 0.0, 20000.0],
+                velocity: [0.0, 0.0, 0.0],
+                pitch: 0.0,
+                pitch_rate: 0.0,
+                mass: 5000.0,
+                thrust: [0.0, 0.0, 0.0],
+                metadata: Default::default(),
+            }],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states([0.0, 0.0, 0.0])],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+        let gravity = GravityModel::constant([-9.81, 0.0, 0.0]);
+
+        for _ in 0..10 {
+            let (missiles, integrators, filters, _) = update_missiles(&state, &params, &gravity, 0.1).unwrap();
+            state.missiles = missiles;
+            state.integrators = integrators;
+            state.filters = filters;
+        }
+
+        let missile = &state.missiles[0];
+        // 重力の向き（-x）へ加速・移動し、高度は変わらない
+        assert!(missile.velocity[0] < 0.0);
+        assert!(missile.position[0] < 0.0);
+        assert_eq!(missile.position[2], 20000.0);
+        assert_eq!(missile.velocity[2], 0.0);
+    }
+
+    #[test]
+    fn test_gravity_y_and_z_components_are_integrated() {
+        use crate::simulation::SimulationState;
+
+        // 推力・抵抗なしで y・z 成分を持つ重力のみを受ける（x 成分は 0）
+        let params = MissileParameters {
+            mass_initial: 5000.0,
+            area: 1.0,
+            ..Default::default()
+        };
+        let gravity = GravityModel::constant([0.0, 3.0, -9.81]);
+        for integrator_kind in [IntegratorKind::Ab2, IntegratorKind::Euler, IntegratorKind::Rk4] {
+            let mut state = SimulationState {
+                missiles: vec![Missile {
+                    velocity: [100.0, 0.0, 0.0],
+                    thrust: [0.0, 0.0, 0.0],
+                    ..missile_at(20000.0)
+                }],
+                integrators: vec![Default::default()],
+                filters: vec![low_pass_filter_states([100.0, 0.0, 0.0])],
+                thrust_rngs: vec![SeededRng::new(0)],
+                integrator_kinds: vec![integrator_kind],
+                force_scales: vec![ForceScale::default()],
+                ..Default::default()
+            };
+
+            for _ in 0..10 {
+                let (missiles, integrators, filters, _) = update_missiles(&state, &params, &gravity, 0.1).unwrap();
+                state.missiles = missiles;
+                state.integrators = integrators;
+                state.filters = filters;
+            }
+
+            let missile = &state.missiles[0];
+            // y・z 軸も重力の向きへ加速・移動し、速度の比は重力ベクトルの比になる
+            assert!(missile.velocity[1] > 0.0 && missile.position[1] > 0.0, "{:?}", integrator_kind);
+            assert!(missile.velocity[2] < 0.0 && missile.position[2] < 20000.0, "{:?}", integrator_kind);
+            assert!((missile.velocity[1] / missile.velocity[2] - 3.0 / -9.81).abs() < 1e-9);
+            assert_eq!(missile.velocity[0], 100.0);
+        }
+    }
+
+    /// 推力オフセットのあるミサイルを1ステップ更新したときの (ピッチ角, ピッチ角速度)
+    fn pitch_after_step(thrust_offset: [f64; 3]) -> (f64, f64) {
+        use crate::simulation::SimulationState;
+
+        let params = MissileParameters {
+            thrust: [5000.0, 0.0, 0.0],
+            thrust_offset,
+            pitch_inertia: 1000.0,
+            ..missile_params()
+        };
+        let state = SimulationState {
+            missiles: vec![Missile {
+                thrust: [5000.0, 0.0, 0.0],
+                ..missile_at(20000.0)
+            }],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states([2000.0, 0.0, 500.0])],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+        let (missiles, _, _, _) = update_missiles(&state, &params, &GravityModel::default(), 0.1).unwrap();
+        (missiles[0].pitch, missiles[0].pitch_rate)
+    }
+
+    #[test]
+    fn test_thrust_offset_above_cg_pitches_nose_down() {
+        // M_y = rz * Fx = 0.1 * 5000 = 500 N・m、角加速度 -500 / 1000 rad/s²（機首下げ）
+        let (pitch, pitch_rate) = pitch_after_step([0.0, 0.0, 0.1]);
+        let expected_rate = (-0.5f64).to_degrees() * 0.1;
+        assert!((pitch_rate - expected_rate).abs() < 1e-12);
+        assert!((pitch - (45.0 + expected_rate * 0.1)).abs() < 1e-12);
+        assert!(pitch < 45.0);
+
+        // 重心より下のオフセットは機首上げ、オフセットなしではピッチ角は変わらない
+        let (pitch, pitch_rate) = pitch_after_step([0.0, 0.0, -0.1]);
+        assert!(pitch > 45.0 && pitch_rate > 0.0);
+        assert_eq!(pitch_after_step([0.0, 0.0, 0.0]), (45.0, 0.0));
+    }
+
+    /// 推力ノイズ付きで1発のミサイルを真空中（抗力なし）で飛行させ、
+    /// `update_missiles` が返した各ステップ後の速度x成分と最後の乱数生成器を返す
+    fn noisy_velocity_history(seed: u64, sigma: f64, steps: usize) -> (Vec<f64>, SeededRng) {
+        use crate::simulation::SimulationState;
+
+        let params = MissileParameters {
+            thrust_noise_sigma: sigma,
+            ..missile_params()
+        };
+        let mut state = SimulationState {
+            missiles: vec![missile_at(20000.0)],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states([2000.0, 0.0, 500.0])],
+            thrust_rngs: vec![SeededRng::new(seed)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+        let mut history = Vec::new();
+        for _ in 0..steps {
+            let (missiles, integrators, filters, thrust_rngs) =
+                update_missiles(&state, &params, &GravityModel::default(), 0.1).unwrap();
+            history.push(missiles[0].velocity[0]);
+            state.missiles = missiles;
+            state.integrators = integrators;
+            state.filters = filters;
+            state.thrust_rngs = thrust_rngs;
+        }
+        (history, state.thrust_rngs.remove(0))
+    }
+
+    #[test]
+    fn test_thrust_noise_is_reproducible_with_fixed_seed() {
+        let (a, _) = noisy_velocity_history(2024, 0.05, 50);
+        let (b, _) = noisy_velocity_history(2024, 0.05, 50);
+        assert_eq!(a, b);
+        // ノイズにより公称推力の軌跡から外れ、別シードでは別の軌跡になる
+        let (nominal, _) = noisy_velocity_history(2024, 0.0, 50);
+        assert_ne!(a, nominal);
+        assert_ne!(a, noisy_velocity_history(7, 0.05, 50).0);
+    }
+
+    #[test]
+    fn test_thrust_noise_mean_matches_nominal() {
+        // 真空中では速度の増分が推力に比例するため、ノイズ付きの増分の合計は公称推力の増分に近い
+        let steps = 4000;
+        let (noisy, _) = noisy_velocity_history(99, 0.05, steps);
+        let (nominal, _) = noisy_velocity_history(99, 0.0, steps);
+        let gain = |history: &[f64]| history[steps - 1] - history[0];
+        // sigma 5%、4000 ステップ → 増分の相対的な標準誤差は約 0.1%
+        let relative = (gain(&noisy) - gain(&nominal)).abs() / gain(&nominal);
+        assert!(relative < 0.005, "relative = {}", relative);
+    }
+
+    #[test]
+    fn test_zero_thrust_noise_keeps_nominal_and_rng() {
+        use crate::models::missile::calculate_thrust_with_noise;
+
+        let mut rng = SeededRng::new(1);
+        let thrust = calculate_thrust_with_noise(&[5000.0, 0.0, 2000.0], 0.0, &mut rng);
+        assert_eq!(thrust, [5000.0, 0.0, 2000.0]);
+        assert_eq!(rng, SeededRng::new(1));
+
+        // update_missiles でもノイズ0なら乱数を消費しない
+        let (_, rng) = noisy_velocity_history(1, 0.0, 10);
+        assert_eq!(rng, SeededRng::new(1));
+    }
+
+    fn radar(id: &str, position: [f64; 3]) -> Radar {
+        Radar {
+            id: id.to_string(),
+            position,
+            detection_range: 5000.0,
+            azimuth_min: 0.0,
+            azimuth_max: 360.0,
+            elevation_min: -90.0,
+            elevation_max: 90.0,
+        }
+    }
+
+    #[test]
+    fn test_detect_all_radars_sorted_by_radar_id() {
+        let missiles = vec![missile_at(1000.0)];
+        let radars = vec![
+            radar("r1", [0.0, 0.0, 0.0]),
+            radar("r2", [100_000.0, 0.0, 0.0]),
+            radar("r3", [1000.0, 0.0, 0.0]),
+        ];
+        let expected = detect_all_radars(&radars, &missiles);
+        assert_eq!(expected, vec![(true, [0.0, 0.0, 1000.0]), (false, [0.0; 3]), (true, [0.0, 0.0, 1000.0])]);
+
+        let shuffled = vec![radars[2].clone(), radars[0].clone(), radars[1].clone()];
+        assert_eq!(radar_order(&shuffled), vec![1, 2, 0]);
+        assert_eq!(detect_all_radars(&shuffled, &missiles), expected);
+    }
+
+    #[test]
+    fn test_detect_all_entities_tags_interceptors_as_friendly() {
+        let missiles = vec![missile_at(1000.0)];
+        let interceptors = vec![Interceptor {
+            id: "interceptor1".to_string(),
+            position: [3000.0, 0.0, 500.0],
+            velocity: [0.0, 0.0, 300.0],
+            pitch: 90.0,
+            mass: 500.0,
+            metadata: Default::default(),
+        }];
+        let radars = vec![radar("r2", [100_000.0, 0.0, 0.0]), radar("r1", [0.0, 0.0, 0.0])];
+
+        let contacts = detect_all_entities(&radars, &missiles, &interceptors);
+        assert_eq!(
+            contacts,
+            vec![
+                RadarContact {
+                    radar_id: "r1".to_string(),
+                    entity_id: "missile1".to_string(),
+                    affiliation: Affiliation::Hostile,
+                    position: [0.0, 0.0, 1000.0],
+                },
+                RadarContact {
+                    radar_id: "r1".to_string(),
+                    entity_id: "interceptor1".to_string(),
+                    affiliation: Affiliation::Friendly,
+                    position: [3000.0, 0.0, 500.0],
+                },
+            ]
+        );
+
+        // 迎撃ミサイルを含めなければミサイルのみ
+        let hostile_only = detect_all_entities(&radars, &missiles, &[]);
+        assert_eq!(hostile_only.len(), 1);
+        assert_eq!(hostile_only[0].affiliation, Affiliation::Hostile);
+    }
+
+    #[test]
+    fn test_update_missiles_rejects_non_finite_state() {
+        use crate::simulation::SimulationState;
+
+        let state = SimulationState {
+            missiles: vec![Missile {
+                velocity: [f64::NAN, 0.0, 500.0],
+                ..missile_at(1000.0)
+            }],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states([f64::NAN, 0.0, 500.0])],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+        let error = update_missiles(&state, &missile_params(), &GravityModel::default(), 0.1).unwrap_err();
+        assert!(matches!(error.downcast_ref::<MathError>(), Some(MathError::NonFinite)), "{}", error);
+    }
+}
@@ -23,11 +23,25 @@ pub struct Radar {
 /// # 戻り値
 /// - ミサイルがレーダーの探知範囲および角度範囲内にある場合は`true`、それ以外は`false`
 pub fn detect(radar: &Radar, missile: &Missile) -> bool {
+    detect_position(radar, &missile.position)
+}
+
+/// 任意の位置にある目標を探知するか判定する関数
+///
+/// ミサイル以外（迎撃ミサイルなど）の探知にも用いる。
+///
+/// # 引数
+/// - `radar`: レーダのデータ
+/// - `position`: 目標の位置 [x, y, z]
+///
+/// # 戻り値
+/// - 目標がレーダーの探知範囲および角度範囲内にある場合は`true`、それ以外は`false`
+pub fn detect_position(radar: &Radar, position: &[f64; 3]) -> bool {
     // 相対位置ベクトルの計算
     let rel_position = [
-        missile.position[0] - radar.position[0],
-        missile.position[1] - radar.position[1],
-        missile.position[2] - radar.position[2],
+        position[0] - radar.position[0],
+        position[1] - radar.position[1],
+        position[2] - radar.position[2],
     ];
 
     // 距離の計算
@@ -61,6 +75,49 @@ pub fn detect(radar: &Radar, missile: &Missile) -> bool {
     azimuth_in_range && elevation_in_range
 }
 
+/// 探知した目標の敵味方識別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Affiliation {
+    /// 敵（ミサイル）
+    Hostile,
+    /// 味方（迎撃ミサイル）
+    Friendly,
+}
+
+/// 敵味方識別付きの探知結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarContact {
+    pub radar_id: String,          // 探知したレーダのID
+    pub entity_id: String,         // 探知した目標のID
+    pub affiliation: Affiliation,  // 敵味方識別
+    pub position: [f64; 3],        // 探知位置 [x, y, z]
+}
+
+/// 探知範囲および角度範囲内にある全ミサイルを距離の近い順に返す関数
+///
+/// # 引数
+/// - `radar`: レーダのデータ
+/// - `missiles`: ミサイルの一覧
+///
+/// # 戻り値
+/// - 探知されたミサイルのインデックス（レーダからの距離の昇順、同距離はインデックス順）
+pub fn missiles_in_coverage(radar: &Radar, missiles: &[Missile]) -> Vec<usize> {
+    let mut in_coverage: Vec<(usize, f64)> = missiles
+        .iter()
+        .enumerate()
+        .filter(|(_, missile)| detect(radar, missile))
+        .map(|(i, missile)| {
+            let distance = (0..3)
+                .map(|k| (missile.position[k] - radar.position[k]).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            (i, distance)
+        })
+        .collect();
+    in_coverage.sort_by(|a, b| a.1.total_cmp(&b.1));
+    in_coverage.into_iter().map(|(i, _)| i).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,7 +140,10 @@ mod tests {
             position: [500.0, 500.0, 0.0], // azimuth = 45°, elevation = 0°
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
 
         assert!(detect(&radar, &missile));
@@ -106,7 +166,10 @@ mod tests {
             position: [1000.0, 1000.0, 0.0], // distance = ~1414.2 > 1000
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
 
         assert!(!detect(&radar, &missile));
@@ -129,7 +192,10 @@ mod tests {
             position: [-500.0, 500.0, 0.0], // azimuth = 135° > 90°
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
 
         assert!(!detect(&radar, &missile));
@@ -152,7 +218,10 @@ mod tests {
             position: [500.0, 500.0, 200.0], // elevation = ~19.1° > 10°
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
 
         assert!(!detect(&radar, &missile));
@@ -173,31 +242,71 @@ mod tests {
         // azimuth = 5° (within 350-10°)
         let missile1 = Missile {
             id: "missile1".to_string(),
-            position: [100.0, -17.3648178, 0.0], // ~5°
+            position: [100.0, 8.7488664, 0.0], // ~5°
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
         assert!(detect(&radar, &missile1));
 
         // azimuth = 355° (within 350-10°)
         let missile2 = Missile {
             id: "missile2".to_string(),
-            position: [100.0, 29.0482216, 0.0], // ~355°
+            position: [100.0, -8.7488664, 0.0], // ~355°
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
         assert!(detect(&radar, &missile2));
 
         // azimuth = 20° (outside 350-10°)
         let missile3 = Missile {
             id: "missile3".to_string(),
-            position: [100.0, -34.202014, 0.0], // ~20°
+            position: [100.0, 36.3970234, 0.0], // ~20°
             velocity: [100.0, 0.0, 50.0],
             pitch: 45.0,
+            pitch_rate: 0.0,
             mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
         };
         assert!(!detect(&radar, &missile3));
     }
+
+    #[test]
+    fn test_missiles_in_coverage_sorted_by_range() {
+        let radar = Radar {
+            id: "radar1".to_string(),
+            position: [0.0, 0.0, 0.0],
+            detection_range: 1000.0,
+            azimuth_min: 0.0,
+            azimuth_max: 90.0,
+            elevation_min: 0.0,
+            elevation_max: 90.0,
+        };
+        let missile_at = |id: &str, position: [f64; 3]| Missile {
+            id: id.to_string(),
+            position,
+            velocity: [100.0, 0.0, 50.0],
+            pitch: 45.0,
+            pitch_rate: 0.0,
+            mass: 5000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
+        };
+        let missiles = vec![
+            missile_at("far", [800.0, 0.0, 100.0]),
+            missile_at("out_of_range", [1500.0, 0.0, 100.0]),
+            missile_at("near", [300.0, 100.0, 100.0]),
+        ];
+
+        assert_eq!(missiles_in_coverage(&radar, &missiles), vec![2, 0]);
+        assert!(missiles_in_coverage(&radar, &[]).is_empty());
+    }
 }
@@ -5,52 +5,238 @@ use std::io::Write;
 use std::fs::File;
 use std::io::BufWriter;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{Missile, Radar, Interceptor};
 use crate::simulation::SimulationState;
+use crate::models::motion::radar_order;
+use crate::math::{enu_to_geodetic, GeodeticPosition};
 
 /// CSV出力の設定とヘッダーの書き込み
 pub fn setup_csv_output(
     path: &str,
     state: &SimulationState,
+    output: &CsvOutput,
 ) -> Result<Box<dyn Write>, Box<dyn Error>> {
     let output_file = File::create(path)?;
     let mut writer = BufWriter::new(output_file);
-    write_csv_header(&mut writer, state)?;
+    write_csv_header_with(&mut writer, state, output)?;
     Ok(Box::new(writer))
 }
 
 
+/// 追加で出力する任意のCSV列（設定ファイルでは `flight_angles`、`range_bearing` などの名前で指定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvColumns {
+    /// 速度方向の角度（飛行経路角 `{id}_fpa`、方位角 `{id}_heading`）
+    FlightAngles,
+    /// 積分器・フィルタの内部状態（ミサイルは x, y, z 軸の `{id}_ab2_prev_f_x` …、`{id}_lpf_previous_x` …、
+    /// 迎撃ミサイルは `{id}_lpf_previous_x`）。
+    /// 値は `create_state_csv_row` で出力する（`create_csv_row_with` では空欄）
+    Diagnostics,
+    /// ミサイルの飛行段階（`{id}_phase`: boost / coast / descent）。
+    /// シミュレーション状態の飛行段階を出力するため、行は `create_state_csv_row` で作成する
+    FlightPhase,
+    /// ENU座標（`{id}_east`、`{id}_north`、`{id}_up`）。シミュレーション座標 x, y, z を東・北・上とみなす
+    Enu,
+    /// 測地座標（`{id}_lat`、`{id}_lon`、`{id}_alt`）。`CsvOutput::geodetic_origin` をシミュレーション座標の原点とする WGS84
+    Geodetic,
+    /// `CsvOutput::range_reference` からの距離・方位・仰角（`{id}_range`、`{id}_bearing`、`{id}_elevation`）。
+    /// 方位は北から時計回り
+    RangeBearing,
+}
+
+/// CSV出力の設定（`config/output.yaml`）
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct CsvOutput {
+    #[serde(default)]
+    pub columns: Vec<CsvColumns>, // 追加で出力する任意列（座標系の列は指定順に並ぶ）
+    #[serde(default)]
+    pub geodetic_origin: GeodeticPosition, // 測地座標の列の基準（シミュレーション座標の原点の測地座標）
+    #[serde(default)]
+    pub range_reference: [f64; 3], // 距離・方位・仰角の列の基準点 [x, y, z]（m）
+}
+
+impl CsvOutput {
+    /// 任意列だけを指定した設定（座標系の基準はいずれも原点）
+    pub fn with_columns(columns: &[CsvColumns]) -> Self {
+        CsvOutput {
+            columns: columns.to_vec(),
+            ..Default::default()
+        }
+    }
+}
+
+/// 基準点から見た位置の距離・方位・仰角を計算する
+///
+/// # 引数
+/// - `position`: 位置 [x, y, z]（東・北・上, m）
+/// - `reference`: 基準点 [x, y, z]（m）
+///
+/// # 戻り値
+/// - (距離（m）, 方位（度、北から時計回りに 0〜360）, 仰角（度）)。基準点と一致する場合は (0, 0, 0)
+pub fn range_bearing(position: &[f64; 3], reference: &[f64; 3]) -> (f64, f64, f64) {
+    let d = [position[0] - reference[0], position[1] - reference[1], position[2] - reference[2]];
+    let horizontal = d[0].hypot(d[1]);
+    let range = horizontal.hypot(d[2]);
+    if range == 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+    let bearing = d[0].atan2(d[1]).to_degrees().rem_euclid(360.0);
+    let elevation = d[2].atan2(horizontal).to_degrees();
+    (range, bearing, elevation)
+}
+
+/// 座標系の列のヘッダー（座標系の列でなければ None）
+fn frame_header(id: &str, column: &CsvColumns) -> Option<String> {
+    match column {
+        CsvColumns::Enu => Some(format!("{0}_east(m),{0}_north(m),{0}_up(m),", id)),
+        CsvColumns::Geodetic => Some(format!("{0}_lat(deg),{0}_lon(deg),{0}_alt(m),", id)),
+        CsvColumns::RangeBearing => Some(format!("{0}_range(m),{0}_bearing(deg),{0}_elevation(deg),", id)),
+        _ => None,
+    }
+}
+
+/// 座標系の列の値（座標系の列でなければ None）
+fn frame_fields(position: &[f64; 3], column: &CsvColumns, output: &CsvOutput) -> Option<String> {
+    match column {
+        CsvColumns::Enu => Some(format!("{},{},{},", position[0], position[1], position[2])),
+        CsvColumns::Geodetic => {
+            let geodetic = enu_to_geodetic(position, &output.geodetic_origin);
+            Some(format!("{},{},{},", geodetic.latitude, geodetic.longitude, geodetic.altitude))
+        }
+        CsvColumns::RangeBearing => {
+            let (range, bearing, elevation) = range_bearing(position, &output.range_reference);
+            Some(format!("{},{},{},", range, bearing, elevation))
+        }
+        _ => None,
+    }
+}
+
+/// 速度ベクトルから飛行経路角と方位角を計算する
+///
+/// # 引数
+/// - `velocity`: 速度ベクトル [vx, vy, vz]
+///
+/// # 戻り値
+/// - (飛行経路角 asin(vz/|v|), 方位角 atan2(vy, vx))（度）。速度ゼロの場合は (0, 0)
+pub fn flight_angles(velocity: &[f64; 3]) -> (f64, f64) {
+    let speed = (velocity[0].powi(2) + velocity[1].powi(2) + velocity[2].powi(2)).sqrt();
+    if speed == 0.0 {
+        return (0.0, 0.0);
+    }
+    let fpa = (velocity[2] / speed).clamp(-1.0, 1.0).asin().to_degrees();
+    let heading = velocity[1].atan2(velocity[0]).to_degrees();
+    (fpa, heading)
+}
+
 /// CSVヘッダーの書き込み
 pub fn write_csv_header<W: Write>(
     writer: &mut W,
     state: &SimulationState,
 ) -> Result<(), std::io::Error> {
-    let mut header = String::from("time(s),");
+    write_csv_header_with(writer, state, &CsvOutput::default())
+}
 
-    // ミサイルのヘッダー
-    for missile in &state.missiles {
-        header.push_str(&format!(
-            "{0}_x(m),{0}_y(m),{0}_z(m),{0}_pitch(deg),",
-            missile.id
-        ));
+/// CSVのフィールドをエスケープする
+///
+/// `,`、`"`、改行を含む場合は全体を `"` で囲み、`"` を `""` に置き換える（RFC 4180）。
+///
+/// # 引数
+/// - `field`: フィールドの値
+///
+/// # 戻り値
+/// - エスケープしたフィールド
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
     }
+}
 
-    // 迎撃ミサイルのヘッダー
-    for interceptor in &state.interceptors {
-        header.push_str(&format!(
-            "{0}_x(m),{0}_y(m),{0}_z(m),{0}_pitch(deg),",
-            interceptor.id
-        ));
+/// エンティティの任意属性をCSVとして書き込む
+///
+/// 時系列の結果CSVとは別の表として、シミュレーション開始時に一度だけ書き込む。
+/// ヘッダー `id,key,value` に続けて、属性1つにつき1行を出力する。
+/// 行はミサイル・迎撃ミサイルの順、同じエンティティ内ではキーの辞書順とし、各フィールドはエスケープする。
+///
+/// # 引数
+/// - `writer`: 書き込み先
+/// - `state`: シミュレーション状態
+pub fn write_metadata_csv<W: Write>(writer: &mut W, state: &SimulationState) -> Result<(), std::io::Error> {
+    writeln!(writer, "id,key,value")?;
+    let entities = state
+        .missiles
+        .iter()
+        .map(|m| (&m.id, &m.metadata))
+        .chain(state.interceptors.iter().map(|i| (&i.id, &i.metadata)));
+    for (id, metadata) in entities {
+        let mut entries: Vec<_> = metadata.iter().collect();
+        entries.sort();
+        for (key, value) in entries {
+            writeln!(writer, "{},{},{}", escape_csv_field(id), escape_csv_field(key), escape_csv_field(value))?;
+        }
     }
+    Ok(())
+}
 
-    // レーダのヘッダー
-    for radar in &state.radars {
+/// 任意列を指定したCSVヘッダーの書き込み
+pub fn write_csv_header_with<W: Write>(
+    writer: &mut W,
+    state: &SimulationState,
+    output: &CsvOutput,
+) -> Result<(), std::io::Error> {
+    let columns = &output.columns;
+    let flight_angles = columns.contains(&CsvColumns::FlightAngles);
+    let mut header = String::from("time(s),");
+
+    // ミサイル・迎撃ミサイルのヘッダー
+    let ids = state
+        .missiles
+        .iter()
+        .map(|m| &m.id)
+        .chain(state.interceptors.iter().map(|i| &i.id));
+    for id in ids {
+        header.push_str(&format!("{0}_x(m),{0}_y(m),{0}_z(m),{0}_pitch(deg),", id));
+        if flight_angles {
+            header.push_str(&format!("{0}_fpa(deg),{0}_heading(deg),", id));
+        }
+        // 座標系の列（指定順）
+        for frame in columns.iter().filter_map(|column| frame_header(id, column)) {
+            header.push_str(&frame);
+        }
+    }
+
+    // レーダのヘッダー（探知結果と同じくレーダIDの辞書順）
+    for radar in radar_order(&state.radars).into_iter().map(|i| &state.radars[i]) {
         header.push_str(&format!(
             "{0}_detected(bool),{0}_detect_x(m),{0}_detect_y(m),{0}_detect_z(m),",
             radar.id
         ));
     }
 
+    // 診断用ヘッダー（ミサイルは積分器とフィルタ、迎撃ミサイルはフィルタのみ）
+    if columns.contains(&CsvColumns::Diagnostics) {
+        for missile in &state.missiles {
+            header.push_str(&format!(
+                "{0}_ab2_prev_f_x,{0}_ab2_prev_f_y,{0}_ab2_prev_f_z,{0}_lpf_previous_x,{0}_lpf_previous_y,{0}_lpf_previous_z,",
+                missile.id
+            ));
+        }
+        for interceptor in &state.interceptors {
+            header.push_str(&format!("{0}_lpf_previous_x,", interceptor.id));
+        }
+    }
+
+    // 飛行段階のヘッダー（ミサイルのみ）
+    if columns.contains(&CsvColumns::FlightPhase) {
+        for missile in &state.missiles {
+            header.push_str(&format!("{0}_phase,", missile.id));
+        }
+    }
+
     header.push('\n');
     writer.write_all(header.as_bytes())?;
     Ok(())
@@ -60,33 +246,128 @@ pub fn write_csv_header<W: Write>(
 /// CSV行の作成
 pub fn create_csv_row(
     time: &f64,
-    missiles: &Vec<Missile>,
-    interceptors: &Vec<Interceptor>,
-    _radars: &Vec<Radar>,
-    radar_detections: &Vec<(bool, [f64; 3])>,
+    missiles: &[Missile],
+    interceptors: &[Interceptor],
+    radars: &[Radar],
+    radar_detections: &[(bool, [f64; 3])],
 ) -> String {
-    let mut row = format!("{},", time);
+    create_csv_row_with(time, missiles, interceptors, radars, radar_detections, &CsvOutput::default())
+}
 
-    // ミサイルの状態
-    for missile in missiles {
-        row.push_str(&format!(
-            "{},{},{},{},",
-            missile.position[0],
-            missile.position[1],
-            missile.position[2],
-            missile.pitch
-        ));
+/// 任意列を指定したCSV行の作成（列の並びと数は `write_csv_header_with` と一致）
+///
+/// `CsvColumns::Diagnostics`・`CsvColumns::FlightPhase` の値はシミュレーション状態が必要なため空欄とする。
+/// 値を出力するには `create_state_csv_row` を使用すること。
+pub fn create_csv_row_with(
+    time: &f64,
+    missiles: &[Missile],
+    interceptors: &[Interceptor],
+    _radars: &[Radar],
+    radar_detections: &[(bool, [f64; 3])],
+    output: &CsvOutput,
+) -> String {
+    let mut row = csv_fields(time, missiles, interceptors, radar_detections, output);
+    row.push_str(&",".repeat(state_column_count(missiles.len(), interceptors.len(), &output.columns)));
+    row.push('\n');
+    row
+}
+
+/// シミュレーション状態から作成する列（積分器・フィルタの状態、飛行段階）の数
+fn state_column_count(n_missiles: usize, n_interceptors: usize, columns: &[CsvColumns]) -> usize {
+    let mut count = 0;
+    if columns.contains(&CsvColumns::Diagnostics) {
+        // ミサイルは x, y, z 軸の積分器とフィルタ、迎撃ミサイルはフィルタ1列
+        count += 6 * n_missiles + n_interceptors;
+    }
+    if columns.contains(&CsvColumns::FlightPhase) {
+        count += n_missiles;
     }
+    count
+}
 
-    // 迎撃ミサイルの状態
-    for interceptor in interceptors {
-        row.push_str(&format!(
-            "{},{},{},{},",
-            interceptor.position[0],
-            interceptor.position[1],
-            interceptor.position[2],
-            interceptor.pitch
-        ));
+/// シミュレーション状態からCSV行を作成（`CsvColumns::Diagnostics`・`CsvColumns::FlightPhase` を含む全列に対応）
+///
+/// # 引数
+/// - `time`: 時刻（s）
+/// - `state`: シミュレーション状態
+/// - `radar_detections`: 各レーダの探知結果
+/// - `output`: CSV出力の設定（追加で出力する任意列と座標系の基準）
+///
+/// # 戻り値
+/// - 改行付きのCSV行（列の並びは `write_csv_header_with` と一致）
+pub fn create_state_csv_row(
+    time: &f64,
+    state: &SimulationState,
+    radar_detections: &[(bool, [f64; 3])],
+    output: &CsvOutput,
+) -> String {
+    let mut row = state_csv_fields(time, state, radar_detections, output);
+    row.push('\n');
+    row
+}
+
+/// シミュレーション状態から作成する列（改行なし）
+fn state_csv_fields(
+    time: &f64,
+    state: &SimulationState,
+    radar_detections: &[(bool, [f64; 3])],
+    output: &CsvOutput,
+) -> String {
+    let columns = &output.columns;
+    let mut row = csv_fields(time, &state.missiles, &state.interceptors, radar_detections, output);
+
+    if columns.contains(&CsvColumns::Diagnostics) {
+        for (integrators, filters) in state.integrators.iter().zip(state.filters.iter()) {
+            // 初回ステップ前は前回値が無いため空欄
+            for integrator in integrators {
+                row.push_str(&format!("{},", integrator.prev_f.map_or(String::new(), |f| f.to_string())));
+            }
+            for filter in filters {
+                row.push_str(&format!("{},", filter.previous));
+            }
+        }
+        for filter in &state.interceptor_filters {
+            row.push_str(&format!("{},", filter.previous));
+        }
+    }
+
+    if columns.contains(&CsvColumns::FlightPhase) {
+        for i in 0..state.missiles.len() {
+            // 飛行段階を持たない状態では空欄
+            let phase = state.missile_phases.get(i).map_or("", |phase| phase.as_str());
+            row.push_str(&format!("{},", phase));
+        }
+    }
+
+    row
+}
+
+/// 時刻・エンティティ・探知結果の列（改行なし）
+fn csv_fields(
+    time: &f64,
+    missiles: &[Missile],
+    interceptors: &[Interceptor],
+    radar_detections: &[(bool, [f64; 3])],
+    output: &CsvOutput,
+) -> String {
+    let columns = &output.columns;
+    let flight_angles = columns.contains(&CsvColumns::FlightAngles);
+    let mut row = format!("{},", time);
+
+    // ミサイル・迎撃ミサイルの状態
+    let entities = missiles
+        .iter()
+        .map(|m| (&m.position, &m.velocity, m.pitch))
+        .chain(interceptors.iter().map(|i| (&i.position, &i.velocity, i.pitch)));
+    for (position, velocity, pitch) in entities {
+        row.push_str(&format!("{},{},{},{},", position[0], position[1], position[2], pitch));
+        if flight_angles {
+            let (fpa, heading) = self::flight_angles(velocity);
+            row.push_str(&format!("{},{},", fpa, heading));
+        }
+        for frame in columns.iter().filter_map(|column| frame_fields(position, column, output)) {
+            row.push_str(&frame);
+        }
     }
 
     // レーダの探知状況
@@ -100,6 +381,372 @@ pub fn create_csv_row(
         ));
     }
 
-    row.push('\n');
     row
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{IntegratorKind, SeededRng};
+    use crate::models::force::ForceScale;
+
+    fn missile_with_velocity(velocity: [f64; 3]) -> Missile {
+        Missile {
+            id: "m1".to_string(),
+            position: [10.0, 20.0, 30.0],
+            velocity,
+            pitch: 45.0,
+            pitch_rate: 0.0,
+            mass: 1000.0,
+            thrust: [0.0, 0.0, 0.0],
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_flight_angle_columns() {
+        let missiles = vec![missile_with_velocity([1.0, 0.0, 1.0])];
+        let row = create_csv_row_with(&0.5, &missiles, &[], &[], &[], &CsvOutput::with_columns(&[CsvColumns::FlightAngles]));
+        let fields: Vec<&str> = row.trim_end().trim_end_matches(',').split(',').collect();
+
+        assert_eq!(fields.len(), 7);
+        let fpa: f64 = fields[5].parse().unwrap();
+        let heading: f64 = fields[6].parse().unwrap();
+        assert!((fpa - 45.0).abs() < 1e-9);
+        assert_eq!(heading, 0.0);
+    }
+
+    #[test]
+    fn test_flight_angle_header_and_default_row() {
+        let state = SimulationState {
+            missiles: vec![missile_with_velocity([1.0, 0.0, 1.0])],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+        let mut header = Vec::new();
+        write_csv_header_with(&mut header, &state, &CsvOutput::with_columns(&[CsvColumns::FlightAngles])).unwrap();
+        assert_eq!(
+            String::from_utf8(header).unwrap(),
+            "time(s),m1_x(m),m1_y(m),m1_z(m),m1_pitch(deg),m1_fpa(deg),m1_heading(deg),\n"
+        );
+
+        // 任意列を指定しなければ従来どおり
+        let row = create_csv_row(&0.5, &state.missiles, &[], &[], &[]);
+        assert_eq!(row, "0.5,10,20,30,45,\n");
+    }
+
+    #[test]
+    fn test_flight_angles_heading_and_zero_velocity() {
+        let (fpa, heading) = flight_angles(&[0.0, 1.0, 0.0]);
+        assert_eq!(fpa, 0.0);
+        assert!((heading - 90.0).abs() < 1e-12);
+        assert_eq!(flight_angles(&[0.0, 0.0, 0.0]), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_diagnostic_columns_carry_post_step_state() {
+        use crate::config::parameters::MissileParameters;
+        use crate::math::low_pass_filter_states;
+        use crate::models::gravity::GravityModel;
+        use crate::models::motion::update_missiles;
+
+        let params = MissileParameters {
+            mass_initial: 1000.0,
+            area: 1.0,
+            thrust: [2000.0, 0.0, 0.0],
+            ..Default::default()
+        };
+        let mut state = SimulationState {
+            missiles: vec![Missile {
+                position: [0.0, 0.0, 20000.0],
+                velocity: [100.0, 0.0, 0.0],
+                thrust: [2000.0, 0.0, 0.0],
+                ..missile_with_velocity([0.0, 0.0, 0.0])
+            }],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states([100.0, 0.0, 0.0])],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        };
+
+        let mut header = Vec::new();
+        write_csv_header_with(&mut header, &state, &CsvOutput::with_columns(&[CsvColumns::Diagnostics])).unwrap();
+        let header = String::from_utf8(header).unwrap();
+        assert!(header.ends_with(
+            "m1_pitch(deg),m1_ab2_prev_f_x,m1_ab2_prev_f_y,m1_ab2_prev_f_z,\
+             m1_lpf_previous_x,m1_lpf_previous_y,m1_lpf_previous_z,\n"
+        ));
+
+        // 初回ステップ前は積分器の前回値が空欄、フィルタは初期速度
+        let row = create_state_csv_row(&0.0, &state, &[], &CsvOutput::with_columns(&[CsvColumns::Diagnostics]));
+        assert!(row.ends_with(",,,,100,0,0,\n"), "{}", row);
+
+        let (missiles, integrators, filters, _) =
+            update_missiles(&state, &params, &GravityModel::default(), 0.1).unwrap();
+        state.missiles = missiles;
+        state.integrators = integrators;
+        state.filters = filters;
+
+        let row = create_state_csv_row(&0.1, &state, &[], &CsvOutput::with_columns(&[CsvColumns::Diagnostics]));
+        let fields: Vec<&str> = row.trim_end().trim_end_matches(',').split(',').collect();
+        assert_eq!(fields.len(), header.trim_end().trim_end_matches(',').split(',').count());
+
+        // x軸には加速度 2000 N / 1000 kg = 2 m/s²、z軸には重力加速度が積分器に記録される
+        let prev_f: Vec<f64> = fields[5..8].iter().map(|f| f.parse().unwrap()).collect();
+        let previous: Vec<f64> = fields[8..11].iter().map(|f| f.parse().unwrap()).collect();
+        assert_eq!(prev_f, vec![2.0, 0.0, -9.81]);
+        for (axis, previous) in previous.iter().enumerate() {
+            assert_eq!(*previous, state.filters[0][axis].previous);
+            assert_eq!(*previous, state.missiles[0].velocity[axis]);
+        }
+    }
+
+    #[test]
+    fn test_scenario_metadata_appears_in_metadata_csv() {
+        use crate::config::parameters::{InterceptorParameters, MissileParameters, RadarParameters};
+        use crate::config::scenario::Scenario;
+        use crate::simulation::framework::initialize_simulation_state;
+
+        let scenario: Scenario = serde_yaml::from_str(
+            "missiles:\n\
+             - id: m1\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [100.0, 0.0, 50.0]\n  initial_pitch: 45.0\n\
+             \x20 metadata: {team: red, type: srbm, label: 'Wave 1, \"alpha\"'}\n\
+             - id: m2\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [100.0, 0.0, 50.0]\n  initial_pitch: 45.0\n\
+             radars: []\n\
+             interceptors:\n\
+             - id: i1\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [0.0, 0.0, 0.0]\n  initial_pitch: 90.0\n\
+             \x20 metadata: {team: blue}\n",
+        )
+        .unwrap();
+        let state = initialize_simulation_state(
+            MissileParameters {
+                mass_initial: 1000.0,
+                area: 1.0,
+                ..Default::default()
+            },
+            RadarParameters {
+                azimuth_min: 0.0,
+                azimuth_max: 360.0,
+                elevation_min: -90.0,
+                elevation_max: 90.0,
+                detection_range: 1000.0,
+            },
+            InterceptorParameters {
+                mass_initial: 500.0,
+                navigation_coefficient: 3.0,
+            },
+            scenario,
+        )
+        .unwrap();
+        assert_eq!(state.missiles[0].metadata["team"], "red");
+
+        let mut metadata = Vec::new();
+        write_metadata_csv(&mut metadata, &state).unwrap();
+        assert_eq!(
+            String::from_utf8(metadata).unwrap(),
+            "id,key,value\n\
+             m1,label,\"Wave 1, \"\"alpha\"\"\"\n\
+             m1,team,red\n\
+             m1,type,srbm\n\
+             i1,team,blue\n"
+        );
+
+        // 結果CSVはヘッダー行から始まり、属性を含まない
+        let mut header = Vec::new();
+        write_csv_header(&mut header, &state).unwrap();
+        assert!(String::from_utf8(header).unwrap().starts_with("time(s),m1_x(m)"));
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("red"), "red");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn test_flight_phase_column() {
+        use crate::math::low_pass_filter_states;
+        use crate::models::missile::FlightPhase;
+
+        let state = SimulationState {
+            missiles: vec![
+                missile_with_velocity([0.0, 0.0, 10.0]),
+                Missile {
+                    id: "m2".to_string(),
+                    ..missile_with_velocity([0.0, 0.0, -10.0])
+                },
+            ],
+            integrators: vec![Default::default(); 2],
+            filters: vec![low_pass_filter_states([0.0; 3]); 2],
+            thrust_rngs: vec![SeededRng::new(0); 2],
+            integrator_kinds: vec![IntegratorKind::Ab2; 2],
+            force_scales: vec![ForceScale::default(); 2],
+            missile_phases: vec![FlightPhase::Coast, FlightPhase::Descent],
+            ..Default::default()
+        };
+
+        let mut header = Vec::new();
+        write_csv_header_with(&mut header, &state, &CsvOutput::with_columns(&[CsvColumns::FlightPhase])).unwrap();
+        let header = String::from_utf8(header).unwrap();
+        assert!(header.ends_with("m2_pitch(deg),m1_phase,m2_phase,\n"));
+
+        let row = create_state_csv_row(&0.5, &state, &[], &CsvOutput::with_columns(&[CsvColumns::FlightPhase]));
+        assert!(row.ends_with(",coast,descent,\n"));
+        assert_eq!(row.split(',').count(), header.split(',').count());
+        // 列を指定しなければ飛行段階は出力しない
+        assert!(!create_state_csv_row(&0.5, &state, &[], &CsvOutput::default()).contains("coast"));
+    }
+
+    #[test]
+    fn test_multi_frame_columns_are_consistent() {
+        let origin = GeodeticPosition {
+            latitude: 35.0,
+            longitude: 139.0,
+            altitude: 0.0,
+        };
+        let output = CsvOutput {
+            columns: vec![CsvColumns::Enu, CsvColumns::Geodetic, CsvColumns::RangeBearing],
+            geodetic_origin: origin,
+            range_reference: [0.0, 0.0, 0.0],
+        };
+        let missile = Missile {
+            position: [3000.0, 4000.0, 0.0],
+            ..missile_with_velocity([1.0, 0.0, 0.0])
+        };
+        let state = SimulationState {
+            missiles: vec![missile.clone()],
+            ..Default::default()
+        };
+        let mut header = Vec::new();
+        write_csv_header_with(&mut header, &state, &output).unwrap();
+        assert_eq!(
+            String::from_utf8(header).unwrap(),
+            "time(s),m1_x(m),m1_y(m),m1_z(m),m1_pitch(deg),\
+             m1_east(m),m1_north(m),m1_up(m),m1_lat(deg),m1_lon(deg),m1_alt(m),\
+             m1_range(m),m1_bearing(deg),m1_elevation(deg),\n"
+        );
+
+        let row = create_csv_row_with(&1.0, &[missile], &[], &[], &[], &output);
+        let fields: Vec<f64> = row
+            .trim_end()
+            .trim_end_matches(',')
+            .split(',')
+            .map(|f| f.parse().unwrap())
+            .collect();
+        assert_eq!(fields.len(), 14);
+        // ENU はシミュレーション座標そのもの
+        assert_eq!(&fields[5..8], &[3000.0, 4000.0, 0.0]);
+        // 測地座標を ENU に戻すと一致する（北へ 4 km で緯度が増え、東へ 3 km で経度が増える）
+        let geodetic = GeodeticPosition {
+            latitude: fields[8],
+            longitude: fields[9],
+            altitude: fields[10],
+        };
+        assert!(geodetic.latitude > 35.0 && geodetic.longitude > 139.0);
+        let enu = crate::math::geodetic_to_enu(&geodetic, &origin);
+        for k in 0..3 {
+            assert!((enu[k] - fields[5 + k]).abs() < 1e-6);
+        }
+        // 距離 5 km、方位 atan2(東, 北) ≈ 36.87 度、仰角 0
+        assert!((fields[11] - 5000.0).abs() < 1e-9);
+        assert!((fields[12] - 3.0_f64.atan2(4.0).to_degrees()).abs() < 1e-9);
+        assert_eq!(fields[13], 0.0);
+    }
+
+    #[test]
+    fn test_range_bearing_quadrants() {
+        assert_eq!(range_bearing(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0]), (0.0, 0.0, 0.0));
+        let (_, west, _) = range_bearing(&[-100.0, 0.0, 0.0], &[0.0, 0.0, 0.0]);
+        assert!((west - 270.0).abs() < 1e-9);
+        let (range, south, elevation) = range_bearing(&[10.0, -100.0, 100.0], &[10.0, 0.0, 0.0]);
+        assert!((range - 100.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((south - 180.0).abs() < 1e-9);
+        assert!((elevation - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rows_have_one_field_per_header_column() {
+        use crate::math::{low_pass_filter_states, AdamsBashforth2State, LowPassFilterState};
+        use crate::models::missile::FlightPhase;
+
+        let state = SimulationState {
+            missiles: vec![
+                missile_with_velocity([1.0, 0.0, 1.0]),
+                Missile {
+                    id: "m2".to_string(),
+                    ..missile_with_velocity([0.0, 1.0, 0.0])
+                },
+            ],
+            radars: vec![Radar {
+                id: "r1".to_string(),
+                position: [0.0, 0.0, 0.0],
+                detection_range: 1000.0,
+                azimuth_min: 0.0,
+                azimuth_max: 360.0,
+                elevation_min: -90.0,
+                elevation_max: 90.0,
+            }],
+            interceptors: vec![Interceptor {
+                id: "i1".to_string(),
+                position: [0.0, 0.0, 0.0],
+                velocity: [0.0, 0.0, 10.0],
+                pitch: 90.0,
+                mass: 500.0,
+                metadata: Default::default(),
+            }],
+            integrators: vec![core::array::from_fn(|_| AdamsBashforth2State { prev_f: Some(2.0) }); 2],
+            filters: vec![low_pass_filter_states([1.0; 3]); 2],
+            interceptor_filters: vec![LowPassFilterState { previous: 0.0 }],
+            missile_phases: vec![FlightPhase::Boost, FlightPhase::Coast],
+            ..Default::default()
+        };
+        let output = CsvOutput::with_columns(&[
+            CsvColumns::FlightAngles,
+            CsvColumns::Diagnostics,
+            CsvColumns::FlightPhase,
+            CsvColumns::Enu,
+            CsvColumns::RangeBearing,
+        ]);
+        let detections = [(true, [10.0, 20.0, 30.0])];
+
+        let mut header = Vec::new();
+        write_csv_header_with(&mut header, &state, &output).unwrap();
+        let header = String::from_utf8(header).unwrap();
+        let header_fields = header.trim_end().split(',').count();
+
+        let row = create_csv_row_with(&0.5, &state.missiles, &state.interceptors, &state.radars, &detections, &output);
+        assert_eq!(row.trim_end().split(',').count(), header_fields, "{}\n{}", header, row);
+        // 状態が必要な列（診断 6 * 2 + 1、飛行段階 2）は空欄で末尾に並ぶ
+        assert!(row.ends_with(&format!("{}\n", ",".repeat(16))));
+
+        let row = create_state_csv_row(&0.5, &state, &detections, &output);
+        assert_eq!(row.trim_end().split(',').count(), header_fields, "{}\n{}", header, row);
+        assert!(row.ends_with(",2,2,2,1,1,1,2,2,2,1,1,1,0,boost,coast,\n"));
+    }
+
+    #[test]
+    fn test_csv_output_from_yaml() {
+        let output: CsvOutput = serde_yaml::from_str(
+            "columns: [flight_phase, enu, geodetic, range_bearing]\n\
+             geodetic_origin: {latitude: 35.0, longitude: 139.0, altitude: 10.0}\n\
+             range_reference: [50000.0, 0.0, 0.0]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            output.columns,
+            [CsvColumns::FlightPhase, CsvColumns::Enu, CsvColumns::Geodetic, CsvColumns::RangeBearing]
+        );
+        assert_eq!(output.geodetic_origin.altitude, 10.0);
+        assert_eq!(output.range_reference, [50000.0, 0.0, 0.0]);
+
+        // 省略時は任意列なし
+        assert_eq!(serde_yaml::from_str::<CsvOutput>("{}").unwrap(), CsvOutput::default());
+        assert!(serde_yaml::from_str::<CsvOutput>("columns: [polar]\n").is_err());
+    }
+}
@@ -1,20 +1,101 @@
 // src/simulation/framework.rs
 
 use std::error::Error;
+use std::sync::Arc;
 
 use crate::{Missile, Radar, Interceptor};
-use crate::math::{AdamsBashforth2State, LowPassFilterState};
+use crate::math::{low_pass_filter_states, AdamsBashforth2State, IntegratorKind, LowPassFilterState, SeededRng};
 use crate::simulation::SimulationState;
 use crate::config::parameters::{MissileParameters, RadarParameters, InterceptorParameters};
 use crate::config::scenario::Scenario;
+use crate::models::force::ForceScale;
+use crate::models::gravity::GravityModel;
+use crate::models::missile::{flight_phase, FlightPhase};
+use crate::simulation::load_parameters::LoadError;
 
-/// シミュレーションステートの初期化
+/// シナリオに含められるエンティティ数（ミサイル・レーダ・迎撃ミサイルの合計）の既定の上限
+pub const DEFAULT_MAX_ENTITIES: usize = 100_000;
+
+/// ミサイルごとの推進力ベクトルを決定する
+///
+/// 優先順位は、シナリオの推進力ベクトル、シナリオの推進力の大きさ（パラメータの向きを維持）、
+/// パラメータの推進力ベクトルの順。パラメータの推進力がゼロの場合、大きさの指定は無視する。
+///
+/// # 引数
+/// - `thrust`: シナリオで指定された推進力ベクトル
+/// - `thrust_magnitude`: シナリオで指定された推進力の大きさ
+/// - `default_thrust`: パラメータの推進力ベクトル
+///
+/// # 戻り値
+/// - 推進力ベクトル [Fx, Fy, Fz]
+pub fn resolve_thrust(thrust: Option<[f64; 3]>, thrust_magnitude: Option<f64>, default_thrust: &[f64; 3]) -> [f64; 3] {
+    if let Some(thrust) = thrust {
+        return thrust;
+    }
+    let norm = (default_thrust[0].powi(2) + default_thrust[1].powi(2) + default_thrust[2].powi(2)).sqrt();
+    match thrust_magnitude {
+        Some(magnitude) if norm > 0.0 => {
+            let scale = magnitude / norm;
+            [default_thrust[0] * scale, default_thrust[1] * scale, default_thrust[2] * scale]
+        }
+        _ => *default_thrust,
+    }
+}
+
+/// シミュレーションステートの初期化（エンティティ数の上限は `DEFAULT_MAX_ENTITIES`）
 pub fn initialize_simulation_state(
     missile_params: MissileParameters,
     radar_params: RadarParameters,
     interceptor_params: InterceptorParameters,
     scenario: Scenario,
-) -> SimulationState {
+) -> Result<SimulationState, LoadError> {
+    initialize_simulation_state_with_limit(
+        missile_params,
+        radar_params,
+        interceptor_params,
+        scenario,
+        DEFAULT_MAX_ENTITIES,
+    )
+}
+
+/// エンティティ数の上限を指定したシミュレーションステートの初期化
+///
+/// 巨大なシナリオによるメモリ枯渇を防ぐため、エンティティ数の合計が上限を超える場合は初期化しない。
+///
+/// # 引数
+/// - `max_entities`: ミサイル・レーダ・迎撃ミサイルの合計数の上限
+///
+/// # 戻り値
+/// - 初期化した状態、または上限を超えた場合の `LoadError::TooManyEntities`
+pub fn initialize_simulation_state_with_limit(
+    missile_params: MissileParameters,
+    radar_params: RadarParameters,
+    interceptor_params: InterceptorParameters,
+    scenario: Scenario,
+    max_entities: usize,
+) -> Result<SimulationState, LoadError> {
+    let count = scenario.missiles.len() + scenario.radars.len() + scenario.interceptors.len();
+    if count > max_entities {
+        return Err(LoadError::TooManyEntities { count, max: max_entities });
+    }
+
+    // ミサイルごとの積分方式
+    let integrator_kinds: Vec<IntegratorKind> = scenario.missiles.iter().map(|m| m.integrator).collect();
+    // ミサイルごとの空気抵抗・推進力の倍率
+    let force_scales: Vec<ForceScale> = scenario
+        .missiles
+        .iter()
+        .map(|m| ForceScale {
+            drag: m.drag_scale.unwrap_or(1.0),
+            thrust: m.thrust_scale.unwrap_or(1.0),
+        })
+        .collect();
+
+    // ミサイル・迎撃ミサイルの出現時刻
+    let missile_spawn_times: Vec<f64> = scenario.missiles.iter().map(|m| m.spawn_time.unwrap_or(0.0)).collect();
+    let interceptor_spawn_times: Vec<f64> =
+        scenario.interceptors.iter().map(|i| i.spawn_time.unwrap_or(0.0)).collect();
+
     // ミサイルの初期化
     let missiles: Vec<Missile> = scenario
         .missiles
@@ -24,7 +105,10 @@ pub fn initialize_simulation_state(
             position: m.initial_position,
             velocity: m.initial_velocity,
             pitch: m.initial_pitch,
-            mass: missile_params.mass_initial,
+            pitch_rate: 0.0,
+            mass: m.mass.unwrap_or(missile_params.mass_initial),
+            thrust: resolve_thrust(m.thrust, m.thrust_magnitude, &missile_params.thrust),
+            metadata: Arc::new(m.metadata),
         })
         .collect();
 
@@ -53,25 +137,44 @@ pub fn initialize_simulation_state(
             velocity: i.initial_velocity,
             pitch: i.initial_pitch,
             mass: interceptor_params.mass_initial,
+            metadata: Arc::new(i.metadata),
         })
         .collect();
 
     // 積分器とフィルタの初期状態
-    let integrators: Vec<AdamsBashforth2State> =
-        vec![AdamsBashforth2State { prev_f: None }; missiles.len()];
-    let filters: Vec<LowPassFilterState> =
-        vec![LowPassFilterState { previous: 0.0 }; missiles.len()];
+    let integrators: Vec<[AdamsBashforth2State; 3]> = vec![Default::default(); missiles.len()];
+    let filters: Vec<[LowPassFilterState; 3]> = missiles.iter().map(|m| low_pass_filter_states(m.velocity)).collect();
     let interceptor_filters: Vec<LowPassFilterState> =
         vec![LowPassFilterState { previous: 0.0 }; interceptors.len()];
+    // 推力ノイズ用の乱数生成器（マスターシードとミサイルIDから導出した独立な系列）
+    let thrust_rngs: Vec<SeededRng> = missiles
+        .iter()
+        .map(|m| SeededRng::for_entity(scenario.seed, &m.id))
+        .collect();
+
+    // 出現時の質量（燃料消費後も概要の総質量に用いる）
+    let missile_initial_masses: Vec<f64> = missiles.iter().map(|m| m.mass).collect();
+    let interceptor_initial_masses: Vec<f64> = interceptors.iter().map(|i| i.mass).collect();
+    // 初期の飛行段階（推力があれば Boost、なければ Coast）
+    let missile_phases: Vec<FlightPhase> = missiles.iter().map(|m| flight_phase(m, &missile_params, None)).collect();
 
-    SimulationState {
+    Ok(SimulationState {
         missiles,
         radars,
         interceptors,
         integrators,
         filters,
         interceptor_filters,
-    }
+        thrust_rngs,
+        integrator_kinds,
+        force_scales,
+        time: 0.0,
+        missile_spawn_times,
+        interceptor_spawn_times,
+        missile_initial_masses,
+        interceptor_initial_masses,
+        missile_phases,
+    })
 }
 
 /// シミュレーションステップの実行
@@ -79,17 +182,28 @@ pub fn execute_simulation_step(
     state: &SimulationState,
     missile_params: &MissileParameters,
     interceptor_params: &InterceptorParameters,
-    gravity: [f64; 3],
+    gravity: &GravityModel,
     dt: f64,
 ) -> Result<SimulationState, Box<dyn Error>> {
     // ミサイルの更新
-    let (updated_missiles, updated_integrators, updated_filters) =
+    let (updated_missiles, updated_integrators, updated_filters, updated_thrust_rngs) =
         crate::models::motion::update_missiles(state, missile_params, gravity, dt)?;
 
     // 迎撃ミサイルの更新
     let (updated_interceptors, updated_interceptor_filters) =
         crate::models::motion::update_interceptors(state, interceptor_params, dt)?;
 
+    // 飛行段階の更新（前ステップの状態と段階から判定）
+    let missile_phases: Vec<FlightPhase> = updated_missiles
+        .iter()
+        .zip(state.missiles.iter())
+        .enumerate()
+        .map(|(i, (missile, previous))| {
+            let previous_phase = state.missile_phases.get(i).copied().unwrap_or(FlightPhase::Coast);
+            flight_phase(missile, missile_params, Some((previous, previous_phase)))
+        })
+        .collect();
+
     Ok(SimulationState {
         missiles: updated_missiles,
         radars: state.radars.clone(),
@@ -97,5 +211,424 @@ pub fn execute_simulation_step(
         integrators: updated_integrators,
         filters: updated_filters,
         interceptor_filters: updated_interceptor_filters,
+        thrust_rngs: updated_thrust_rngs,
+        integrator_kinds: state.integrator_kinds.clone(),
+        force_scales: state.force_scales.clone(),
+        time: state.time + dt,
+        missile_spawn_times: state.missile_spawn_times.clone(),
+        interceptor_spawn_times: state.interceptor_spawn_times.clone(),
+        missile_initial_masses: state.missile_initial_masses.clone(),
+        interceptor_initial_masses: state.interceptor_initial_masses.clone(),
+        missile_phases,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::scenario::{InterceptorInstance, MissileInstance, RadarInstance, ScenarioBuilder};
+    use crate::simulation::StateSummary;
+
+    fn missile_params() -> MissileParameters {
+        MissileParameters {
+            mass_initial: 5000.0,
+            fuel_consumption_rate: 10.0,
+            drag_coefficient: 0.3,
+            area: 1.0,
+            thrust: [5000.0, 0.0, 0.0],
+            ..Default::default()
+        }
+    }
+
+    fn radar_params() -> RadarParameters {
+        RadarParameters {
+            azimuth_min: 0.0,
+            azimuth_max: 360.0,
+            elevation_min: -90.0,
+            elevation_max: 90.0,
+            detection_range: 100000.0,
+        }
+    }
+
+    fn interceptor_params() -> InterceptorParameters {
+        InterceptorParameters {
+            mass_initial: 2000.0,
+            navigation_coefficient: 3.0,
+        }
+    }
+
+    fn missile_instance(id: &str, mass: Option<f64>) -> MissileInstance {
+        MissileInstance {
+            id: id.to_string(),
+            initial_position: [0.0, 0.0, 0.0],
+            initial_velocity: [100.0, 0.0, 50.0],
+            initial_pitch: 45.0,
+            mass,
+            thrust: None,
+            thrust_magnitude: None,
+            metadata: Default::default(),
+            integrator: Default::default(),
+            drag_scale: None,
+            thrust_scale: None,
+            spawn_time: None,
+        }
+    }
+
+    #[test]
+    fn test_initialize_missile_mass_override() {
+        let scenario = Scenario {
+            missiles: vec![
+                missile_instance("light", Some(1000.0)),
+                missile_instance("heavy", Some(8000.0)),
+                missile_instance("default", None),
+            ],
+            radars: vec![],
+            interceptors: vec![],
+            seed: 0,
+        };
+
+        let state = initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+
+        assert_eq!(state.missiles[0].mass, 1000.0);
+        assert_eq!(state.missiles[1].mass, 8000.0);
+        assert_eq!(state.missiles[2].mass, 5000.0);
+    }
+
+    #[test]
+    fn test_force_scales_from_scenario() {
+        let scaled = |drag_scale: Option<f64>, thrust_scale: Option<f64>| MissileInstance {
+            drag_scale,
+            thrust_scale,
+            ..missile_instance("m1", None)
+        };
+        let run = |instance: MissileInstance| {
+            let scenario = Scenario {
+                missiles: vec![instance],
+                radars: vec![],
+                interceptors: vec![],
+                seed: 0,
+            };
+            let state =
+                initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+            let scale = state.force_scales[0];
+            let next = execute_simulation_step(
+                &state,
+                &missile_params(),
+                &interceptor_params(),
+                &GravityModel::default(),
+                0.1,
+            )
+            .unwrap();
+            (scale, next.missiles[0].velocity[0])
+        };
+
+        // 未指定時は倍率 1.0 で、明示的に 1.0 を指定した場合と同じ結果
+        let (default_scale, default_vx) = run(scaled(None, None));
+        assert_eq!(default_scale, ForceScale::default());
+        assert_eq!(run(scaled(Some(1.0), Some(1.0))).1, default_vx);
+
+        // 推進力を増やすと加速が大きく、空気抵抗を増やすと小さくなる
+        let (scale, thrust_vx) = run(scaled(None, Some(2.0)));
+        assert_eq!(scale, ForceScale { drag: 1.0, thrust: 2.0 });
+        assert!(thrust_vx > default_vx);
+        assert!(run(scaled(Some(1.5), None)).1 < default_vx);
+    }
+
+    #[test]
+    fn test_spawn_time_delays_missile() {
+        let spawn = |spawn_time: Option<f64>| {
+            let scenario = Scenario {
+                missiles: vec![MissileInstance {
+                    initial_position: [1000.0, 0.0, 0.0],
+                    spawn_time,
+                    ..missile_instance("m1", None)
+                }],
+                radars: vec![RadarInstance {
+                    id: "r1".to_string(),
+                    position: [0.0, 0.0, 0.0],
+                }],
+                interceptors: vec![],
+                seed: 0,
+            };
+            initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap()
+        };
+        let step = |state: &SimulationState| {
+            execute_simulation_step(state, &missile_params(), &interceptor_params(), &GravityModel::default(), 0.1)
+                .unwrap()
+        };
+        let detected = |state: &SimulationState| {
+            crate::models::motion::detect_all_radars(&state.radars, &state.spawned_missiles())[0].0
+        };
+
+        // t = 10 s までは動かず、探知もされない
+        let mut delayed = spawn(Some(10.0));
+        for _ in 0..100 {
+            assert!(!detected(&delayed));
+            delayed = step(&delayed);
+            assert_eq!(delayed.missiles[0].position, [1000.0, 0.0, 0.0]);
+            assert_eq!(delayed.missiles[0].mass, 5000.0);
+        }
+        assert!((delayed.time - 10.0).abs() < 1e-9);
+        assert!(detected(&delayed));
+
+        // 出現後は開始時から存在するミサイルと同じ運動をする
+        let mut reference = spawn(None);
+        assert!(detected(&reference));
+        for _ in 0..20 {
+            delayed = step(&delayed);
+            reference = step(&reference);
+        }
+        assert_eq!(delayed.missiles[0], reference.missiles[0]);
+    }
+
+    #[test]
+    fn test_spawn_time_delays_interceptor() {
+        let scenario = Scenario {
+            missiles: vec![missile_instance("m1", None)],
+            radars: vec![],
+            interceptors: vec![InterceptorInstance {
+                id: "i1".to_string(),
+                initial_position: [10000.0, 0.0, 0.0],
+                initial_velocity: [0.0, 0.0, 0.0],
+                initial_pitch: 90.0,
+                metadata: Default::default(),
+                spawn_time: Some(1.0),
+            }],
+            seed: 0,
+        };
+        let mut state =
+            initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+        let mut positions = Vec::new();
+        for _ in 0..20 {
+            state = execute_simulation_step(&state, &missile_params(), &interceptor_params(), &GravityModel::default(), 0.1)
+                .unwrap();
+            positions.push(state.interceptors[0].position);
+        }
+        // 出現前は静止し、出現後に誘導されて動き出す
+        assert_eq!(positions[9], [10000.0, 0.0, 0.0]);
+        assert_ne!(positions[19], [10000.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_summary_and_entity_ids_match_scenario() {
+        let scenario = Scenario {
+            missiles: vec![missile_instance("m1", None), missile_instance("m2", Some(3000.0))],
+            radars: vec![RadarInstance {
+                id: "r1".to_string(),
+                position: [0.0, 0.0, 0.0],
+            }],
+            interceptors: vec![InterceptorInstance {
+                id: "i1".to_string(),
+                initial_position: [0.0, 0.0, 0.0],
+                initial_velocity: [0.0, 0.0, 0.0],
+                initial_pitch: 90.0,
+                metadata: Default::default(),
+                spawn_time: None,
+            }],
+            seed: 0,
+        };
+
+        let state = initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+
+        assert_eq!(
+            state.summary(),
+            StateSummary {
+                n_missiles: 2,
+                n_radars: 1,
+                n_interceptors: 1,
+                total_initial_mass: 5000.0 + 3000.0 + 2000.0,
+            }
+        );
+        assert_eq!(state.entity_ids(), vec!["m1", "m2", "r1", "i1"]);
+
+        // 燃料を消費しても出現時の総質量は変わらない
+        let stepped =
+            execute_simulation_step(&state, &missile_params(), &interceptor_params(), &GravityModel::default(), 0.1)
+                .unwrap();
+        assert!(stepped.missiles[0].mass < state.missiles[0].mass);
+        assert_eq!(stepped.summary(), state.summary());
+    }
+
+    #[test]
+    fn test_instance_thrust_overrides_params() {
+        let booster = MissileInstance {
+            thrust: Some([20000.0, 0.0, 0.0]),
+            ..missile_instance("booster", None)
+        };
+        let scaled = MissileInstance {
+            thrust_magnitude: Some(2500.0),
+            ..missile_instance("scaled", None)
+        };
+        let scenario = Scenario {
+            missiles: vec![booster, missile_instance("default", None), scaled],
+            radars: vec![],
+            interceptors: vec![],
+            seed: 0,
+        };
+
+        let mut state = initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+        assert_eq!(state.missiles[0].thrust, [20000.0, 0.0, 0.0]);
+        assert_eq!(state.missiles[1].thrust, [5000.0, 0.0, 0.0]);
+        assert_eq!(state.missiles[2].thrust, [2500.0, 0.0, 0.0]);
+
+        // 同じ初速から、推力の大きいミサイルほどx方向に大きく加速する
+        for _ in 0..2 {
+            state = execute_simulation_step(
+                &state,
+                &missile_params(),
+                &interceptor_params(),
+                &GravityModel::default(),
+                0.1,
+            )
+            .unwrap();
+        }
+        let vx: Vec<f64> = state.missiles.iter().map(|m| m.velocity[0]).collect();
+        assert!(vx[0] > vx[1] && vx[1] > vx[2], "vx = {:?}", vx);
+    }
+
+    #[test]
+    fn test_adding_missile_keeps_other_thrust_noise_streams() {
+        let scenario = |ids: &[&str]| Scenario {
+            missiles: ids.iter().map(|id| missile_instance(id, None)).collect(),
+            radars: vec![],
+            interceptors: vec![],
+            seed: 2024,
+        };
+        let draws = |state: &SimulationState| -> Vec<Vec<f64>> {
+            state
+                .thrust_rngs
+                .iter()
+                .map(|rng| {
+                    let mut rng = rng.clone();
+                    (0..5).map(|_| rng.normal(0.0, 1.0)).collect()
+                })
+                .collect()
+        };
+
+        let two = initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario(&["m1", "m2"]))
+            .unwrap();
+        let three = initialize_simulation_state(
+            missile_params(),
+            radar_params(),
+            interceptor_params(),
+            scenario(&["m1", "m2", "m3"]),
+        )
+        .unwrap();
+        let (two, three) = (draws(&two), draws(&three));
+        assert_eq!(three[..2], two[..]);
+        assert_ne!(three[2], three[0]);
+        assert_ne!(three[0], three[1]);
+    }
+
+    #[test]
+    fn test_entity_cap_rejects_oversized_scenario() {
+        let scenario = ScenarioBuilder::new()
+            .missile("m1", [0.0, 0.0, 0.0], [100.0, 0.0, 50.0], 45.0)
+            .missile("m2", [0.0, 0.0, 0.0], [100.0, 0.0, 50.0], 45.0)
+            .radar("r1", [0.0, 0.0, 0.0])
+            .interceptor("i1", [0.0, 0.0, 0.0], [0.0, 0.0, 0.0], 90.0)
+            .build();
+
+        let result = initialize_simulation_state_with_limit(
+            missile_params(),
+            radar_params(),
+            interceptor_params(),
+            scenario.clone(),
+            3,
+        );
+        assert!(matches!(result, Err(LoadError::TooManyEntities { count: 4, max: 3 })));
+
+        let state = initialize_simulation_state_with_limit(missile_params(), radar_params(), interceptor_params(), scenario, 4)
+            .unwrap();
+        assert_eq!(state.entity_ids().len(), 4);
+    }
+
+    #[test]
+    fn test_per_missile_integrator_from_scenario() {
+        use crate::math::IntegratorKind;
+
+        let scenario: Scenario = serde_yaml::from_str(
+            "missiles:\n\
+             - id: euler\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [2000.0, 0.0, 0.0]\n  initial_pitch: 0.0\n\
+             \x20 integrator: euler\n\
+             - id: rk4\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [2000.0, 0.0, 0.0]\n  initial_pitch: 0.0\n\
+             \x20 integrator: rk4\n\
+             - id: default\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [2000.0, 0.0, 0.0]\n  initial_pitch: 0.0\n\
+             radars: []\n\
+             interceptors: []\n",
+        )
+        .unwrap();
+        let mut state =
+            initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+        assert_eq!(
+            state.integrator_kinds,
+            vec![IntegratorKind::Euler, IntegratorKind::Rk4, IntegratorKind::Ab2]
+        );
+
+        // 速度に依存する空気抵抗があるため、積分方式ごとに軌道が異なる
+        for _ in 0..5 {
+            state = execute_simulation_step(
+                &state,
+                &missile_params(),
+                &interceptor_params(),
+                &GravityModel::default(),
+                0.1,
+            )
+            .unwrap();
+        }
+        let euler = &state.missiles[0];
+        let rk4 = &state.missiles[1];
+        assert_ne!(euler.velocity[0], rk4.velocity[0]);
+        assert_ne!(euler.position[0], rk4.position[0]);
+    }
+
+    #[test]
+    fn test_flight_phase_is_updated_each_step() {
+        use crate::models::missile::FlightPhase;
+
+        // 燃料消費 10 kg/s、燃焼終了質量 4990 kg → 1 s で燃焼終了
+        let params = MissileParameters {
+            burnout_mass: 4990.0,
+            ..missile_params()
+        };
+        let scenario = ScenarioBuilder::new()
+            .missile("climbing", [0.0, 0.0, 1000.0], [100.0, 0.0, 50.0], 45.0)
+            .missile("falling", [0.0, 0.0, 1000.0], [100.0, 0.0, -50.0], -45.0)
+            .build();
+        let mut state =
+            initialize_simulation_state(params.clone(), radar_params(), interceptor_params(), scenario).unwrap();
+        assert_eq!(state.missile_phases, vec![FlightPhase::Boost; 2]);
+
+        let mut phases = Vec::new();
+        for _ in 0..20 {
+            state = execute_simulation_step(&state, &params, &interceptor_params(), &GravityModel::default(), 0.1)
+                .unwrap();
+            phases.push(state.missile_phases.clone());
+        }
+        // 燃焼中は Boost、燃焼終了後は上昇中（vz > 0）なら Coast、降下中（vz < 0）なら Descent
+        assert_eq!(phases[5], vec![FlightPhase::Boost; 2]);
+        assert_eq!(phases[19], vec![FlightPhase::Coast, FlightPhase::Descent]);
+    }
+
+    #[test]
+    fn test_metadata_is_shared_across_steps() {
+        let scenario: Scenario = serde_yaml::from_str(
+            "missiles:\n\
+             - id: m1\n  initial_position: [0.0, 0.0, 1000.0]\n  initial_velocity: [100.0, 0.0, 50.0]\n  initial_pitch: 45.0\n\
+             \x20 metadata: {team: red}\n\
+             radars: []\n\
+             interceptors:\n\
+             - id: i1\n  initial_position: [0.0, 0.0, 0.0]\n  initial_velocity: [0.0, 0.0, 10.0]\n  initial_pitch: 90.0\n\
+             \x20 metadata: {team: blue}\n",
+        )
+        .unwrap();
+        let state = initialize_simulation_state(missile_params(), radar_params(), interceptor_params(), scenario).unwrap();
+        let next = execute_simulation_step(&state, &missile_params(), &interceptor_params(), &GravityModel::default(), 0.1)
+            .unwrap();
+
+        // 属性はステップごとに複製せず、初期化時のものを共有する
+        assert!(Arc::ptr_eq(&next.missiles[0].metadata, &state.missiles[0].metadata));
+        assert!(Arc::ptr_eq(&next.interceptors[0].metadata, &state.interceptors[0].metadata));
+        assert_eq!(next.missiles[0].metadata["team"], "red");
+    }
+}
@@ -0,0 +1,185 @@
+// src/simulation/guard.rs
+
+use crate::simulation::SimulationState;
+
+/// 1ステップあたりの移動距離の上限設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepDistanceGuard {
+    pub max_step_distance: f64, // 1ステップで許容する最大移動距離 (m)
+    pub clamp: bool,            // true の場合、超過した移動を上限距離に制限する
+}
+
+/// 上限を超えた移動の警告
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepDistanceWarning {
+    pub id: String,
+    pub distance: f64, // 制限前の移動距離 (m)
+}
+
+/// 1エンティティの移動距離を検査する純粋関数
+///
+/// # 引数
+/// - `previous`: ステップ開始時の位置
+/// - `next`: ステップ終了時の位置
+/// - `guard`: 移動距離の上限設定
+///
+/// # 戻り値
+/// - 検査後の位置（clamp 有効時は上限距離に制限）
+/// - 上限を超えた場合は制限前の移動距離
+pub fn check_step_distance(
+    previous: &[f64; 3],
+    next: &[f64; 3],
+    guard: &StepDistanceGuard,
+) -> ([f64; 3], Option<f64>) {
+    let delta = [next[0] - previous[0], next[1] - previous[1], next[2] - previous[2]];
+    let distance = (delta[0].powi(2) + delta[1].powi(2) + delta[2].powi(2)).sqrt();
+    if distance <= guard.max_step_distance {
+        return (*next, None);
+    }
+    if !guard.clamp {
+        return (*next, Some(distance));
+    }
+    let scale = guard.max_step_distance / distance;
+    (
+        [
+            previous[0] + delta[0] * scale,
+            previous[1] + delta[1] * scale,
+            previous[2] + delta[2] * scale,
+        ],
+        Some(distance),
+    )
+}
+
+/// シミュレーションステップ前後の全エンティティの移動距離を検査する
+///
+/// # 引数
+/// - `previous`: ステップ開始時の状態
+/// - `next`: ステップ終了時の状態
+/// - `guard`: 移動距離の上限設定
+///
+/// # 戻り値
+/// - 検査後の状態
+/// - 上限を超えたエンティティの警告
+pub fn apply_step_distance_guard(
+    previous: &SimulationState,
+    mut next: SimulationState,
+    guard: &StepDistanceGuard,
+) -> (SimulationState, Vec<StepDistanceWarning>) {
+    let mut warnings = Vec::new();
+    for (prev, missile) in previous.missiles.iter().zip(next.missiles.iter_mut()) {
+        let (position, exceeded) = check_step_distance(&prev.position, &missile.position, guard);
+        missile.position = position;
+        if let Some(distance) = exceeded {
+            warnings.push(StepDistanceWarning { id: missile.id.clone(), distance });
+        }
+    }
+    for (prev, interceptor) in previous.interceptors.iter().zip(next.interceptors.iter_mut()) {
+        let (position, exceeded) = check_step_distance(&prev.position, &interceptor.position, guard);
+        interceptor.position = position;
+        if let Some(distance) = exceeded {
+            warnings.push(StepDistanceWarning { id: interceptor.id.clone(), distance });
+        }
+    }
+    (next, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MissileParameters;
+    use crate::math::{low_pass_filter_states, IntegratorKind, SeededRng};
+    use crate::models::motion::update_missiles;
+    use crate::models::gravity::GravityModel;
+    use crate::models::force::ForceScale;
+    use crate::Missile;
+
+    fn state_with_velocity(velocity: [f64; 3]) -> SimulationState {
+        SimulationState {
+            missiles: vec![Missile {
+                id: "missile1".to_string(),
+                position: [0.0, 0.0, 1000.0],
+                velocity,
+                pitch: 0.0,
+                pitch_rate: 0.0,
+                mass: 5000.0,
+                thrust: [0.0, 0.0, 0.0],
+                metadata: Default::default(),
+            }],
+            integrators: vec![Default::default()],
+            filters: vec![low_pass_filter_states(velocity)],
+            thrust_rngs: vec![SeededRng::new(0)],
+            integrator_kinds: vec![IntegratorKind::Ab2],
+            force_scales: vec![ForceScale::default()],
+            ..Default::default()
+        }
+    }
+
+    fn missile_params() -> MissileParameters {
+        MissileParameters {
+            mass_initial: 5000.0,
+            area: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn step(state: &SimulationState) -> SimulationState {
+        let (missiles, integrators, filters, thrust_rngs) =
+            update_missiles(state, &missile_params(), &GravityModel::default(), 0.1).unwrap();
+        SimulationState {
+            missiles,
+            integrators,
+            filters,
+            thrust_rngs,
+            integrator_kinds: state.integrator_kinds.clone(),
+            force_scales: state.force_scales.clone(),
+            time: state.time + 0.1,
+            missile_spawn_times: state.missile_spawn_times.clone(),
+            interceptor_spawn_times: state.interceptor_spawn_times.clone(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_absurd_velocity_is_flagged_and_clamped() {
+        let guard = StepDistanceGuard {
+            max_step_distance: 1000.0,
+            clamp: true,
+        };
+        let previous = state_with_velocity([1.0e7, 0.0, 0.0]);
+        let next = step(&previous);
+
+        let (guarded, warnings) = apply_step_distance_guard(&previous, next, &guard);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, "missile1");
+        assert!((warnings[0].distance - 1.0e6).abs() < 1.0);
+        assert!((guarded.missiles[0].position[0] - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_flag_without_clamp_keeps_position() {
+        let guard = StepDistanceGuard {
+            max_step_distance: 1000.0,
+            clamp: false,
+        };
+        let (position, exceeded) = check_step_distance(&[0.0, 0.0, 0.0], &[3000.0, 4000.0, 0.0], &guard);
+        assert_eq!(position, [3000.0, 4000.0, 0.0]);
+        assert_eq!(exceeded, Some(5000.0));
+    }
+
+    #[test]
+    fn test_normal_step_passes_through() {
+        let guard = StepDistanceGuard {
+            max_step_distance: 1000.0,
+            clamp: true,
+        };
+        let previous = state_with_velocity([300.0, 0.0, 0.0]);
+        let next = step(&previous);
+        let expected = next.missiles[0].position;
+
+        let (guarded, warnings) = apply_step_distance_guard(&previous, next, &guard);
+
+        assert!(warnings.is_empty());
+        assert_eq!(guarded.missiles[0].position, expected);
+    }
+}
@@ -1,35 +1,789 @@
 // src/simulation/load_parameters.rs
 
-use std::error::Error;
+use std::cell::Cell;
+use std::fmt;
 use std::fs::File;
-use serde_yaml::from_reader;
+use std::marker::PhantomData;
+use std::path::Path;
+use serde::Deserialize;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Serialize;
+use thiserror::Error;
 
 use crate::config::{parameters::MissileParameters, parameters::RadarParameters, parameters::InterceptorParameters, scenario::Scenario};
+use crate::config::scenario::{InterceptorInstance, MissileInstance};
+use crate::simulation::csv::CsvOutput;
+use crate::simulation::framework::DEFAULT_MAX_ENTITIES;
 
-/// ミサイルパラメータの読み込み
-pub fn load_missile_parameters(path: &str) -> Result<MissileParameters, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let params: MissileParameters = from_reader(file)?;
+/// 設定ファイル読み込みのエラー
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("ファイル {path} を開けません: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("YAMLの解析に失敗しました: {0}")]
+    Parse(#[from] serde_yaml::Error),
+    #[error("ファイル {path} のYAMLの解析に失敗しました: {source}")]
+    ParseFile {
+        path: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+    #[error("ファイル {path} へのYAMLの書き出しに失敗しました: {source}")]
+    Serialize {
+        path: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+    #[error("ファイル {path} の {line} 行目のCSVの解析に失敗しました: {message}")]
+    Csv {
+        path: String,
+        line: usize,
+        message: String,
+    },
+    #[error("エンティティ数 {count} が上限 {max} を超えています")]
+    TooManyEntities { count: usize, max: usize },
+    #[error("パラメータ {name} の値 {value} が不正です: {message}")]
+    InvalidParameter {
+        name: &'static str,
+        value: f64,
+        message: String,
+    },
+}
+
+/// シミュレーション設定一式
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfig {
+    pub missile_params: MissileParameters,
+    pub radar_params: RadarParameters,
+    pub interceptor_params: InterceptorParameters,
+    pub scenario: Scenario,
+}
+
+/// YAMLファイルを任意の型として読み込む
+///
+/// # 引数
+/// - `path`: YAMLファイルのパス
+///
+/// # 戻り値
+/// - 読み込んだ値、またはファイルを開けない／解析に失敗した場合の `LoadError`
+pub fn load_yaml<T: DeserializeOwned>(path: &str) -> Result<T, LoadError> {
+    let file = File::open(path).map_err(|source| LoadError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    serde_yaml::from_reader(file).map_err(|source| LoadError::ParseFile {
+        path: path.to_string(),
+        source,
+    })
+}
+
+/// 任意の値をYAMLファイルに書き出す
+///
+/// 読み込んだ設定をプログラムで変更して保存し直す用途を想定しており、
+/// `load_yaml` で読み戻すと同じ値になる。
+///
+/// # 引数
+/// - `value`: 書き出す値
+/// - `path`: 出力先のYAMLファイルのパス
+///
+/// # 戻り値
+/// - 成功時は `()`、ファイルを作成できない／書き出しに失敗した場合の `LoadError`
+pub fn save_yaml<T: Serialize>(value: &T, path: &str) -> Result<(), LoadError> {
+    let file = File::create(path).map_err(|source| LoadError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    serde_yaml::to_writer(file, value).map_err(|source| LoadError::Serialize {
+        path: path.to_string(),
+        source,
+    })
+}
+
+/// YAML文字列を任意の型として読み込む
+///
+/// # 引数
+/// - `contents`: YAML文字列
+///
+/// # 戻り値
+/// - 読み込んだ値、または解析に失敗した場合の `LoadError`
+pub fn load_yaml_str<T: DeserializeOwned>(contents: &str) -> Result<T, LoadError> {
+    Ok(serde_yaml::from_str(contents)?)
+}
+
+/// ミサイルパラメータの読み込み（読み込み後に `validate_missile_parameters` で検証する）
+pub fn load_missile_parameters(path: &str) -> Result<MissileParameters, LoadError> {
+    let params = load_yaml(path)?;
+    validate_missile_parameters(&params)?;
     Ok(params)
 }
 
+/// ミサイルパラメータの検証
+///
+/// 燃焼終了質量 `burnout_mass` は0より大きく `mass_initial` 以下でなければならない。
+/// 0以下では燃料を使い切っても推力が止まらず、質量が0や負になって加速度が発散する。
+/// `burnout_mass` 自体は必須項目で、省略したファイル（燃焼終了の導入前の形式）は解析エラーになる。
+///
+/// # 引数
+/// - `params`: ミサイルのパラメータ
+///
+/// # 戻り値
+/// - 成功時は `()`、不正な値がある場合の `LoadError::InvalidParameter`
+pub fn validate_missile_parameters(params: &MissileParameters) -> Result<(), LoadError> {
+    let burnout_mass = params.burnout_mass;
+    if !(burnout_mass > 0.0 && burnout_mass <= params.mass_initial) {
+        return Err(LoadError::InvalidParameter {
+            name: "burnout_mass",
+            value: burnout_mass,
+            message: format!("0より大きく mass_initial ({}) 以下である必要があります", params.mass_initial),
+        });
+    }
+    // 推力オフセットがあるのに慣性モーメントがないと、モーメントが黙って無視される
+    if params.thrust_offset.iter().any(|&r| r != 0.0) && !(params.pitch_inertia > 0.0 && params.pitch_inertia.is_finite()) {
+        return Err(LoadError::InvalidParameter {
+            name: "pitch_inertia",
+            value: params.pitch_inertia,
+            message: "thrust_offset を指定する場合は0より大きい必要があります".to_string(),
+        });
+    }
+    Ok(())
+}
+
 /// レーダパラメータの読み込み
-pub fn load_radar_parameters(path: &str) -> Result<RadarParameters, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let params: RadarParameters = from_reader(file)?;
-    Ok(params)
+pub fn load_radar_parameters(path: &str) -> Result<RadarParameters, LoadError> {
+    load_yaml(path)
 }
 
 /// 迎撃ミサイルパラメータの読み込み
-pub fn load_interceptor_parameters(path: &str) -> Result<InterceptorParameters, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let params: InterceptorParameters = from_reader(file)?;
-    Ok(params)
+pub fn load_interceptor_parameters(path: &str) -> Result<InterceptorParameters, LoadError> {
+    load_yaml(path)
+}
+
+/// CSV出力の設定（任意列と座標系の基準）の読み込み
+pub fn load_csv_output(path: &str) -> Result<CsvOutput, LoadError> {
+    load_yaml(path)
+}
+
+/// シナリオの読み込み（エンティティ数の上限は `DEFAULT_MAX_ENTITIES`）
+pub fn load_scenario(path: &str) -> Result<Scenario, LoadError> {
+    load_scenario_with_limit(path, DEFAULT_MAX_ENTITIES)
+}
+
+/// エンティティ数の上限を指定したシナリオの読み込み
+///
+/// 巨大なシナリオによるメモリ枯渇を防ぐため、ミサイル・レーダ・迎撃ミサイルを読み込みながら数え、
+/// 合計が上限を超えた時点で残りを解析せずに打ち切る。
+///
+/// # 引数
+/// - `path`: シナリオのYAMLファイルのパス
+/// - `max_entities`: ミサイル・レーダ・迎撃ミサイルの合計数の上限
+///
+/// # 戻り値
+/// - 読み込んだシナリオ、または上限を超えた場合の `LoadError::TooManyEntities`
+///   （`count` は打ち切った時点の数）
+pub fn load_scenario_with_limit(path: &str, max_entities: usize) -> Result<Scenario, LoadError> {
+    let file = File::open(path).map_err(|source| LoadError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    let counter = EntityCounter::new(max_entities);
+    ScenarioSeed(&counter)
+        .deserialize(serde_yaml::Deserializer::from_reader(file))
+        .map_err(|source| counter.load_error(path, source))
+}
+
+/// 読み込み中のエンティティ数（ミサイル・レーダ・迎撃ミサイルの合計）
+struct EntityCounter {
+    max: usize,
+    count: Cell<usize>,
+}
+
+impl EntityCounter {
+    fn new(max: usize) -> Self {
+        EntityCounter { max, count: Cell::new(0) }
+    }
+
+    /// エンティティを1つ数え、上限を超えた場合は解析を打ち切るエラーを返す
+    fn add<E: de::Error>(&self) -> Result<(), E> {
+        let count = self.count.get() + 1;
+        self.count.set(count);
+        if count > self.max {
+            return Err(E::custom(format!("エンティティ数が上限 {} を超えています", self.max)));
+        }
+        Ok(())
+    }
+
+    /// 解析エラーを `LoadError` に変換する（上限超過による打ち切りは `TooManyEntities`）
+    fn load_error(&self, path: &str, source: serde_yaml::Error) -> LoadError {
+        let count = self.count.get();
+        if count > self.max {
+            LoadError::TooManyEntities { count, max: self.max }
+        } else {
+            LoadError::ParseFile {
+                path: path.to_string(),
+                source,
+            }
+        }
+    }
+}
+
+/// エンティティの配列を数えながら読み込む
+struct CountedSeq<'a, T> {
+    counter: &'a EntityCounter,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> CountedSeq<'a, T> {
+    fn new(counter: &'a EntityCounter) -> Self {
+        CountedSeq { counter, marker: PhantomData }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for CountedSeq<'_, T> {
+    type Value = Vec<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<T>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for CountedSeq<'_, T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("エンティティの配列")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<T>, A::Error> {
+        // 要素数の申告 (size_hint) は信用せず、読み込んだ分だけ確保する
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            self.counter.add()?;
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+/// エンティティ数を数えながら `Scenario` を読み込む
+struct ScenarioSeed<'a>(&'a EntityCounter);
+
+impl<'de> DeserializeSeed<'de> for ScenarioSeed<'_> {
+    type Value = Scenario;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Scenario, D::Error> {
+        deserializer.deserialize_map(self)
+    }
 }
 
-/// シナリオの読み込み
-pub fn load_scenario(path: &str) -> Result<Scenario, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let scenario: Scenario = from_reader(file)?;
+impl<'de> Visitor<'de> for ScenarioSeed<'_> {
+    type Value = Scenario;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("シナリオ")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Scenario, A::Error> {
+        let (mut missiles, mut radars, mut interceptors, mut seed) = (None, None, None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "missiles" => missiles = Some(map.next_value_seed(CountedSeq::new(self.0))?),
+                "radars" => radars = Some(map.next_value_seed(CountedSeq::new(self.0))?),
+                "interceptors" => interceptors = Some(map.next_value_seed(CountedSeq::new(self.0))?),
+                "seed" => seed = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(Scenario {
+            missiles: missiles.ok_or_else(|| de::Error::missing_field("missiles"))?,
+            radars: radars.ok_or_else(|| de::Error::missing_field("radars"))?,
+            interceptors: interceptors.ok_or_else(|| de::Error::missing_field("interceptors"))?,
+            seed: seed.unwrap_or_default(),
+        })
+    }
+}
+
+/// CSVの初期条件に必要な列
+const SCENARIO_CSV_COLUMNS: [&str; 10] = ["type", "id", "x", "y", "z", "vx", "vy", "vz", "pitch", "mass"];
+
+/// 初期条件のCSVファイルからシナリオを読み込む（エンティティ数の上限は `DEFAULT_MAX_ENTITIES`）
+pub fn load_scenario_csv(path: &str) -> Result<Scenario, LoadError> {
+    load_scenario_csv_with_limit(path, DEFAULT_MAX_ENTITIES)
+}
+
+/// エンティティ数の上限を指定して初期条件のCSVファイルからシナリオを読み込む
+///
+/// 他のツールで生成した大規模な飛来シナリオの取り込みを想定する。1行目はヘッダで、
+/// `type,id,x,y,z,vx,vy,vz,pitch,mass` の各列を任意の順序で含む。
+/// `type` が `missile` の行はミサイル、`interceptor` の行は迎撃ミサイルとする。
+/// `mass` は空欄にするとパラメータの mass_initial を使う（迎撃ミサイルでは使用しない）。
+/// 空行と `#` で始まる行は無視する。レーダは含まず、乱数シードは0とする。
+/// エンティティの行数が上限を超えた時点で残りの行を解析せずに打ち切る。
+///
+/// # 引数
+/// - `path`: CSVファイルのパス
+/// - `max_entities`: ミサイル・迎撃ミサイルの合計数の上限
+///
+/// # 戻り値
+/// - 読み込んだシナリオ、またはファイルを開けない／解析に失敗した／上限を超えた場合の `LoadError`
+pub fn load_scenario_csv_with_limit(path: &str, max_entities: usize) -> Result<Scenario, LoadError> {
+    let contents = std::fs::read_to_string(path).map_err(|source| LoadError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    let error = |line: usize, message: String| LoadError::Csv {
+        path: path.to_string(),
+        line,
+        message,
+    };
+
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let (header_line, header) = lines.next().ok_or_else(|| error(1, "ヘッダ行がありません".to_string()))?;
+    let header: Vec<&str> = header.split(',').map(str::trim).collect();
+    let mut index = [0; SCENARIO_CSV_COLUMNS.len()];
+    for (slot, name) in index.iter_mut().zip(SCENARIO_CSV_COLUMNS) {
+        *slot = header
+            .iter()
+            .position(|column| *column == name)
+            .ok_or_else(|| error(header_line, format!("列 {} がありません", name)))?;
+    }
+
+    let mut scenario = Scenario {
+        missiles: Vec::new(),
+        radars: Vec::new(),
+        interceptors: Vec::new(),
+        seed: 0,
+    };
+    for (count, (line, row)) in lines.enumerate().map(|(i, row)| (i + 1, row)) {
+        if count > max_entities {
+            return Err(LoadError::TooManyEntities { count, max: max_entities });
+        }
+        let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+        if fields.len() != header.len() {
+            return Err(error(line, format!("列数 {} がヘッダの列数 {} と一致しません", fields.len(), header.len())));
+        }
+        let field = |column: usize| fields[index[column]];
+        let number = |column: usize| {
+            field(column)
+                .parse::<f64>()
+                .map_err(|_| error(line, format!("列 {} の値 '{}' が数値ではありません", SCENARIO_CSV_COLUMNS[column], field(column))))
+        };
+        let id = field(1).to_string();
+        let initial_position = [number(2)?, number(3)?, number(4)?];
+        let initial_velocity = [number(5)?, number(6)?, number(7)?];
+        let initial_pitch = number(8)?;
+        match field(0) {
+            "missile" => scenario.missiles.push(MissileInstance {
+                id,
+                initial_position,
+                initial_velocity,
+                initial_pitch,
+                mass: if field(9).is_empty() { None } else { Some(number(9)?) },
+                thrust: None,
+                thrust_magnitude: None,
+                metadata: Default::default(),
+                integrator: Default::default(),
+                drag_scale: None,
+                thrust_scale: None,
+                spawn_time: None,
+            }),
+            "interceptor" => scenario.interceptors.push(InterceptorInstance {
+                id,
+                initial_position,
+                initial_velocity,
+                initial_pitch,
+                metadata: Default::default(),
+                spawn_time: None,
+            }),
+            other => return Err(error(line, format!("不明なエンティティ種別 '{}' です", other))),
+        }
+    }
     Ok(scenario)
 }
+
+/// ディレクトリ内の設定ファイル一式を読み込む（エンティティ数の上限は `DEFAULT_MAX_ENTITIES`）
+pub fn load_config_dir(dir: impl AsRef<Path>) -> Result<SimConfig, LoadError> {
+    load_config_dir_with_limit(dir, DEFAULT_MAX_ENTITIES)
+}
+
+/// エンティティ数の上限を指定してディレクトリ内の設定ファイル一式を読み込む
+///
+/// `missile_parameters.yaml`、`radar_parameters.yaml`、`interceptor_parameters.yaml`、
+/// `scenario.yaml` を読み込む。
+///
+/// # 引数
+/// - `dir`: 設定ファイルを格納したディレクトリ
+/// - `max_entities`: シナリオのミサイル・レーダ・迎撃ミサイルの合計数の上限
+///
+/// # 戻り値
+/// - 設定一式、または失敗したファイルのパスを含む `LoadError`
+pub fn load_config_dir_with_limit(dir: impl AsRef<Path>, max_entities: usize) -> Result<SimConfig, LoadError> {
+    let dir = dir.as_ref();
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    Ok(SimConfig {
+        missile_params: load_missile_parameters(&path("missile_parameters.yaml"))?,
+        radar_params: load_radar_parameters(&path("radar_parameters.yaml"))?,
+        interceptor_params: load_interceptor_parameters(&path("interceptor_parameters.yaml"))?,
+        scenario: load_scenario_with_limit(&path("scenario.yaml"), max_entities)?,
+    })
+}
+
+/// 1ファイル形式の設定（`missile:`、`radar:`、`interceptor:`、`scenario:` の各セクション）を
+/// シナリオのエンティティ数を数えながら読み込む
+struct UnifiedSeed<'a>(&'a EntityCounter);
+
+impl<'de> DeserializeSeed<'de> for UnifiedSeed<'_> {
+    type Value = SimConfig;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<SimConfig, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for UnifiedSeed<'_> {
+    type Value = SimConfig;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("統合設定")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<SimConfig, A::Error> {
+        let (mut missile, mut radar, mut interceptor, mut scenario) = (None, None, None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "missile" => missile = Some(map.next_value()?),
+                "radar" => radar = Some(map.next_value()?),
+                "interceptor" => interceptor = Some(map.next_value()?),
+                "scenario" => scenario = Some(map.next_value_seed(ScenarioSeed(self.0))?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(SimConfig {
+            missile_params: missile.ok_or_else(|| de::Error::missing_field("missile"))?,
+            radar_params: radar.ok_or_else(|| de::Error::missing_field("radar"))?,
+            interceptor_params: interceptor.ok_or_else(|| de::Error::missing_field("interceptor"))?,
+            scenario: scenario.ok_or_else(|| de::Error::missing_field("scenario"))?,
+        })
+    }
+}
+
+/// 全設定を1つにまとめたYAMLファイルを読み込む（エンティティ数の上限は `DEFAULT_MAX_ENTITIES`）
+pub fn load_unified(path: &str) -> Result<SimConfig, LoadError> {
+    load_unified_with_limit(path, DEFAULT_MAX_ENTITIES)
+}
+
+/// エンティティ数の上限を指定して全設定を1つにまとめたYAMLファイルを読み込む
+///
+/// # 引数
+/// - `path`: 統合YAMLファイルのパス
+/// - `max_entities`: シナリオのミサイル・レーダ・迎撃ミサイルの合計数の上限
+///
+/// # 戻り値
+/// - 設定一式、または読み込みに失敗した／上限を超えた場合の `LoadError`
+pub fn load_unified_with_limit(path: &str, max_entities: usize) -> Result<SimConfig, LoadError> {
+    let file = File::open(path).map_err(|source| LoadError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    let counter = EntityCounter::new(max_entities);
+    let config = UnifiedSeed(&counter)
+        .deserialize(serde_yaml::Deserializer::from_reader(file))
+        .map_err(|source| counter.load_error(path, source))?;
+    validate_missile_parameters(&config.missile_params)?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MISSILE_YAML: &str = "mass_initial: 5000.0\nfuel_consumption_rate: 10.0\ndrag_coefficient: 0.3\narea: 1.0\nthrust: [5000.0, 0.0, 0.0]\nburnout_mass: 4000.0\n";
+    const RADAR_YAML: &str = "azimuth_min: 0.0\nazimuth_max: 360.0\nelevation_min: -90.0\nelevation_max: 90.0\ndetection_range: 100000.0\n";
+    const INTERCEPTOR_YAML: &str = "mass_initial: 2000.0\nnavigation_coefficient: 3.0\n";
+    const SCENARIO_YAML: &str = "missiles:\n  - id: m1\n    initial_position: [0.0, 0.0, 0.0]\n    initial_velocity: [100.0, 0.0, 50.0]\n    initial_pitch: 45.0\nradars:\n  - id: r1\n    position: [0.0, 0.0, 0.0]\ninterceptors: []\n";
+
+    #[test]
+    fn test_load_yaml_str_each_type() {
+        let missile: MissileParameters = load_yaml_str(MISSILE_YAML).unwrap();
+        assert_eq!(missile.mass_initial, 5000.0);
+        assert_eq!(missile.thrust_offset, [0.0, 0.0, 0.0]);
+
+        let radar: RadarParameters = load_yaml_str(RADAR_YAML).unwrap();
+        assert_eq!(radar.detection_range, 100000.0);
+
+        let interceptor: InterceptorParameters = load_yaml_str(INTERCEPTOR_YAML).unwrap();
+        assert_eq!(interceptor.navigation_coefficient, 3.0);
+
+        let scenario: Scenario = load_yaml_str(SCENARIO_YAML).unwrap();
+        assert_eq!(scenario.missiles[0].id, "m1");
+        assert_eq!(scenario.radars.len(), 1);
+        assert!(scenario.interceptors.is_empty());
+    }
+
+    #[test]
+    fn test_load_yaml_from_file() {
+        let path = std::env::temp_dir().join(format!("mslsim_load_yaml_{}.yaml", std::process::id()));
+        std::fs::write(&path, INTERCEPTOR_YAML).unwrap();
+
+        let result = load_interceptor_parameters(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap().mass_initial, 2000.0);
+    }
+
+    #[test]
+    fn test_load_yaml_missing_file() {
+        let result: Result<Scenario, LoadError> = load_yaml("does/not/exist.yaml");
+        assert!(matches!(result, Err(LoadError::Io { path, .. }) if path == "does/not/exist.yaml"));
+    }
+
+    #[test]
+    fn test_load_yaml_str_parse_error() {
+        let result: Result<RadarParameters, LoadError> = load_yaml_str("azimuth_min: [not a number]");
+        assert!(matches!(result, Err(LoadError::Parse(_))));
+    }
+
+    #[test]
+    fn test_load_config_dir_fixture() {
+        let config = load_config_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/config")).unwrap();
+
+        assert_eq!(config.missile_params.mass_initial, 5000.0);
+        assert_eq!(config.radar_params.detection_range, 200000.0);
+        assert_eq!(config.interceptor_params.navigation_coefficient, 3.0);
+        assert_eq!(config.scenario.missiles.len(), 1);
+        assert_eq!(config.scenario.radars[0].id, "radar1");
+        assert_eq!(config.scenario.interceptors[0].id, "interceptor1");
+    }
+
+    #[test]
+    fn test_load_csv_output_fixture() {
+        let output = load_csv_output(concat!(env!("CARGO_MANIFEST_DIR"), "/config/output.yaml")).unwrap();
+        assert!(output.columns.is_empty());
+        assert_eq!(output.geodetic_origin.latitude, 35.0);
+        assert_eq!(output.range_reference, [50000.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_load_config_dir_reports_failing_file() {
+        let dir = std::env::temp_dir().join(format!("mslsim_config_dir_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("missile_parameters.yaml"), MISSILE_YAML).unwrap();
+        std::fs::write(dir.join("radar_parameters.yaml"), "detection_range: [broken").unwrap();
+
+        let result = load_config_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        match result {
+            Err(LoadError::ParseFile { path, .. }) => assert!(path.ends_with("radar_parameters.yaml")),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    /// 個別の設定ファイルを各セクションの下に字下げして1つの文書にまとめる
+    fn merge_config_dir(dir: &str) -> String {
+        [
+            ("missile", "missile_parameters.yaml"),
+            ("radar", "radar_parameters.yaml"),
+            ("interceptor", "interceptor_parameters.yaml"),
+            ("scenario", "scenario.yaml"),
+        ]
+        .iter()
+        .map(|(section, file)| {
+            let contents = std::fs::read_to_string(format!("{}/{}", dir, file)).unwrap();
+            let body: String = contents.lines().map(|line| format!("  {}\n", line)).collect();
+            format!("{}:\n{}", section, body)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_load_unified_matches_separate_files() {
+        // 統合形式は個別ファイルから生成し、設定の正本を個別ファイルに一本化する
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/config");
+        let separate = load_config_dir(dir).unwrap();
+
+        let path = std::env::temp_dir().join(format!("mslsim_unified_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, merge_config_dir(dir)).unwrap();
+        let unified = load_unified(path);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(unified.unwrap(), separate);
+    }
+
+    #[test]
+    fn test_save_yaml_round_trips_modified_scenario() {
+        let mut scenario = load_scenario(concat!(env!("CARGO_MANIFEST_DIR"), "/config/scenario.yaml")).unwrap();
+        scenario.missiles[0].initial_position = [1234.5, -678.0, 90.0];
+
+        let path = std::env::temp_dir().join(format!("mslsim_save_yaml_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_yaml(&scenario, path).unwrap();
+        let reloaded = load_scenario(path);
+        std::fs::remove_file(path).unwrap();
+
+        let reloaded = reloaded.unwrap();
+        assert_eq!(reloaded.missiles[0].initial_position, [1234.5, -678.0, 90.0]);
+        assert_eq!(reloaded, scenario);
+    }
+
+    #[test]
+    fn test_save_yaml_round_trips_parameters() {
+        let config = load_config_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/config")).unwrap();
+        let mut missile = config.missile_params.clone();
+        missile.drag_coefficient = 0.42;
+
+        let path = std::env::temp_dir().join(format!("mslsim_save_params_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_yaml(&missile, path).unwrap();
+        let reloaded = load_missile_parameters(path);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(reloaded.unwrap(), missile);
+    }
+
+    #[test]
+    fn test_load_scenario_csv_matches_hand_built_scenario() {
+        let csv = "type,id,x,y,z,vx,vy,vz,pitch,mass\n\
+                   missile,m1,0.0,0.0,0.0,100.0,0.0,50.0,45.0,4500.0\n\
+                   # コメント行\n\
+                   missile,m2,1000.0,500.0,0.0,0.0,100.0,50.0,30.0,\n\
+                   \n\
+                   interceptor,i1,10000.0,0.0,0.0,0.0,0.0,0.0,90.0,\n";
+        let path = std::env::temp_dir().join(format!("mslsim_scenario_csv_{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, csv).unwrap();
+        let loaded = load_scenario_csv(path);
+        std::fs::remove_file(path).unwrap();
+
+        let mut expected = crate::config::scenario::ScenarioBuilder::new()
+            .missile("m1", [0.0, 0.0, 0.0], [100.0, 0.0, 50.0], 45.0)
+            .missile("m2", [1000.0, 500.0, 0.0], [0.0, 100.0, 50.0], 30.0)
+            .interceptor("i1", [10000.0, 0.0, 0.0], [0.0, 0.0, 0.0], 90.0)
+            .build();
+        expected.missiles[0].mass = Some(4500.0);
+        assert_eq!(loaded.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_load_scenario_csv_reports_bad_row() {
+        let path = std::env::temp_dir().join(format!("mslsim_scenario_csv_bad_{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "id,type,x,y,z,vx,vy,vz,pitch,mass\nm1,missile,0,0,0,1,0,0,0,\nm2,missile,0,abc,0,1,0,0,0,\n").unwrap();
+        let result = load_scenario_csv(path);
+        std::fs::remove_file(path).unwrap();
+        assert!(matches!(result, Err(LoadError::Csv { line: 3, .. })), "{:?}", result.map(|_| ()));
+    }
+
+    #[test]
+    fn test_load_scenario_with_limit_stops_parsing_at_cap() {
+        let path = std::env::temp_dir().join(format!("mslsim_scenario_cap_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        // 上限を超えた後の要素は解析しないため、壊れた要素があっても上限超過として報告される
+        let yaml = format!("{}  - id: broken\n    position: [not a number]\n", SCENARIO_YAML.replace("interceptors: []\n", "interceptors:\n"));
+        std::fs::write(path, &yaml).unwrap();
+        let capped = load_scenario_with_limit(path, 1);
+        let broken = load_scenario_with_limit(path, 10);
+        std::fs::write(path, SCENARIO_YAML).unwrap();
+        let within = load_scenario_with_limit(path, 2);
+        std::fs::remove_file(path).unwrap();
+
+        assert!(matches!(capped, Err(LoadError::TooManyEntities { count: 2, max: 1 })));
+        assert!(matches!(broken, Err(LoadError::ParseFile { .. })));
+        assert_eq!(within.unwrap(), load_yaml_str::<Scenario>(SCENARIO_YAML).unwrap());
+    }
+
+    #[test]
+    fn test_load_config_dir_and_unified_apply_entity_cap() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/config");
+        assert!(matches!(load_config_dir_with_limit(dir, 1), Err(LoadError::TooManyEntities { max: 1, .. })));
+        assert_eq!(load_config_dir_with_limit(dir, 3).unwrap(), load_config_dir(dir).unwrap());
+
+        let path = std::env::temp_dir().join(format!("mslsim_unified_cap_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, merge_config_dir(dir)).unwrap();
+        let capped = load_unified_with_limit(path, 1);
+        std::fs::remove_file(path).unwrap();
+
+        assert!(matches!(capped, Err(LoadError::TooManyEntities { max: 1, .. })));
+    }
+
+    #[test]
+    fn test_load_scenario_csv_with_limit_stops_at_cap() {
+        let csv = "type,id,x,y,z,vx,vy,vz,pitch,mass\n\
+                   missile,m1,0.0,0.0,0.0,100.0,0.0,50.0,45.0,\n\
+                   missile,m2,0.0,0.0,0.0,100.0,0.0,50.0,45.0,\n\
+                   missile,m3,broken\n";
+        let path = std::env::temp_dir().join(format!("mslsim_scenario_csv_cap_{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, csv).unwrap();
+        let capped = load_scenario_csv_with_limit(path, 1);
+        let broken = load_scenario_csv(path);
+        std::fs::remove_file(path).unwrap();
+
+        assert!(matches!(capped, Err(LoadError::TooManyEntities { count: 2, max: 1 })));
+        assert!(matches!(broken, Err(LoadError::Csv { line: 4, .. })));
+    }
+
+    #[test]
+    fn test_burnout_mass_is_required_and_validated() {
+        let path = std::env::temp_dir().join(format!("mslsim_burnout_mass_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        let load = |burnout_mass: &str| {
+            std::fs::write(path, MISSILE_YAML.replace("burnout_mass: 4000.0\n", burnout_mass)).unwrap();
+            let result = load_missile_parameters(path);
+            std::fs::remove_file(path).unwrap();
+            result
+        };
+
+        assert_eq!(load("burnout_mass: 4000.0\n").unwrap().burnout_mass, 4000.0);
+        assert_eq!(load("burnout_mass: 5000.0\n").unwrap().burnout_mass, 5000.0);
+        // 未指定は既定値0にせず解析エラーとする
+        assert!(matches!(load(""), Err(LoadError::ParseFile { .. })));
+        for invalid in ["0.0", "-1.0", "5000.1", ".nan"] {
+            let result = load(&format!("burnout_mass: {}\n", invalid));
+            assert!(
+                matches!(result, Err(LoadError::InvalidParameter { name: "burnout_mass", .. })),
+                "{}: {:?}",
+                invalid,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_thrust_offset_requires_pitch_inertia() {
+        let path = std::env::temp_dir().join(format!("mslsim_pitch_inertia_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        let load = |extra: &str| {
+            std::fs::write(path, format!("{}{}", MISSILE_YAML, extra)).unwrap();
+            let result = load_missile_parameters(path);
+            std::fs::remove_file(path).unwrap();
+            result
+        };
+
+        assert_eq!(load("").unwrap().pitch_inertia, 0.0);
+        let params = load("thrust_offset: [0.0, 0.0, 0.1]\npitch_inertia: 1000.0\n").unwrap();
+        assert_eq!(params.pitch_inertia, 1000.0);
+        // オフセットがあるのに慣性モーメントがないとモーメントが無視されるため拒否する
+        for invalid in ["", "pitch_inertia: 0.0\n", "pitch_inertia: -1.0\n"] {
+            let result = load(&format!("thrust_offset: [0.0, 0.0, 0.1]\n{}", invalid));
+            assert!(
+                matches!(result, Err(LoadError::InvalidParameter { name: "pitch_inertia", .. })),
+                "{:?}",
+                result
+            );
+        }
+    }
+}
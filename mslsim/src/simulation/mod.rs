@@ -3,17 +3,113 @@
 pub mod load_parameters;
 pub mod csv;
 pub mod framework;
+pub mod guard;
+#[cfg(any(test, feature = "test-util"))]
+pub mod regression;
 use crate::{Missile, Radar, Interceptor};
-use crate::math::{AdamsBashforth2State, LowPassFilterState};
+use crate::math::{AdamsBashforth2State, IntegratorKind, LowPassFilterState, SeededRng};
+use crate::models::force::ForceScale;
+use crate::models::missile::FlightPhase;
 
 /// シミュレーションの全体状態を表す構造体
+///
+/// `Default` はエンティティのない時刻0の状態（テストでは必要なフィールドだけを指定して使う）。
+#[derive(Default)]
 pub struct SimulationState {
     pub missiles: Vec<Missile>,
     pub radars: Vec<Radar>,
     pub interceptors: Vec<Interceptor>,
-    pub integrators: Vec<AdamsBashforth2State>,        // 各ミサイルの積分器状態
-    pub filters: Vec<LowPassFilterState>,             // 各ミサイルのフィルタ状態
+    pub integrators: Vec<[AdamsBashforth2State; 3]>,   // 各ミサイルの速度の積分器状態（x, y, z 軸）
+    pub filters: Vec<[LowPassFilterState; 3]>,        // 各ミサイルの速度のフィルタ状態（x, y, z 軸）
     pub interceptor_filters: Vec<LowPassFilterState>, // 各迎撃ミサイルのフィルタ状態
+    pub thrust_rngs: Vec<SeededRng>,                  // 各ミサイルの推力ノイズ用乱数生成器
+    pub integrator_kinds: Vec<IntegratorKind>,        // 各ミサイルの速度の積分方式
+    pub force_scales: Vec<ForceScale>,                // 各ミサイルの空気抵抗・推進力の倍率
+    pub time: f64,                                    // シミュレーション時刻（s）
+    pub missile_spawn_times: Vec<f64>,                // 各ミサイルの出現時刻（s）。要素がなければ開始時から存在
+    pub interceptor_spawn_times: Vec<f64>,            // 各迎撃ミサイルの出現時刻（s）。要素がなければ開始時から存在
+    pub missile_initial_masses: Vec<f64>,             // 各ミサイルの出現時の質量（kg）。要素がなければ現在の質量
+    pub interceptor_initial_masses: Vec<f64>,         // 各迎撃ミサイルの出現時の質量（kg）。要素がなければ現在の質量
+    pub missile_phases: Vec<FlightPhase>,             // 各ミサイルの飛行段階（ステップごとに更新）
 }
 
+/// シミュレーション状態の概要
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSummary {
+    pub n_missiles: usize,
+    pub n_radars: usize,
+    pub n_interceptors: usize,
+    pub total_initial_mass: f64, // ミサイルと迎撃ミサイルの出現時の質量の合計（kg）
+}
+
+impl SimulationState {
+    /// エンティティ数と出現時の総質量の概要を返す（燃料を消費しても総質量は変わらない）
+    pub fn summary(&self) -> StateSummary {
+        let initial_mass = |masses: &[f64], index: usize, current: f64| masses.get(index).copied().unwrap_or(current);
+        let missile_mass: f64 = self
+            .missiles
+            .iter()
+            .enumerate()
+            .map(|(i, m)| initial_mass(&self.missile_initial_masses, i, m.mass))
+            .sum();
+        let interceptor_mass: f64 = self
+            .interceptors
+            .iter()
+            .enumerate()
+            .map(|(i, interceptor)| initial_mass(&self.interceptor_initial_masses, i, interceptor.mass))
+            .sum();
+        StateSummary {
+            n_missiles: self.missiles.len(),
+            n_radars: self.radars.len(),
+            n_interceptors: self.interceptors.len(),
+            total_initial_mass: missile_mass + interceptor_mass,
+        }
+    }
+
+    /// ミサイルが現在時刻までに出現しているか
+    ///
+    /// # 引数
+    /// - `index`: ミサイルのインデックス
+    ///
+    /// # 戻り値
+    /// - 出現時刻に達していれば `true`（出現時刻の指定がなければ常に `true`）
+    pub fn missile_spawned(&self, index: usize) -> bool {
+        spawned(self.missile_spawn_times.get(index).copied(), self.time)
+    }
+
+    /// 迎撃ミサイルが現在時刻までに出現しているか
+    ///
+    /// # 引数
+    /// - `index`: 迎撃ミサイルのインデックス
+    ///
+    /// # 戻り値
+    /// - 出現時刻に達していれば `true`（出現時刻の指定がなければ常に `true`）
+    pub fn interceptor_spawned(&self, index: usize) -> bool {
+        spawned(self.interceptor_spawn_times.get(index).copied(), self.time)
+    }
 
+    /// 出現済みのミサイルを返す（レーダ探知の対象）
+    pub fn spawned_missiles(&self) -> Vec<Missile> {
+        self.missiles
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.missile_spawned(*i))
+            .map(|(_, m)| m.clone())
+            .collect()
+    }
+
+    /// 全エンティティのIDをミサイル、レーダ、迎撃ミサイルの順に返す
+    pub fn entity_ids(&self) -> Vec<String> {
+        self.missiles
+            .iter()
+            .map(|m| m.id.clone())
+            .chain(self.radars.iter().map(|r| r.id.clone()))
+            .chain(self.interceptors.iter().map(|i| i.id.clone()))
+            .collect()
+    }
+}
+
+/// 出現時刻 `spawn_time` に時刻 `time` が達しているか（時刻の累積誤差を許容する）
+fn spawned(spawn_time: Option<f64>, time: f64) -> bool {
+    spawn_time.is_none_or(|spawn_time| time + 1e-9 >= spawn_time)
+}
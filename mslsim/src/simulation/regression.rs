@@ -0,0 +1,147 @@
+// src/simulation/regression.rs
+
+use thiserror::Error;
+
+/// 軌跡CSVの比較で検出した不一致
+#[derive(Error, Debug, PartialEq)]
+pub enum TrajectoryMismatch {
+    #[error("行数が一致しません（期待値: {expected}, 実際: {actual}）")]
+    RowCount { expected: usize, actual: usize },
+    #[error("ステップ {step} の列数が一致しません（期待値: {expected}, 実際: {actual}）")]
+    ColumnCount { step: usize, expected: usize, actual: usize },
+    #[error("ステップ {step} の列 {column} が許容誤差を超えています（期待値: {expected}, 実際: {actual}）")]
+    Value {
+        step: usize,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// CSVの1行をセルに分割する（行末のカンマによる空セルは除く）
+fn split_cells(line: &str) -> Vec<&str> {
+    line.trim_end().trim_end_matches(',').split(',').collect()
+}
+
+/// 基準軌跡CSVと実行結果CSVをセル単位で比較する
+///
+/// 先頭行はヘッダーとして列名に使用する。数値セルは絶対誤差 `tol` 以内であれば一致とし、
+/// 数値以外のセル（探知フラグなど）は文字列として完全一致を要求する。
+///
+/// # 引数
+/// - `golden_csv`: 基準となる軌跡CSVの内容
+/// - `run_output`: 比較対象の軌跡CSVの内容
+/// - `tol`: 数値セルの許容誤差
+///
+/// # 戻り値
+/// - 一致すれば `Ok(())`、最初に見つかった不一致を `Err` で返す
+pub fn compare_trajectories(golden_csv: &str, run_output: &str, tol: f64) -> Result<(), TrajectoryMismatch> {
+    let golden: Vec<&str> = golden_csv.lines().collect();
+    let actual: Vec<&str> = run_output.lines().collect();
+    if golden.len() != actual.len() {
+        return Err(TrajectoryMismatch::RowCount {
+            expected: golden.len(),
+            actual: actual.len(),
+        });
+    }
+
+    let header = golden.first().map(|line| split_cells(line)).unwrap_or_default();
+
+    for (step, (golden_line, actual_line)) in golden.iter().zip(actual.iter()).enumerate().skip(1) {
+        let step = step - 1;
+        let golden_cells = split_cells(golden_line);
+        let actual_cells = split_cells(actual_line);
+        if golden_cells.len() != actual_cells.len() {
+            return Err(TrajectoryMismatch::ColumnCount {
+                step,
+                expected: golden_cells.len(),
+                actual: actual_cells.len(),
+            });
+        }
+
+        for (col, (g, a)) in golden_cells.iter().zip(actual_cells.iter()).enumerate() {
+            let matches = match (g.trim().parse::<f64>(), a.trim().parse::<f64>()) {
+                (Ok(gv), Ok(av)) => (gv - av).abs() <= tol,
+                _ => g.trim() == a.trim(),
+            };
+            if !matches {
+                return Err(TrajectoryMismatch::Value {
+                    step,
+                    column: header.get(col).map(|c| c.to_string()).unwrap_or_else(|| col.to_string()),
+                    expected: g.to_string(),
+                    actual: a.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 軌跡CSVが基準と許容誤差内で一致することを表明する
+///
+/// 不一致の場合は最初の（ステップ, 列）と両方の値を含むメッセージで panic する。
+pub fn assert_trajectory_matches(golden_csv: &str, run_output: &str, tol: f64) {
+    if let Err(mismatch) = compare_trajectories(golden_csv, run_output, tol) {
+        panic!("軌跡が基準と一致しません: {}", mismatch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOLDEN: &str = "time(s),m1_x(m),m1_z(m),r1_detected(bool),\n\
+                          0,0,100,false,\n\
+                          0.1,10,99.5,false,\n\
+                          0.2,20,98,true,\n";
+
+    #[test]
+    fn test_trajectory_matches_itself() {
+        assert_trajectory_matches(GOLDEN, GOLDEN, 0.0);
+    }
+
+    #[test]
+    fn test_trajectory_within_tolerance() {
+        let perturbed = GOLDEN.replace("99.5", "99.5000001");
+        assert!(compare_trajectories(GOLDEN, &perturbed, 1e-6).is_ok());
+    }
+
+    #[test]
+    fn test_trajectory_reports_first_divergent_cell() {
+        let perturbed = GOLDEN.replace("0.1,10,99.5", "0.1,10,99.7").replace("0.2,20,98", "0.2,21,98");
+        let result = compare_trajectories(GOLDEN, &perturbed, 1e-3);
+        assert_eq!(
+            result,
+            Err(TrajectoryMismatch::Value {
+                step: 1,
+                column: "m1_z(m)".to_string(),
+                expected: "99.5".to_string(),
+                actual: "99.7".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_trajectory_non_numeric_cell_mismatch() {
+        let perturbed = GOLDEN.replace("98,true", "98,false");
+        let result = compare_trajectories(GOLDEN, &perturbed, 1.0);
+        assert!(matches!(result, Err(TrajectoryMismatch::Value { step: 2, .. })));
+    }
+
+    #[test]
+    fn test_trajectory_row_count_mismatch() {
+        let truncated: String = GOLDEN.lines().take(3).map(|l| format!("{}\n", l)).collect();
+        assert_eq!(
+            compare_trajectories(GOLDEN, &truncated, 0.0),
+            Err(TrajectoryMismatch::RowCount { expected: 4, actual: 3 })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "m1_x(m)")]
+    fn test_assert_trajectory_matches_panics_on_divergence() {
+        let perturbed = GOLDEN.replace("0.2,20,", "0.2,25,");
+        assert_trajectory_matches(GOLDEN, &perturbed, 1e-3);
+    }
+}
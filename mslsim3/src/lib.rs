@@ -0,0 +1,4 @@
+pub mod math;
+pub mod models;
+pub mod utils;
+pub mod simulation;
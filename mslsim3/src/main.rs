@@ -1,17 +1,12 @@
 use std::error::Error;
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, Write};
 
-mod math;
-mod models;
-mod utils;
-
-use math::integrator::AdamsBashforthIntegrator;
-use math::low_pass_filter::LowPassFilter;
-use models::interceptor::{check_interception, launch_interceptor, Interceptor};
-use models::missile::{check_collision as check_missile_collision, Missile};
-use models::radar::{detect_missile, generate_fire_command, Radar};
-use utils::yaml_parser::{parse_yaml, InterceptorParams, MissileParams, RadarParams, Scenario};
+use mslsim2::models::fire_control::summarize_impacts;
+use mslsim2::models::missile::check_stability;
+use mslsim2::simulation::Simulation;
+use mslsim2::utils::event_log::{EventLog, EventLogFormat};
+use mslsim2::utils::yaml_parser::{parse_yaml, InterceptorParams, MissileParams, RadarParams, Scenario};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // === 1. YAMLファイルからパラメータとシナリオを読み込む ===
@@ -21,62 +16,28 @@ fn main() -> Result<(), Box<dyn Error>> {
     let scenario: Scenario = parse_yaml("config/scenario.yaml")?;
 
     // === 2. 初期化 ===
+    // タイムステップ dt
+    let dt = 0.1; // デフォルト
+                  // 必要に応じて scenario.yaml などから dt を設定しても良い
+
+    // 時間刻みの安定性チェック (シナリオの想定最大速度に対する抗力と、推力による質量減少の時定数)
+    let max_expected_speed = scenario.max_expected_speed();
+    for (i, init_state) in scenario.initial_conditions.missiles.iter().enumerate() {
+        if let Err(warning) = check_stability(&missile_params, init_state, max_expected_speed, dt) {
+            eprintln!("警告: ミサイル {}: {}", i, warning);
+        }
+    }
+
     // シミュレーション用のオブジェクトを生成
-    let mut missiles: Vec<Missile> = scenario
-        .initial_conditions
-        .missiles
-        .iter()
-        .map(|init_state| Missile {
-            params: missile_params.clone(),
-            state: init_state.clone(),
-        })
-        .collect();
-
-    let radar = Radar {
-        params: radar_params.clone(),
-    };
+    let mut sim = Simulation::new(&missile_params, &radar_params, &interceptor_params, &scenario, dt);
 
-    let mut interceptors: Vec<Interceptor> = scenario
-        .initial_conditions
-        .interceptors
-        .iter()
-        .map(|init_state| Interceptor {
-            params: interceptor_params.clone(),
-            state: init_state.clone(),
-        })
-        .collect();
+    // 計算時間の上限 [s]
+    let duration = 2000.0;
+    // 再現用の実行設定 (シード・時間刻み・計算時間)
+    let metadata = sim.run_metadata(duration);
 
-    // タイムステップ dt
-    let mut dt = 0.1; // デフォルト
-                      // 必要に応じて scenario.yaml などから dt を設定しても良い
-
-    // Adams-Bashforth およびローパスフィルタ用のインスタンス
-    // ※ ミサイル数や迎撃ミサイル数に応じて生成する
-    let mut missile_integrators: Vec<[AdamsBashforthIntegrator; 3]> = (0..missiles.len())
-        .map(|_| core::array::from_fn(|_| AdamsBashforthIntegrator::new()))
-        .collect();
-
-    let mut missile_filters: Vec<[LowPassFilter; 3]> = vec![
-        [
-            LowPassFilter::new(missile_params.alpha_filter),
-            LowPassFilter::new(missile_params.alpha_filter),
-            LowPassFilter::new(missile_params.alpha_filter)
-        ];
-        missiles.len()
-    ];
-
-    let mut interceptor_integrators: Vec<[AdamsBashforthIntegrator; 3]> = (0..interceptors.len())
-        .map(|_| core::array::from_fn(|_| AdamsBashforthIntegrator::new()))
-        .collect();
-
-    let mut interceptor_filters: Vec<[LowPassFilter; 3]> = vec![
-        [
-            LowPassFilter::new(interceptor_params.alpha_filter),
-            LowPassFilter::new(interceptor_params.alpha_filter),
-            LowPassFilter::new(interceptor_params.alpha_filter)
-        ];
-        interceptors.len()
-    ];
+    // 実行設定は結果のCSVとは別ファイルに記録する
+    metadata.write_csv(File::create("output/run_metadata.csv")?)?;
 
     // CSV出力ファイルを準備
     let mut file = File::create("output/simulation_results.csv")?;
@@ -86,113 +47,49 @@ fn main() -> Result<(), Box<dyn Error>> {
         "time[s],missile_id,missile_x[m],missile_y[m],missile_z[m],radar_detected,interceptor_id,interceptor_x[m],interceptor_y[m],interceptor_z[m]"
     )?;
 
-    // === 3. タイムループ ===
-    let mut time = 0.0;
-    let mut running = true;
-
-    while running {
-        // 各オブジェクトがまだ「終了条件」に達していないか確認しつつ進める
-        // 今回はサンプルとして、一定時間を超えたら強制的にシミュレーション終了
-        if time > 2000.0 {
-            break;
-        }
-
-        running = false; // 全て終了していればループを抜ける
-
-        // ===== (1) ミサイルの更新 =====
-        for (i, missile) in missiles.iter_mut().enumerate() {
-            // すでに地表衝突 or 迎撃されている場合は更新不要
-            if check_missile_collision(&missile.state) {
-                continue;
-            }
+    // 迎撃ミサイルと目標の相対幾何 (誘導調整用、scenario.yaml の output で選択)
+    let mut geometry_file = if scenario.output.relative_geometry {
+        let mut file = BufWriter::new(File::create("output/relative_geometry.csv")?);
+        writeln!(file, "time[s],interceptor_id,missile_id,range[m],los_rate[rad/s],closing_speed[m/s]")?;
+        Some(file)
+    } else {
+        None
+    };
 
-            // まだ生存中ならフラグを true にする
-            running = true;
-
-            // Adams-Bashforth & ローパスを用いて更新
-            missile.state = models::missile::update_missile(
-                &missile.params,
-                &missile.state,
-                &mut missile_integrators[i],
-                &mut missile_filters[i],
-                dt,
-            );
-        }
+    // イベントログ (発射・迎撃・着弾・無力化)
+    let mut event_log = EventLog::with_metadata(
+        BufWriter::new(File::create("output/events.csv")?),
+        EventLogFormat::Csv,
+        &metadata,
+    )?;
 
-        // ===== (2) レーダ演算 (探知 & 発射指示) =====
-        // 0.1 s周期で探知するとあるので、簡易的に dt が 0.1 前後なら毎ステップチェック
-        let mut detections = Vec::new();
-        for (missile_id, missile) in missiles.iter().enumerate() {
-            // 衝突 (終了) のミサイルはスキップ
-            if check_missile_collision(&missile.state) {
-                continue;
-            }
-            // 探知を試みる
-            let detection_result = detect_missile(&radar, &missile.state, 1e-6);
-            if detection_result.detected {
-                // 発射指示
-                let fire_command = generate_fire_command(&detection_result);
-                detections.push((missile_id, detection_result, fire_command));
-            }
+    // === 3. タイムループ ===
+    // 今回はサンプルとして、一定時間を超えたら強制的にシミュレーション終了
+    while sim.is_running() && sim.time <= duration {
+        let time = sim.time;
+        let events = sim.step();
+        for event in &events {
+            println!("t={:.2} s: {:?}", event.time(), event);
         }
-
-        // ===== (3) 迎撃ミサイルの更新 =====
-        // レーダが探知した場合、発射フラグをオンにする
-        for (i, interceptor) in interceptors.iter_mut().enumerate() {
-            if !interceptor.state.launched {
-                // まだ発射していない → レーダからの指示があれば発射
-                if let Some((_mid, _dres, fire_command)) =
-                    detections.iter().find(|(_, _, fire)| *fire)
-                {
-                    if *fire_command {
-                        interceptor.state = launch_interceptor(&interceptor.state);
-                    }
-                }
-            }
-            // インターセプタの運動更新
-            // ここでは最も近いミサイルを狙うなど、シンプルなロジックにする
-            // (複数ミサイルがあるときは誘導ターゲットを決める必要がある)
-            if interceptor.state.launched {
-                // とりあえず最初のミサイルを追尾
-                if let Some(target_missile) = missiles.get(0) {
-                    interceptor.state = models::interceptor::update_interceptor(
-                        &interceptor.params,
-                        &interceptor.state,
-                        &target_missile.state.position,
-                        &mut interceptor_integrators[i],
-                        &mut interceptor_filters[i],
-                        dt,
-                    );
-                    // 迎撃成功判定
-                    let intercept_distance = 50.0; // 適当な判定距離
-                    if check_interception(
-                        &interceptor.state,
-                        &target_missile.state,
-                        intercept_distance,
-                    ) {
-                        println!(
-                            "Interceptor {} has intercepted Missile 0 at t={:.2} s",
-                            i, time
-                        );
-                        // 迎撃成功 → ミサイルを強制的に地表衝突扱いにするなど
-                        // ここでは簡単に z=0 にして衝突状態とします
-                        missiles[0].state.position[2] = 0.0;
-                    }
-                }
+        event_log.write_all(&events)?;
+
+        if let Some(geometry_file) = geometry_file.as_mut() {
+            for (interceptor_id, missile_id, geom) in sim.relative_geometries() {
+                writeln!(
+                    geometry_file,
+                    "{:.3},{},{},{:.3},{:.6},{:.3}",
+                    sim.time, interceptor_id, missile_id, geom.range, geom.los_rate, geom.closing_speed
+                )?;
             }
         }
 
         // ===== (4) CSVログ出力 =====
         // ミサイルごとに行を出力 (本来はまとめて出してもよい)
-        for (missile_id, missile) in missiles.iter().enumerate() {
-            let detected = if detections.iter().any(|(mid, _, _)| *mid == missile_id) {
-                "true"
-            } else {
-                "false"
-            };
+        for (missile_id, missile) in sim.missiles.iter().enumerate() {
+            let detected = if sim.detected[missile_id] { "true" } else { "false" };
             // 1つ目の迎撃ミサイルの位置だけを記録する例
             // (本来は複数インターセプタもループで出力する)
-            let (interceptor_id, ix, iy, iz) = if let Some(intc) = interceptors.get(0) {
+            let (interceptor_id, ix, iy, iz) = if let Some(intc) = sim.interceptors.first() {
                 (
                     0,
                     intc.state.position[0],
@@ -218,9 +115,46 @@ fn main() -> Result<(), Box<dyn Error>> {
                 iz
             )?;
         }
+    }
+
+    // 迎撃ミサイルごとの目標への最接近 (scenario.yaml の output で選択)
+    if scenario.output.closest_approach {
+        let mut file = BufWriter::new(File::create("output/closest_approach.csv")?);
+        writeln!(file, "interceptor_id,missile_id,min_distance[m],time[s]")?;
+        for (interceptor_id, miss) in sim.summary().min_miss_distances.iter().enumerate() {
+            if let Some(miss) = miss {
+                let missile_id = sim.interceptor_targets[interceptor_id];
+                writeln!(file, "{},{},{:.3},{:.3}", interceptor_id, missile_id, miss.distance, miss.time)?;
+            }
+        }
+    }
+
+    // 着弾被害の集計
+    // 防衛線を越えてからの迎撃も防御失敗として数える
+    let impacts = summarize_impacts(&sim.missiles, sim.defended_point.as_ref());
+    let late_intercepts = sim.summary().late_intercepts;
+    println!(
+        "Leakers: {} (late intercepts: {}), total impact energy: {:.3e} J",
+        impacts.leakers + late_intercepts,
+        late_intercepts,
+        impacts.total_impact_energy
+    );
+    for damage in &sim.summary().impact_damages {
+        println!(
+            "  missile {} impact at ({:.1}, {:.1}), damage radius: {:.1} m",
+            damage.missile, damage.position[0], damage.position[1], damage.damage_radius
+        );
+    }
 
-        // 時間経過
-        time += dt;
+    for record in &sim.summary().intercepts {
+        println!(
+            "  interceptor {} intercepted missile {} at t={:.2} s, remaining mass: {:.1}%{}",
+            record.interceptor,
+            record.missile,
+            record.time,
+            record.remaining_mass_fraction * 100.0,
+            if record.thrusting { " (thrusting)" } else { "" }
+        );
     }
 
     println!("Simulation finished. Results saved to output/simulation_results.csv");
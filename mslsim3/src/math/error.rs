@@ -0,0 +1,16 @@
+/// 数値計算のエラー
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MathError {
+    /// フィルタ係数 alpha が [0, 1] の範囲外 (NaN を含む)
+    InvalidAlpha(f64),
+}
+
+impl std::fmt::Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathError::InvalidAlpha(alpha) => write!(f, "フィルタ係数 alpha={} が [0, 1] の範囲外です", alpha),
+        }
+    }
+}
+
+impl std::error::Error for MathError {}
@@ -1,3 +1,15 @@
+use crate::math::error::MathError;
+
+/// フィルタ係数 alpha が [0, 1] の範囲内 (NaN は範囲外) ならそのまま返す
+/// 範囲外ではフィルタが発散・増幅するため MathError::InvalidAlpha とする
+pub fn validate_alpha(alpha: f64) -> Result<f64, MathError> {
+    if (0.0..=1.0).contains(&alpha) {
+        Ok(alpha)
+    } else {
+        Err(MathError::InvalidAlpha(alpha))
+    }
+}
+
 /// 1次遅れローパスフィルタ
 #[derive(Clone, Debug)]
 pub struct LowPassFilter {
@@ -6,16 +18,52 @@ pub struct LowPassFilter {
 }
 
 impl LowPassFilter {
+    /// alpha は [0, 1] に丸める (範囲外ではフィルタが発散・増幅するため)
+    /// NaN は丸められないため panic する (設定ファイルの値は読み込み時に validate_alpha で検証する)
     pub fn new(alpha: f64) -> Self {
+        assert!(!alpha.is_nan(), "フィルタ係数 alpha が NaN です");
         LowPassFilter {
             filtered: 0.0,
-            alpha,
+            alpha: alpha.clamp(0.0, 1.0),
         }
     }
 
+    /// alpha が [0, 1] の範囲外 (NaN を含む) なら MathError::InvalidAlpha
+    pub fn try_new(alpha: f64) -> Result<Self, MathError> {
+        Ok(LowPassFilter::new(validate_alpha(alpha)?))
+    }
+
     /// 入力値 input に対して、y_filtered(t) = alpha * input + (1-alpha) * y_filtered(t-1)
     pub fn apply(&mut self, input: f64) -> f64 {
         self.filtered = self.alpha * input + (1.0 - self.alpha) * self.filtered;
         self.filtered
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_alpha_is_clamped_or_rejected() {
+        let mut amplifying = LowPassFilter::new(2.0);
+        assert_eq!(amplifying.apply(10.0), 10.0);
+        assert_eq!(amplifying.apply(20.0), 20.0);
+        assert_eq!(LowPassFilter::try_new(2.0).unwrap_err(), MathError::InvalidAlpha(2.0));
+        assert_eq!(LowPassFilter::try_new(-0.1).unwrap_err(), MathError::InvalidAlpha(-0.1));
+        assert!(matches!(LowPassFilter::try_new(f64::NAN), Err(MathError::InvalidAlpha(alpha)) if alpha.is_nan()));
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN")]
+    fn test_nan_alpha_is_not_clamped() {
+        LowPassFilter::new(f64::NAN);
+    }
+
+    #[test]
+    fn test_valid_alpha_behaves_as_before() {
+        let mut filter = LowPassFilter::try_new(0.5).unwrap();
+        assert_eq!(filter.apply(10.0), 5.0);
+        assert_eq!(filter.apply(10.0), 7.5);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod error;
 pub mod integrator;
-pub mod low_pass_filter;
\ No newline at end of file
+pub mod low_pass_filter;
+pub mod quaternion;
+pub mod random;
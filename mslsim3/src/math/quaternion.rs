@@ -0,0 +1,174 @@
+/// 姿勢を表す単位クォータニオン (機体座標系 → 慣性座標系の回転)
+/// 機体 x 軸を機首方向とし、オイラー角 (theta: 仰角、psi: 方位角) の特異点 (theta = ±90°) を持たない。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Quaternion::IDENTITY
+    }
+}
+
+impl Quaternion {
+    /// 回転なし (機首が +x 方向)
+    pub const IDENTITY: Quaternion = Quaternion {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// 回転軸 axis (単位ベクトル) まわりに angle [rad] 回転するクォータニオン
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Self {
+        let (s, c) = (0.5 * angle).sin_cos();
+        Quaternion {
+            w: c,
+            x: axis[0] * s,
+            y: axis[1] * s,
+            z: axis[2] * s,
+        }
+    }
+
+    /// オイラー角 (theta: 水平面からの仰角、psi: x 軸から y 軸方向への方位角) [rad] から生成する
+    /// 方位回転 (z 軸まわり) と仰角回転 (y 軸まわり、機首上げが正) の合成。ロールは0とする
+    pub fn from_euler(theta: f64, psi: f64) -> Self {
+        Quaternion::from_axis_angle([0.0, 0.0, 1.0], psi) * Quaternion::from_axis_angle([0.0, 1.0, 0.0], -theta)
+    }
+
+    /// オイラー角 (theta, psi) [rad] に変換する
+    /// 機首方向ベクトルから求めるため、theta = ±90° では psi は不定 (0 を返す)
+    pub fn to_euler(&self) -> (f64, f64) {
+        let forward = self.forward();
+        let theta = forward[2].clamp(-1.0, 1.0).asin();
+        let horizontal = (forward[0] * forward[0] + forward[1] * forward[1]).sqrt();
+        let psi = if horizontal < 1e-12 { 0.0 } else { forward[1].atan2(forward[0]) };
+        (theta, psi)
+    }
+
+    pub fn norm(&self) -> f64 {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// 正規化したクォータニオン (ノルム0の場合は回転なし)
+    pub fn normalized(&self) -> Self {
+        let n = self.norm();
+        if n < 1e-12 {
+            return Quaternion::IDENTITY;
+        }
+        Quaternion {
+            w: self.w / n,
+            x: self.x / n,
+            y: self.y / n,
+            z: self.z / n,
+        }
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Quaternion {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    /// 機体座標系のベクトルを慣性座標系へ回転する
+    pub fn rotate(&self, v: [f64; 3]) -> [f64; 3] {
+        let p = Quaternion {
+            w: 0.0,
+            x: v[0],
+            y: v[1],
+            z: v[2],
+        };
+        let r = *self * p * self.conjugate();
+        [r.x, r.y, r.z]
+    }
+
+    /// 機首方向 (機体 x 軸) の単位ベクトル
+    pub fn forward(&self) -> [f64; 3] {
+        self.rotate([1.0, 0.0, 0.0])
+    }
+
+    /// 機体座標系の角速度 omega [rad/s] で dt [s] 進めた姿勢 (毎ステップ正規化する)
+    pub fn propagate(&self, omega: [f64; 3], dt: f64) -> Self {
+        let rate = (omega[0] * omega[0] + omega[1] * omega[1] + omega[2] * omega[2]).sqrt();
+        if rate < 1e-15 {
+            return self.normalized();
+        }
+        let axis = [omega[0] / rate, omega[1] / rate, omega[2] / rate];
+        (*self * Quaternion::from_axis_angle(axis, rate * dt)).normalized()
+    }
+}
+
+impl std::ops::Mul for Quaternion {
+    type Output = Quaternion;
+
+    /// ハミルトン積
+    fn mul(self, rhs: Quaternion) -> Quaternion {
+        Quaternion {
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_euler_round_trip() {
+        for &theta_deg in &[-80.0, -30.0, 0.0, 45.0, 89.0] {
+            for &psi_deg in &[-170.0, -45.0, 0.0, 60.0, 179.0] {
+                let (theta, psi) = (f64::to_radians(theta_deg), f64::to_radians(psi_deg));
+                let q = Quaternion::from_euler(theta, psi);
+                assert!((q.norm() - 1.0).abs() < 1e-12);
+                let (theta2, psi2) = q.to_euler();
+                assert!((theta2 - theta).abs() < 1e-9, "theta {} -> {}", theta, theta2);
+                assert!((psi2 - psi).abs() < 1e-9, "psi {} -> {}", psi, psi2);
+                // 機首方向は推力方向の式と一致する
+                let f = q.forward();
+                let expected = [theta.cos() * psi.cos(), theta.cos() * psi.sin(), theta.sin()];
+                for k in 0..3 {
+                    assert!((f[k] - expected[k]).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pitch_through_vertical_avoids_singularity() {
+        // 機首上げ 20°/s で 5 s (100°) 回転し、鉛直を越えて背面側へ倒れる
+        let pitch_rate = f64::to_radians(20.0);
+        let dt = 0.01;
+        let mut q = Quaternion::IDENTITY;
+        for _ in 0..500 {
+            q = q.propagate([0.0, -pitch_rate, 0.0], dt);
+            assert!(q.w.is_finite() && (q.norm() - 1.0).abs() < 1e-12);
+        }
+        let f = q.forward();
+        let angle = f64::to_radians(100.0);
+        assert!((f[0] - angle.cos()).abs() < 1e-9 && f[1].abs() < 1e-12 && (f[2] - angle.sin()).abs() < 1e-9);
+        // オイラー角では仰角 80°・方位角 180° として表される
+        let (theta, psi) = q.to_euler();
+        assert!((theta - f64::to_radians(80.0)).abs() < 1e-9);
+        assert!((psi.abs() - std::f64::consts::PI).abs() < 1e-9);
+
+        // 鉛直姿勢での鉛直軸まわりの回転: オイラー角の運動学 psi_dot = r / cos(theta) は発散するが、
+        // クォータニオンは有限のまま機首方向を保つ
+        let vertical = Quaternion::from_euler(FRAC_PI_2, 0.0);
+        assert!(1.0 / FRAC_PI_2.cos() > 1e15);
+        let rolled = vertical.propagate([0.5, 0.0, 0.0], 1.0);
+        let f = rolled.forward();
+        assert!((f[2] - 1.0).abs() < 1e-12);
+        assert!((rolled.norm() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,697 @@
+use serde_derive::Deserialize;
+use crate::math::low_pass_filter::LowPassFilter;
+use crate::models::interceptor::{
+    below_engagement_altitude, launch_interceptor, new_interceptor_integrators, update_interceptor, InterceptorParams, InterceptorState,
+};
+use crate::models::missile::Missile;
+
+/// 防護対象地点
+#[derive(Clone, Debug, Deserialize)]
+pub struct DefendedPoint {
+    pub position: [f64; 3], // 防護地点 [m]
+    pub radius: f64,        // 防護半径 [m] (予測着弾点がこの範囲内なら脅威とみなす)
+    #[serde(default)]
+    pub defense_line: Option<f64>, // 防衛線 (防護地点からの水平距離) [m]。これより内側での迎撃は遅すぎるとみなす
+}
+
+/// 予測着弾点と防護地点の水平距離
+pub fn miss_distance(impact: &[f64; 3], defended_point: &DefendedPoint) -> f64 {
+    let dx = impact[0] - defended_point.position[0];
+    let dy = impact[1] - defended_point.position[1];
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// 予測着弾点が防護地点を脅かすかどうか
+pub fn threatens_defended_point(impact: Option<[f64; 3]>, defended_point: &DefendedPoint) -> bool {
+    match impact {
+        Some(impact) => miss_distance(&impact, defended_point) <= defended_point.radius,
+        None => false,
+    }
+}
+
+/// 防衛線に対する迎撃のタイミング
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterceptTiming {
+    /// 防衛線の外側で迎撃した
+    InTime,
+    /// 防衛線を越えてから迎撃した (被害集計では着弾と同様に扱う)
+    TooLate,
+}
+
+/// 迎撃位置 position が防衛線の外側かどうかを判定する
+/// 防護地点または防衛線が無い場合は常に InTime
+pub fn classify_intercept(position: &[f64; 3], defended_point: Option<&DefendedPoint>) -> InterceptTiming {
+    match defended_point {
+        Some(point) if point.defense_line.is_some_and(|line| miss_distance(position, point) < line) => {
+            InterceptTiming::TooLate
+        }
+        _ => InterceptTiming::InTime,
+    }
+}
+
+/// 最低交戦高度以上の候補のみを残す
+/// candidates は (ミサイル番号, 予測着弾点) の組、target_positions はミサイル番号で引く現在位置。
+pub fn filter_by_min_altitude(
+    candidates: &[(usize, Option<[f64; 3]>)],
+    target_positions: &[[f64; 3]],
+    min_engagement_altitude: Option<f64>,
+) -> Vec<(usize, Option<[f64; 3]>)> {
+    candidates
+        .iter()
+        .filter(|(id, _)| {
+            target_positions
+                .get(*id)
+                .is_some_and(|p| !below_engagement_altitude(p, min_engagement_altitude))
+        })
+        .copied()
+        .collect()
+}
+
+/// 到達範囲表の生成条件 (シナリオの flyout)
+/// 迎撃ミサイルのパラメータと最初の迎撃ミサイルの初期状態から、シミュレーションの時間刻みで表を作る
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "FlyoutConfigFile")]
+pub struct FlyoutConfig {
+    pub elevations: Vec<f64>, // 発射仰角 [deg]
+    pub times: Vec<f64>,      // 表の時刻 [s] (昇順)
+    pub altitude_step: f64,   // 高度帯の幅 [m]
+}
+
+/// 設定ファイル上の到達範囲表の生成条件
+#[derive(Clone, Debug, Deserialize)]
+struct FlyoutConfigFile {
+    elevations: Vec<f64>,
+    times: Vec<f64>,
+    altitude_step: f64,
+}
+
+impl TryFrom<FlyoutConfigFile> for FlyoutConfig {
+    type Error = String;
+
+    fn try_from(config: FlyoutConfigFile) -> Result<Self, Self::Error> {
+        if !config.altitude_step.is_finite() || config.altitude_step <= 0.0 {
+            return Err(format!("flyout の altitude_step は正の値にしてください ({})", config.altitude_step));
+        }
+        if config.times.iter().any(|t| !t.is_finite() || *t <= 0.0)
+            || config.times.windows(2).any(|pair| pair[1] <= pair[0])
+        {
+            return Err(format!("flyout の times は正の昇順に並べてください ({:?})", config.times));
+        }
+        Ok(FlyoutConfig {
+            elevations: config.elevations,
+            times: config.times,
+            altitude_step: config.altitude_step,
+        })
+    }
+}
+
+impl FlyoutConfig {
+    /// 迎撃ミサイル (params, launch_state) の到達範囲表を時間刻み dt で作る
+    pub fn generate(&self, params: &InterceptorParams, launch_state: &InterceptorState, dt: f64) -> FlyoutTable {
+        let elevations: Vec<f64> = self.elevations.iter().map(|e| e.to_radians()).collect();
+        FlyoutTable::generate(params, launch_state, &elevations, &self.times, self.altitude_step, dt)
+    }
+}
+
+/// 迎撃ミサイルの到達範囲表 (fly-out table)
+/// 目標なし (誘導なし) で発射仰角ごとに飛翔を模擬し、各時刻までに到達できた
+/// 高度帯ごとの最大水平距離を記録する。高度・水平距離は発射地点を基準とする
+#[derive(Clone, Debug)]
+pub struct FlyoutTable {
+    pub times: Vec<f64>,          // 表の時刻 [s] (昇順)
+    pub altitude_step: f64,       // 高度帯の幅 [m]
+    pub max_ranges: Vec<Vec<f64>>, // [時刻][高度帯] → その時刻までに到達できる最大水平距離 [m]
+}
+
+impl FlyoutTable {
+    /// 迎撃ミサイルの飛翔を模擬して到達範囲表を作る
+    /// launch_state の速さ・質量・推力で発射し、elevations [rad] の各仰角へ直進させる (時間刻み dt)
+    pub fn generate(
+        params: &InterceptorParams,
+        launch_state: &InterceptorState,
+        elevations: &[f64],
+        times: &[f64],
+        altitude_step: f64,
+        dt: f64,
+    ) -> FlyoutTable {
+        // 誘導・自爆を無効にした弾道飛行
+        let params = InterceptorParams {
+            seeker_warmup: f64::INFINITY,
+            divert_thruster: None,
+            min_engagement_altitude: None,
+            ..params.clone()
+        };
+        let horizon = times.last().copied().unwrap_or(0.0);
+        let origin = launch_state.position;
+        let speed = {
+            let v = launch_state.velocity;
+            (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+        };
+        let mut max_ranges: Vec<Vec<f64>> = vec![Vec::new(); times.len()];
+        for &elevation in elevations {
+            let mut state = launch_interceptor(
+                &InterceptorState {
+                    theta: elevation,
+                    psi: 0.0,
+                    velocity: [speed * elevation.cos(), 0.0, speed * elevation.sin()],
+                    ..launch_state.clone()
+                },
+                None,
+            );
+            let mut integrators = new_interceptor_integrators(&params);
+            let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(params.alpha_filter));
+            let mut t = 0.0;
+            while t < horizon - 1e-9 && state.mass > 0.0 {
+                state = update_interceptor(&params, &state, &origin, &[0.0; 3], &mut integrators, &mut filters, dt);
+                t += dt;
+                let altitude = state.position[2] - origin[2];
+                if altitude < 0.0 {
+                    break;
+                }
+                let dx = state.position[0] - origin[0];
+                let dy = state.position[1] - origin[1];
+                let range = (dx * dx + dy * dy).sqrt();
+                let bin = (altitude / altitude_step) as usize;
+                // この時点以降の表の時刻すべてで到達可能
+                for (k, _) in times.iter().enumerate().filter(|(_, &time)| time + 1e-9 >= t) {
+                    let row = &mut max_ranges[k];
+                    if row.len() <= bin {
+                        row.resize(bin + 1, 0.0);
+                    }
+                    row[bin] = row[bin].max(range);
+                }
+            }
+        }
+        FlyoutTable {
+            times: times.to_vec(),
+            altitude_step,
+            max_ranges,
+        }
+    }
+
+    /// 表の最終時刻 [s]
+    pub fn horizon(&self) -> f64 {
+        self.times.last().copied().unwrap_or(0.0)
+    }
+
+    /// 発射から time [s] 以内に高度 altitude [m] で到達できる最大水平距離 [m]
+    /// 表の時刻の間は手前の時刻の値 (保守側) とし、到達できない場合は0
+    pub fn max_range_at(&self, altitude: f64, time: f64) -> f64 {
+        if altitude < 0.0 || self.altitude_step <= 0.0 {
+            return 0.0;
+        }
+        let Some(k) = self.times.iter().rposition(|&t| t <= time + 1e-9) else {
+            return 0.0;
+        };
+        let bin = (altitude / self.altitude_step) as usize;
+        self.max_ranges[k].get(bin).copied().unwrap_or(0.0)
+    }
+
+    /// launch_position から target へ time_available [s] 以内に到達できるか
+    pub fn reachable(&self, launch_position: &[f64; 3], target: &[f64; 3], time_available: f64) -> bool {
+        let dx = target[0] - launch_position[0];
+        let dy = target[1] - launch_position[1];
+        let range = (dx * dx + dy * dy).sqrt();
+        let altitude = target[2] - launch_position[2];
+        range <= self.max_range_at(altitude, time_available)
+    }
+
+    /// 等速直線運動する目標 (position, velocity) を表の時間内に迎撃できるか
+    /// 表の各時刻 t における目標の予測位置 (予測迎撃点の候補) に、発射から t 以内に到達できれば迎撃できるとする
+    pub fn can_intercept(&self, launch_position: &[f64; 3], position: &[f64; 3], velocity: &[f64; 3]) -> bool {
+        self.times.iter().any(|&t| {
+            let predicted = [position[0] + velocity[0] * t, position[1] + velocity[1] * t, position[2] + velocity[2] * t];
+            self.reachable(launch_position, &predicted, t)
+        })
+    }
+}
+
+/// 到達範囲表の時間内に予測迎撃点へ到達できる候補のみを残す
+/// candidates は (ミサイル番号, 予測着弾点) の組、missiles はミサイル番号で引くミサイル。
+pub fn filter_by_flyout(
+    candidates: &[(usize, Option<[f64; 3]>)],
+    missiles: &[Missile],
+    launch_position: &[f64; 3],
+    table: &FlyoutTable,
+) -> Vec<(usize, Option<[f64; 3]>)> {
+    candidates
+        .iter()
+        .filter(|(id, _)| {
+            missiles
+                .get(*id)
+                .is_some_and(|m| table.can_intercept(launch_position, &m.state.position, &m.state.velocity))
+        })
+        .copied()
+        .collect()
+}
+
+/// 指揮統制の遅れを経過し、発射判断が下りたかどうか
+/// first_detection は初探知の時刻 (未探知なら None)
+pub fn c2_decision_ready(first_detection: Option<f64>, time: f64, c2_latency: f64) -> bool {
+    match first_detection {
+        Some(detected_at) => time - detected_at + 1e-9 >= c2_latency,
+        None => false,
+    }
+}
+
+/// 目標ごとの迎撃ミサイル数の上限を考慮した発射目標の選択
+/// engage_order は優先順位順のミサイル番号、committed はミサイル番号ごとの交戦中の迎撃ミサイル数。
+/// 上限に達していない最も優先度の高い目標を返す (None: 割り当て可能な目標がなく発射を保留)
+pub fn select_target(engage_order: &[usize], committed: &[usize], max_per_target: Option<usize>) -> Option<usize> {
+    engage_order.iter().copied().find(|&target| match max_per_target {
+        Some(max) => committed.get(target).copied().unwrap_or(0) < max,
+        None => true,
+    })
+}
+
+/// 交戦対象の優先順位付け
+/// candidates は (ミサイル番号, 予測着弾点) の組。
+/// 防護地点が無い場合は全候補をそのままの順序で返す。
+/// 防護地点がある場合は脅威となるミサイルのみを、予測着弾点が防護地点に近い順に返す。
+pub fn prioritize_targets(
+    candidates: &[(usize, Option<[f64; 3]>)],
+    defended_point: Option<&DefendedPoint>,
+) -> Vec<usize> {
+    let defended_point = match defended_point {
+        Some(point) => point,
+        None => return candidates.iter().map(|(id, _)| *id).collect(),
+    };
+    let mut threats: Vec<(usize, f64)> = candidates
+        .iter()
+        .filter(|(_, impact)| threatens_defended_point(*impact, defended_point))
+        .map(|(id, impact)| (*id, impact.map_or(f64::INFINITY, |p| miss_distance(&p, defended_point))))
+        .collect();
+    threats.sort_by(|a, b| a.1.total_cmp(&b.1));
+    threats.into_iter().map(|(id, _)| id).collect()
+}
+
+/// 着弾 (迎撃されずに地表到達した) ミサイルの被害集計
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImpactSummary {
+    pub leakers: usize,            // 防護範囲に着弾したミサイル数
+    pub total_impact_energy: f64,  // 着弾時の運動エネルギーの合計 [J]
+}
+
+/// 迎撃されずに着弾したミサイルが防護範囲内か (防護地点が無い場合は全着弾が対象)
+fn is_leaker(missile: &Missile, defended_point: Option<&DefendedPoint>) -> bool {
+    !missile.state.active
+        && !missile.state.intercepted
+        && match defended_point {
+            Some(point) => miss_distance(&missile.state.position, point) <= point.radius,
+            None => true,
+        }
+}
+
+/// 防護範囲に着弾したミサイルの数と運動エネルギーを集計する
+/// 防護地点が無い場合は地表に着弾した全ミサイルを対象とする
+pub fn summarize_impacts(missiles: &[Missile], defended_point: Option<&DefendedPoint>) -> ImpactSummary {
+    let leakers: Vec<&Missile> = missiles.iter().filter(|m| is_leaker(m, defended_point)).collect();
+    ImpactSummary {
+        leakers: leakers.len(),
+        total_impact_energy: leakers.iter().fold(0.0, |total, m| total + m.kinetic_energy()),
+    }
+}
+
+/// 着弾の運動エネルギーから被害半径を求めるスケーリング則 R = coefficient * E^exponent
+/// 既定は爆発の3乗根則 (exponent = 1/3)
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct DamageScaling {
+    pub coefficient: f64, // 係数 [m/J^exponent]
+    pub exponent: f64,    // 運動エネルギーの指数
+}
+
+impl Default for DamageScaling {
+    fn default() -> Self {
+        DamageScaling {
+            coefficient: 0.05,
+            exponent: 1.0 / 3.0,
+        }
+    }
+}
+
+impl DamageScaling {
+    /// 着弾時の運動エネルギー impact_ke [J] に対する被害半径 [m] (エネルギー0以下なら0)
+    pub fn damage_radius(&self, impact_ke: f64) -> f64 {
+        if impact_ke <= 0.0 {
+            return 0.0;
+        }
+        self.coefficient * impact_ke.powf(self.exponent)
+    }
+}
+
+/// 着弾したミサイルの被害範囲
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImpactDamage {
+    pub missile: usize,        // ミサイル番号
+    pub position: [f64; 3],    // 着弾点 [m]
+    pub energy: f64,           // 着弾時の運動エネルギー [J]
+    pub damage_radius: f64,    // 被害半径 [m]
+}
+
+/// 防護範囲に着弾したミサイルごとの着弾点と被害半径
+pub fn impact_damages(
+    missiles: &[Missile],
+    defended_point: Option<&DefendedPoint>,
+    scaling: &DamageScaling,
+) -> Vec<ImpactDamage> {
+    missiles
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| is_leaker(m, defended_point))
+        .map(|(i, m)| {
+            let energy = m.kinetic_energy();
+            ImpactDamage {
+                missile: i,
+                position: m.state.position,
+                energy,
+                damage_radius: scaling.damage_radius(energy),
+            }
+        })
+        .collect()
+}
+
+/// 定常風を補正した発射方位角・仰角 [rad]
+/// 発射点を原点とし、目標 target への視線方向に対地速度が向くよう、
+/// 対気速度 nominal_speed のベクトルを風上側へ傾ける。
+/// 対気速度 v_a と風 w について v_a + w = s * u (u: 視線単位ベクトル, s > 0) を解く。
+/// 風が強すぎて補正しきれない場合は判別式を0として最大限補正する。
+pub fn wind_corrected_launch_heading(target: &[f64; 3], wind: &[f64; 3], nominal_speed: f64) -> (f64, f64) {
+    let range = (target[0] * target[0] + target[1] * target[1] + target[2] * target[2]).sqrt();
+    if range < 1e-9 {
+        return (0.0, 0.0);
+    }
+    let u = [target[0] / range, target[1] / range, target[2] / range];
+    let u_dot_w = u[0] * wind[0] + u[1] * wind[1] + u[2] * wind[2];
+    let wind_sq = wind[0] * wind[0] + wind[1] * wind[1] + wind[2] * wind[2];
+    let discriminant = (u_dot_w * u_dot_w - wind_sq + nominal_speed * nominal_speed).max(0.0);
+    let ground_speed = u_dot_w + discriminant.sqrt();
+    let aim = [
+        ground_speed * u[0] - wind[0],
+        ground_speed * u[1] - wind[1],
+        ground_speed * u[2] - wind[2],
+    ];
+    let azimuth = aim[1].atan2(aim[0]);
+    let elevation = aim[2].atan2((aim[0] * aim[0] + aim[1] * aim[1]).sqrt());
+    (azimuth, elevation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::integrator::AdamsBashforthIntegrator;
+    use crate::models::interceptor::GuidanceConstants;
+    use crate::models::missile::{predict_impact, MissileParams, MissileState};
+
+    fn ballistic_params() -> MissileParams {
+        MissileParams {
+            area: 1.0,
+            rho0: 1.225,
+            h: 8500.0,
+            g: 9.81,
+            alpha_filter: 1.0,
+            density_floor: 1e-5,
+            ..Default::default()
+        }
+    }
+
+    fn missile(velocity: [f64; 3]) -> MissileState {
+        MissileState {
+            mass: 1000.0,
+            position: [0.0, 0.0, 5000.0],
+            velocity,
+            ..Default::default()
+        }
+    }
+
+    fn predicted_impact(state: &MissileState) -> Option<[f64; 3]> {
+        let integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        predict_impact(&ballistic_params(), state, &integrators, &filters, 0.1, 600.0)
+    }
+
+    #[test]
+    fn test_missile_aimed_away_is_not_engaged() {
+        // 約 32 s で着弾 → 東向きは x ≈ 6400 m、西向きは x ≈ -6400 m
+        let toward = missile([200.0, 0.0, 0.0]);
+        let away = missile([-200.0, 0.0, 0.0]);
+        let defended = DefendedPoint {
+            position: [6400.0, 0.0, 0.0],
+            radius: 500.0,
+            defense_line: None,
+        };
+
+        let candidates = vec![(0, predicted_impact(&away)), (1, predicted_impact(&toward))];
+        assert_eq!(prioritize_targets(&candidates, Some(&defended)), vec![1]);
+    }
+
+    #[test]
+    fn test_targets_ordered_by_threat() {
+        let defended = DefendedPoint {
+            position: [0.0, 0.0, 0.0],
+            radius: 1000.0,
+            defense_line: None,
+        };
+        let candidates = vec![(0, Some([800.0, 0.0, 0.0])), (1, Some([0.0, 100.0, 0.0])), (2, None)];
+        assert_eq!(prioritize_targets(&candidates, Some(&defended)), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_without_defended_point_all_candidates_engaged() {
+        let candidates = vec![(2, None), (0, Some([1.0e6, 0.0, 0.0]))];
+        assert_eq!(prioritize_targets(&candidates, None), vec![2, 0]);
+    }
+
+    #[test]
+    fn test_low_flying_target_not_engaged() {
+        let candidates = vec![(0, None), (1, None)];
+        let positions = [[0.0, 0.0, 5000.0], [0.0, 0.0, 30.0]];
+
+        let engageable = filter_by_min_altitude(&candidates, &positions, Some(100.0));
+        assert_eq!(prioritize_targets(&engageable, None), vec![0]);
+
+        // 制限が無ければ両方と交戦
+        let engageable = filter_by_min_altitude(&candidates, &positions, None);
+        assert_eq!(prioritize_targets(&engageable, None), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_impact_summary_aggregates_leakers_in_defended_area() {
+        let impacted = |x: f64, speed: f64| Missile {
+            params: ballistic_params(),
+            state: MissileState {
+                position: [x, 0.0, 0.0],
+                active: false,
+                ..missile([0.0, 0.0, -speed])
+            },
+        };
+        let inside_a = impacted(100.0, 100.0);
+        let inside_b = impacted(-200.0, 200.0);
+        let outside = impacted(5000.0, 300.0);
+        let mut intercepted = impacted(0.0, 400.0);
+        intercepted.state.intercepted = true;
+        let mut flying = impacted(0.0, 500.0);
+        flying.state.active = true;
+
+        let missiles = vec![inside_a, inside_b, outside, intercepted, flying];
+        let defended = DefendedPoint {
+            position: [0.0, 0.0, 0.0],
+            radius: 1000.0,
+            defense_line: None,
+        };
+
+        // 0.5 * 1000 * (100^2 + 200^2)
+        assert_eq!(
+            summarize_impacts(&missiles, Some(&defended)),
+            ImpactSummary {
+                leakers: 2,
+                total_impact_energy: 0.5 * 1000.0 * (100.0f64.powi(2) + 200.0f64.powi(2)),
+            }
+        );
+        assert_eq!(summarize_impacts(&missiles, None).leakers, 3);
+
+        // 被害範囲は防護範囲内の着弾ごとに出力する
+        let damages = impact_damages(&missiles, Some(&defended), &DamageScaling::default());
+        assert_eq!(damages.iter().map(|d| d.missile).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(damages[1].position, [-200.0, 0.0, 0.0]);
+        assert_eq!(damages[1].energy, 0.5 * 1000.0 * 200.0f64.powi(2));
+        assert!(damages[1].damage_radius > damages[0].damage_radius);
+    }
+
+    #[test]
+    fn test_damage_radius_scales_with_impact_energy() {
+        let scaling = DamageScaling {
+            coefficient: 0.1,
+            exponent: 1.0 / 3.0,
+        };
+        assert_eq!(scaling.damage_radius(0.0), 0.0);
+        assert!((scaling.damage_radius(1e6) - 10.0).abs() < 1e-9);
+        // エネルギー8倍で半径2倍 (3乗根則)
+        assert!((scaling.damage_radius(8e6) - 20.0).abs() < 1e-9);
+        assert!(scaling.damage_radius(2e6) > scaling.damage_radius(1e6));
+        // 設定しない項目は既定値
+        let yaml: DamageScaling = serde_yaml::from_str("coefficient: 0.2").unwrap();
+        assert_eq!(yaml.coefficient, 0.2);
+        assert_eq!(yaml.exponent, DamageScaling::default().exponent);
+    }
+
+    #[test]
+    fn test_wind_corrected_heading_without_wind_is_direct_bearing() {
+        let target = [10000.0, 10000.0, 0.0];
+        let (azimuth, elevation) = wind_corrected_launch_heading(&target, &[0.0, 0.0, 0.0], 300.0);
+        assert!((azimuth - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        assert!(elevation.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_wind_corrected_heading_biased_into_crosswind() {
+        // 東 (+x) の目標に対し北 (+y) へ流す風 → 南 (-y) 寄りに向ける
+        let target = [10000.0, 0.0, 0.0];
+        let wind = [0.0, 30.0, 0.0];
+        let speed = 300.0;
+        let (azimuth, elevation) = wind_corrected_launch_heading(&target, &wind, speed);
+
+        assert!(azimuth < 0.0);
+        assert!(elevation.abs() < 1e-12);
+        // 対気速度 + 風 が目標方向を向く (横方向成分が打ち消される)
+        let lateral = speed * azimuth.sin() + wind[1];
+        assert!(lateral.abs() < 1e-9);
+        assert!((azimuth - (-30.0f64 / 300.0).asin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_c2_decision_ready_after_latency() {
+        assert!(!c2_decision_ready(None, 10.0, 0.0));
+        assert!(c2_decision_ready(Some(1.0), 1.0, 0.0));
+        assert!(!c2_decision_ready(Some(1.0), 2.9, 2.0));
+        assert!(c2_decision_ready(Some(1.0), 3.0, 2.0));
+    }
+
+    #[test]
+    fn test_select_target_respects_max_per_target() {
+        let order = [2, 0, 1];
+        // 上限なしなら最優先の目標
+        assert_eq!(select_target(&order, &[0, 0, 5], None), Some(2));
+        // 上限1: 交戦中の目標を飛ばして次の目標へ
+        assert_eq!(select_target(&order, &[0, 0, 1], Some(1)), Some(0));
+        // 全目標が上限に達していれば保留
+        assert_eq!(select_target(&order, &[1, 1, 1], Some(1)), None);
+        assert_eq!(select_target(&order, &[1, 1, 1], Some(2)), Some(2));
+    }
+
+    #[test]
+    fn test_intercept_classified_against_defense_line() {
+        let defended = DefendedPoint {
+            position: [0.0, 0.0, 0.0],
+            radius: 1000.0,
+            defense_line: Some(3000.0),
+        };
+        // 防衛線のすぐ外側は成功、すぐ内側は遅すぎる迎撃
+        assert_eq!(classify_intercept(&[3000.5, 0.0, 2000.0], Some(&defended)), InterceptTiming::InTime);
+        assert_eq!(classify_intercept(&[0.0, 2999.5, 2000.0], Some(&defended)), InterceptTiming::TooLate);
+        let no_line = DefendedPoint {
+            defense_line: None,
+            ..defended.clone()
+        };
+        assert_eq!(classify_intercept(&[10.0, 0.0, 0.0], Some(&no_line)), InterceptTiming::InTime);
+        assert_eq!(classify_intercept(&[10.0, 0.0, 0.0], None), InterceptTiming::InTime);
+    }
+
+    fn flyout_params() -> InterceptorParams {
+        InterceptorParams {
+            alpha: 0.0001,
+            cd: 0.3,
+            area: 0.1,
+            g: 9.81,
+            thrust: 50000.0,
+            alpha_filter: 1.0,
+            guidance_constants: GuidanceConstants { n: 3.0 },
+            turn_around_acceleration: 50.0,
+            ..Default::default()
+        }
+    }
+
+    fn flyout_table() -> FlyoutTable {
+        let launch_state = InterceptorState {
+            mass: 500.0,
+            thrust: 50000.0,
+            velocity: [10.0, 0.0, 0.0],
+            ..Default::default()
+        };
+        let elevations: Vec<f64> = (1..=9).map(|k| (10.0 * k as f64).to_radians()).collect();
+        FlyoutTable::generate(&flyout_params(), &launch_state, &elevations, &[5.0, 10.0, 15.0, 20.0], 1000.0, 0.05)
+    }
+
+    #[test]
+    fn test_flyout_reachability_is_monotonic_in_time() {
+        let table = flyout_table();
+        assert_eq!(table.horizon(), 20.0);
+        for altitude in [500.0, 2500.0, 5500.0, 9500.0] {
+            let ranges: Vec<f64> = [0.0, 5.0, 7.5, 10.0, 15.0, 20.0, 30.0]
+                .iter()
+                .map(|&t| table.max_range_at(altitude, t))
+                .collect();
+            assert!(ranges.windows(2).all(|w| w[0] <= w[1]), "{}: {:?}", altitude, ranges);
+        }
+        // 発射前・発射地点より下には届かない
+        assert_eq!(table.max_range_at(500.0, 0.0), 0.0);
+        assert_eq!(table.max_range_at(-100.0, 20.0), 0.0);
+        // 推力重量比 ~10 → 20 s で低高度 10 km 先には届き、時間が短ければ届かない
+        assert!(table.reachable(&[0.0, 0.0, 0.0], &[8000.0, 0.0, 600.0], 20.0));
+        assert!(!table.reachable(&[0.0, 0.0, 0.0], &[8000.0, 0.0, 600.0], 5.0));
+        assert!(!table.reachable(&[0.0, 0.0, 0.0], &[300000.0, 0.0, 600.0], 20.0));
+    }
+
+    fn flyout_target(position: [f64; 3], velocity: [f64; 3]) -> Missile {
+        Missile {
+            params: MissileParams::default(),
+            state: MissileState {
+                position,
+                velocity,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_filter_by_flyout_drops_unreachable_targets() {
+        let table = flyout_table();
+        let candidates = vec![(0, None), (1, None)];
+        let missiles = [
+            flyout_target([8000.0, 0.0, 3000.0], [0.0; 3]),
+            flyout_target([300000.0, 0.0, 3000.0], [0.0; 3]),
+        ];
+        let kept = filter_by_flyout(&candidates, &missiles, &[0.0, 0.0, 0.0], &table);
+        assert_eq!(kept, vec![(0, None)]);
+    }
+
+    #[test]
+    fn test_filter_by_flyout_uses_predicted_intercept_point() {
+        let table = flyout_table();
+        let candidates = vec![(0, None), (1, None)];
+        // 目標0は現在は届くが高速で遠ざかり、目標1は現在は届かないが接近してくる
+        let missiles = [
+            flyout_target([8000.0, 0.0, 3000.0], [3000.0, 0.0, 0.0]),
+            flyout_target([60000.0, 0.0, 3000.0], [-3000.0, 0.0, 0.0]),
+        ];
+        assert!(table.reachable(&[0.0; 3], &missiles[0].state.position, table.horizon()));
+        assert!(!table.reachable(&[0.0; 3], &missiles[1].state.position, table.horizon()));
+        let kept = filter_by_flyout(&candidates, &missiles, &[0.0, 0.0, 0.0], &table);
+        assert_eq!(kept, vec![(1, None)]);
+    }
+
+    #[test]
+    fn test_flyout_config_rejects_unsorted_times() {
+        let config: FlyoutConfig =
+            serde_yaml::from_str("elevations: [30.0, 60.0]\ntimes: [5.0, 10.0]\naltitude_step: 1000.0\n").unwrap();
+        let launch_state = InterceptorState {
+            mass: 500.0,
+            thrust: 50000.0,
+            velocity: [10.0, 0.0, 0.0],
+            ..Default::default()
+        };
+        assert_eq!(config.generate(&flyout_params(), &launch_state, 0.05).times, vec![5.0, 10.0]);
+        assert!(serde_yaml::from_str::<FlyoutConfig>("elevations: [30.0]\ntimes: [10.0, 5.0]\naltitude_step: 1000.0\n").is_err());
+        assert!(serde_yaml::from_str::<FlyoutConfig>("elevations: [30.0]\ntimes: [5.0]\naltitude_step: 0.0\n").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::models::gravity::GravityModel;
+use crate::models::missile::{MissileParams, MissileState};
+
+/// 力モデルの評価に使う環境
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForceEnvironment {
+    pub air_density: f64,  // 抗力を評価する大気密度 [kg/m^3] (大気圏外では0)
+    pub thrust: [f64; 3], // このステップの推力ベクトル [N]
+}
+
+/// ミサイルに働く力のモデル (net_force でパイプライン全体の合計を求める)
+pub trait ForceModel {
+    /// ミサイルに働く力 [N]
+    fn force(&self, state: &MissileState, environment: &ForceEnvironment) -> [f64; 3];
+}
+
+/// 力モデルのパイプラインによる合計力 [N]
+pub fn net_force(models: &[Box<dyn ForceModel>], state: &MissileState, environment: &ForceEnvironment) -> [f64; 3] {
+    models.iter().fold([0.0; 3], |sum, model| {
+        let f = model.force(state, environment);
+        [sum[0] + f[0], sum[1] + f[1], sum[2] + f[2]]
+    })
+}
+
+/// 既定のパイプライン (推力 + 抗力 + 重力)
+pub fn default_force_models(params: &MissileParams) -> Vec<Box<dyn ForceModel>> {
+    vec![
+        Box::new(Thrust),
+        Box::new(Drag {
+            cd: params.cd,
+            area: params.area,
+        }),
+        Box::new(Gravity(params.gravity())),
+    ]
+}
+
+/// 推力 (環境の推力ベクトルをそのまま返す)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thrust;
+
+impl ForceModel for Thrust {
+    fn force(&self, _state: &MissileState, environment: &ForceEnvironment) -> [f64; 3] {
+        environment.thrust
+    }
+}
+
+/// 抗力 (速度と逆向きに 0.5 ρ |v|^2 cd area)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Drag {
+    pub cd: f64,   // 空気抵抗係数
+    pub area: f64, // 断面積 [m^2]
+}
+
+impl ForceModel for Drag {
+    fn force(&self, state: &MissileState, environment: &ForceEnvironment) -> [f64; 3] {
+        let v = state.velocity;
+        let speed = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        // 速度がほぼ0、または大気圏外なら抗力0 (NaN回避)
+        if speed < 1e-9 || environment.air_density == 0.0 {
+            return [0.0; 3];
+        }
+        let drag = 0.5 * environment.air_density * self.cd * self.area * speed * speed;
+        [-drag * v[0] / speed, -drag * v[1] / speed, -drag * v[2] / speed]
+    }
+}
+
+/// 重力
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gravity(pub GravityModel);
+
+impl ForceModel for Gravity {
+    fn force(&self, state: &MissileState, _environment: &ForceEnvironment) -> [f64; 3] {
+        self.0.force(state.mass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 速度 [300, 0, 400] (|v| = 500 m/s)、質量 1000 kg のミサイル
+    fn state() -> MissileState {
+        MissileState {
+            mass: 1000.0,
+            velocity: [300.0, 0.0, 400.0],
+            ..Default::default()
+        }
+    }
+
+    fn environment() -> ForceEnvironment {
+        ForceEnvironment {
+            air_density: 1.0,
+            thrust: [2000.0, 0.0, 1000.0],
+        }
+    }
+
+    /// 一定の力を加える試験用のモデル
+    struct ConstantForce([f64; 3]);
+
+    impl ForceModel for ConstantForce {
+        fn force(&self, _state: &MissileState, _environment: &ForceEnvironment) -> [f64; 3] {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_default_pipeline_sums_independent_contributions() {
+        let params = MissileParams {
+            cd: 0.2,
+            area: 0.5,
+            g: 9.81,
+            ..Default::default()
+        };
+        let force = net_force(&default_force_models(&params), &state(), &environment());
+        // 抗力 0.5 * 1 * 0.2 * 0.5 * 500^2 = 12500 N を速度 (0.6, 0, 0.8) と逆向きに、重力 9810 N を -z 方向に
+        let expected = [2000.0 - 7500.0, 0.0, 1000.0 - 10000.0 - 9810.0];
+        for k in 0..3 {
+            assert!((force[k] - expected[k]).abs() < 1e-9, "{:?} != {:?}", force, expected);
+        }
+    }
+
+    #[test]
+    fn test_pipeline_accepts_additional_models() {
+        let models: Vec<Box<dyn ForceModel>> = vec![
+            Box::new(Thrust),
+            Box::new(Gravity(GravityModel::constant([0.0, 2.0, 0.0]))),
+            Box::new(ConstantForce([0.0, 0.0, 500.0])),
+        ];
+        assert_eq!(net_force(&models, &state(), &environment()), [2000.0, 2000.0, 1500.0]);
+        assert_eq!(net_force(&[], &state(), &environment()), [0.0; 3]);
+        // 大気圏外・静止時は抗力なし
+        let drag = Drag { cd: 0.2, area: 0.5 };
+        let vacuum = ForceEnvironment {
+            air_density: 0.0,
+            ..environment()
+        };
+        assert_eq!(drag.force(&state(), &vacuum), [0.0; 3]);
+        let at_rest = MissileState {
+            velocity: [0.0; 3],
+            ..state()
+        };
+        assert_eq!(drag.force(&at_rest, &environment()), [0.0; 3]);
+    }
+}
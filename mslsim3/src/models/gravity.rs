@@ -0,0 +1,49 @@
+/// 一定の重力加速度ベクトル
+/// 通常は -z 方向だが、回転した座標系での試験用に任意の方向を設定できる
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GravityModel {
+    pub acceleration: [f64; 3], // 重力加速度ベクトル [m/s^2]
+}
+
+impl GravityModel {
+    /// 任意の一定重力加速度ベクトル
+    pub fn constant(acceleration: [f64; 3]) -> Self {
+        GravityModel { acceleration }
+    }
+
+    /// -z 方向の大きさ g [m/s^2] の重力
+    pub fn vertical(g: f64) -> Self {
+        Self::constant([0.0, 0.0, -g])
+    }
+
+    /// パラメータの重力: gravity_vector があればその方向、なければ -z 方向に g
+    pub fn from_params(g: f64, gravity_vector: Option<[f64; 3]>) -> Self {
+        gravity_vector.map_or_else(|| Self::vertical(g), Self::constant)
+    }
+
+    /// 質量 mass [kg] に働く重力 [N]
+    pub fn force(&self, mass: f64) -> [f64; 3] {
+        [
+            mass * self.acceleration[0],
+            mass * self.acceleration[1],
+            mass * self.acceleration[2],
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertical_points_down() {
+        assert_eq!(GravityModel::vertical(9.81).force(2.0), [0.0, 0.0, -2.0 * 9.81]);
+        assert_eq!(GravityModel::from_params(9.81, None), GravityModel::vertical(9.81));
+    }
+
+    #[test]
+    fn test_gravity_vector_overrides_vertical() {
+        let gravity = GravityModel::from_params(9.81, Some([3.0, -4.0, 0.0]));
+        assert_eq!(gravity.force(10.0), [30.0, -40.0, 0.0]);
+    }
+}
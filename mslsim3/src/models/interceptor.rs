@@ -1,24 +1,306 @@
 use serde_derive::Deserialize;
 use crate::math::integrator::AdamsBashforthIntegrator;
-use crate::math::low_pass_filter::LowPassFilter;
-use crate::models::missile::MissileState;
+use crate::math::low_pass_filter::{validate_alpha, LowPassFilter};
+use crate::models::gravity::GravityModel;
+use crate::models::missile::{Missile, MissileState};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize)]
 pub struct GuidanceConstants {
     pub n: f64, // 比例航法定数
 }
 
+/// 残り時間 (time-to-go) に対する比例航法定数のスケジュール
+/// points は (残り時間 [s], 比例航法定数) の組で、残り時間の昇順に並べる。
+/// 残り時間について線形補間し、範囲外では端の値とする。
+/// 設定ファイルの読み込み時に残り時間が狭義単調増加であることを検査する
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "NavConstantScheduleConfig")]
+pub struct NavConstantSchedule {
+    pub points: Vec<(f64, f64)>,
+}
+
+/// 設定ファイル上の比例航法定数スケジュール
+#[derive(Clone, Debug, Deserialize)]
+struct NavConstantScheduleConfig {
+    points: Vec<(f64, f64)>,
+}
+
+impl TryFrom<NavConstantScheduleConfig> for NavConstantSchedule {
+    type Error = String;
+
+    fn try_from(config: NavConstantScheduleConfig) -> Result<Self, Self::Error> {
+        if let Some(&(t, _)) = config.points.iter().find(|p| !p.0.is_finite() || !p.1.is_finite()) {
+            return Err(format!("nav_schedule の点 (残り時間 {}) に有限でない値があります", t));
+        }
+        if let Some(pair) = config.points.windows(2).find(|pair| pair[1].0 <= pair[0].0) {
+            return Err(format!(
+                "nav_schedule の残り時間は昇順に並べてください ({} の後に {})",
+                pair[0].0, pair[1].0
+            ));
+        }
+        Ok(NavConstantSchedule { points: config.points })
+    }
+}
+
+impl NavConstantSchedule {
+    /// 残り時間 time_to_go [s] における比例航法定数 (点がなければ None)
+    pub fn nav_constant(&self, time_to_go: f64) -> Option<f64> {
+        let (first, last) = (self.points.first()?, self.points.last()?);
+        if time_to_go <= first.0 {
+            return Some(first.1);
+        }
+        if time_to_go >= last.0 {
+            return Some(last.1);
+        }
+        let upper = self.points.iter().position(|p| p.0 >= time_to_go)?;
+        let (t0, n0) = self.points[upper - 1];
+        let (t1, n1) = self.points[upper];
+        Some(n0 + (n1 - n0) * (time_to_go - t0) / (t1 - t0))
+    }
+}
+
+/// 迎撃ミサイルのパラメータ (Default は推力・抗力・重力なし、フィルタなし。テストでは必要なフィールドだけを指定して使う)
 #[derive(Clone, Debug, Deserialize)]
 pub struct InterceptorParams {
     pub alpha: f64,
     pub cd: f64,
     pub area: f64,
     pub g: f64,
+    #[serde(default)]
+    pub gravity_vector: Option<[f64; 3]>, // 重力加速度ベクトル [m/s^2] (None: -z 方向に g)
     pub thrust: f64,
-    pub alpha_filter: f64,
+    #[serde(deserialize_with = "deserialize_alpha_filter")]
+    pub alpha_filter: f64, // ローパスフィルタalpha ([0, 1]、範囲外・NaN は読み込み時にエラー)
     pub guidance_constants: GuidanceConstants,
+    pub divert_budget: Option<f64>, // 誘導に使用できる速度変化量の上限 [m/s] (None: 無制限)
+    #[serde(default)]
+    pub launch_delay: f64, // 発射指示から運動開始までの遅れ [s]
+    #[serde(default)]
+    pub seeker_warmup: f64, // 運動開始から誘導開始までのシーカ捕捉時間 [s] (この間は弾道飛行)
+    #[serde(default)]
+    pub min_control_speed: f64, // 舵が効き誘導可能となる最低速度 [m/s]
+    pub divert_thruster: Option<DivertThruster>, // 大気圏外用ダイバートスラスタ (None: 空力誘導)
+    pub guidance_rate_hz: Option<f64>, // 誘導指令の更新レート [Hz] (None: 毎ステップ更新)
+    pub min_engagement_altitude: Option<f64>, // 最低交戦高度 [m] (None: 制限なし)
+    pub g_limit: Option<GLimit>, // 誘導加速度の上限 (None: 制限なし)
+    pub phased_guidance: Option<PhasedGuidance>, // 飛翔段階ごとの誘導則 (None: 追尾誘導のみ)
+    #[serde(default)]
+    pub turn_around_acceleration: f64, // 目標を追い越した (接近速度が負) ときの旋回加速度 [m/s^2] (0: 旋回せず誘導則を適用)
+    #[serde(default)]
+    pub aim_point: AimPoint, // 誘導の目標点
+    #[serde(default)]
+    pub guidance_deadband: f64, // この大きさ未満の誘導指令を0とする不感帯 [m/s^2] (0: 無効)
+    #[serde(default)]
+    pub target_extrapolation_time: Option<f64>, // 計測した目標位置を目標速度で外挿する時間 [s] (None: 計測からの経過時間だけ外挿して計測遅れを補償)
+    #[serde(default)]
+    pub arming_time: Option<f64>, // 近接信管の安全解除までの飛翔時間 [s] (None: 信管なし、距離のみで迎撃判定)
+    #[serde(default)]
+    pub nav_schedule: Option<NavConstantSchedule>, // 残り時間に応じた比例航法定数 (None: guidance_constants.n で一定)
+    #[serde(default)]
+    pub guidance_frame: GuidanceFrame, // 誘導指令を表現・制限する座標系
+    #[serde(default)]
+    pub channel_limits: Option<[f64; 3]>, // guidance_frame の各軸の誘導加速度の上限 [m/s^2] (None: 制限なし)
+    #[serde(default)]
+    pub retarget_range: Option<f64>, // 目標が撃破されたとき再割り当てできる目標までの距離 [m] (None: 撃破された目標を追い続ける)
+    #[serde(default, deserialize_with = "deserialize_integrator_damping")]
+    pub integrator_damping: f64, // 速度積分の数値減衰係数 [0, 1) (0: 通常の AB2、1 に近いほど前進オイラー法に近づく)
+    #[serde(default)]
+    pub command_filter: Option<CommandFilter>, // 誘導指令の1次遅れフィルタ (自動操縦の応答) (None: 遅れなし)
+}
+
+/// 推力・抗力・重力・誘導のないパラメータ
+/// alpha_filter は 1 (フィルタなし。0 では速度が変化しない)、それ以外の省略可能な項目は無効
+impl Default for InterceptorParams {
+    fn default() -> Self {
+        InterceptorParams {
+            alpha: 0.0,
+            cd: 0.0,
+            area: 0.0,
+            g: 0.0,
+            gravity_vector: None,
+            thrust: 0.0,
+            alpha_filter: 1.0,
+            guidance_constants: GuidanceConstants::default(),
+            divert_budget: None,
+            launch_delay: 0.0,
+            seeker_warmup: 0.0,
+            min_control_speed: 0.0,
+            divert_thruster: None,
+            guidance_rate_hz: None,
+            min_engagement_altitude: None,
+            g_limit: None,
+            phased_guidance: None,
+            turn_around_acceleration: 0.0,
+            aim_point: AimPoint::default(),
+            guidance_deadband: 0.0,
+            target_extrapolation_time: None,
+            arming_time: None,
+            nav_schedule: None,
+            guidance_frame: GuidanceFrame::default(),
+            channel_limits: None,
+            retarget_range: None,
+            integrator_damping: 0.0,
+            command_filter: None,
+        }
+    }
+}
+
+impl InterceptorParams {
+    /// 重力モデル
+    pub fn gravity(&self) -> GravityModel {
+        GravityModel::from_params(self.g, self.gravity_vector)
+    }
+}
+
+/// alpha_filter の読み込み ([0, 1] の範囲外・NaN はエラー)
+fn deserialize_alpha_filter<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let alpha = <f64 as serde::Deserialize>::deserialize(deserializer)?;
+    validate_alpha(alpha).map_err(serde::de::Error::custom)
+}
+
+/// integrator_damping の読み込み ([0, 1) の範囲外はエラー)
+/// 1 では AB2 の外挿項が打ち消されて1次精度の前進オイラー法に退化するため、1 は受け付けない
+fn deserialize_integrator_damping<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let damping = <f64 as serde::Deserialize>::deserialize(deserializer)?;
+    if (0.0..1.0).contains(&damping) {
+        Ok(damping)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "integrator_damping は [0, 1) の範囲で指定してください ({})",
+            damping
+        )))
+    }
+}
+
+/// 誘導則
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuidanceLaw {
+    /// 追尾誘導 (視線方向に N [m/s^2] の加速度)
+    #[default]
+    Pursuit,
+    /// 比例航法 (a = N Vc λ̇、視線に垂直な加速度)
+    ProportionalNavigation,
+}
+
+/// 誘導指令の座標系
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuidanceFrame {
+    /// 慣性座標系 (x, y, z)
+    #[default]
+    Inertial,
+    /// 速度座標系 (速度方向, 水平横方向, 法線方向)
+    Velocity,
+}
+
+/// 速度座標系の単位ベクトル (速度方向, 水平横方向, 法線方向) を慣性座標系で表したもの
+/// 横方向は z 軸と速度方向の外積 (左向き)、法線方向は速度方向と横方向の外積 (上向き)。
+/// 速度がほぼ0なら慣性座標系の軸、鉛直飛行中は横方向を y 軸とする
+pub fn velocity_frame_axes(velocity: &[f64; 3]) -> [[f64; 3]; 3] {
+    if vector_norm(velocity) < 1e-9 {
+        return [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    }
+    let along = vector_normalize(velocity);
+    let horizontal = [-along[1], along[0], 0.0];
+    let cross = if vector_norm(&horizontal) < 1e-9 {
+        [0.0, 1.0, 0.0]
+    } else {
+        vector_normalize(&horizontal)
+    };
+    let normal = [
+        along[1] * cross[2] - along[2] * cross[1],
+        along[2] * cross[0] - along[0] * cross[2],
+        along[0] * cross[1] - along[1] * cross[0],
+    ];
+    [along, cross, normal]
+}
+
+/// 慣性座標系の指令 a を guidance frame の成分に変換する
+pub fn to_guidance_frame(frame: GuidanceFrame, velocity: &[f64; 3], a: &[f64; 3]) -> [f64; 3] {
+    match frame {
+        GuidanceFrame::Inertial => *a,
+        GuidanceFrame::Velocity => velocity_frame_axes(velocity).map(|axis| dot(&axis, a)),
+    }
+}
+
+/// guidance frame の成分で表した指令を慣性座標系に変換する
+pub fn from_guidance_frame(frame: GuidanceFrame, velocity: &[f64; 3], components: &[f64; 3]) -> [f64; 3] {
+    match frame {
+        GuidanceFrame::Inertial => *components,
+        GuidanceFrame::Velocity => {
+            let axes = velocity_frame_axes(velocity);
+            core::array::from_fn(|k| (0..3).map(|c| axes[c][k] * components[c]).sum())
+        }
+    }
+}
+
+/// 誘導指令を guidance frame の軸ごとに ±limits に制限する (limits が None なら制限しない)
+pub fn apply_channel_limits(
+    a_command: &[f64; 3],
+    frame: GuidanceFrame,
+    velocity: &[f64; 3],
+    limits: Option<&[f64; 3]>,
+) -> [f64; 3] {
+    let Some(limits) = limits else {
+        return *a_command;
+    };
+    let components = to_guidance_frame(frame, velocity, a_command);
+    let limited: [f64; 3] = core::array::from_fn(|c| components[c].clamp(-limits[c], limits[c]));
+    from_guidance_frame(frame, velocity, &limited)
+}
+
+/// 誘導で目指す点
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AimPoint {
+    /// 目標の現在位置
+    #[default]
+    Target,
+    /// 予測会合点 (PIP)。誘導指令の更新ごとに目標の位置・速度から再計算する
+    PredictedInterceptPoint,
+}
+
+/// 中間誘導と終末誘導の切り替え
+/// 目標までの距離が handover_range 以下で終末誘導に切り替える。
+/// blend_range > 0 の場合は handover_range + blend_range から handover_range にかけて
+/// 両者の指令を線形に混合し、切り替え時の加速度の不連続を抑える。
+/// loft_altitude を指定すると、中間誘導では目標の真上の高度 loft_altitude の点を目指して上昇し、
+/// 終末誘導で目標へ降下する (高高度目標に対する到達範囲を広げる)。
+#[derive(Clone, Debug, Deserialize)]
+pub struct PhasedGuidance {
+    pub midcourse: GuidanceLaw,
+    pub terminal: GuidanceLaw,
+    pub handover_range: f64, // 終末誘導へ切り替える距離 [m]
+    #[serde(default)]
+    pub blend_range: f64, // 指令を混合する距離幅 [m]
+    #[serde(default)]
+    pub loft_altitude: Option<f64>, // 中間誘導で目指す高度 [m] (None: 目標を直接目指す)
+}
+
+/// 誘導指令の1次遅れフィルタ
+/// 内部状態は g_limit で制限した後の指令に置き換える (バックカリキュレーション) ため、
+/// 飽和が続いても上限を超えて蓄積 (ワインドアップ) せず、飽和解消直後から指令に追従する
+#[derive(Clone, Debug, Deserialize)]
+pub struct CommandFilter {
+    pub time_constant: f64, // 時定数 [s] (0以下: 遅れなし)
+}
+
+/// 誘導加速度の上限と、飽和が続いた場合のホーミング過負荷判定
+#[derive(Clone, Debug, Deserialize)]
+pub struct GLimit {
+    pub max_acceleration: f64, // 誘導加速度の上限 [m/s^2]
+    pub overload_time: f64,    // この時間以上飽和が続いたら迎撃失敗の見込みとする [s]
 }
 
+/// ダイバートスラスタ (パルス状の横方向噴射による軌道修正)
+#[derive(Clone, Debug, Deserialize)]
+pub struct DivertThruster {
+    pub impulse_per_command: f64, // 1回の誘導指令あたりの力積 [N s]
+    pub count: u32,               // 使用可能なパルス数
+}
+
+/// 迎撃ミサイルの動的状態
 #[derive(Clone, Debug, Deserialize)]
 pub struct InterceptorState {
     pub mass: f64,
@@ -28,6 +310,48 @@ pub struct InterceptorState {
     pub position: [f64; 3],
     pub velocity: [f64; 3],
     pub launched: bool,
+    #[serde(default)]
+    pub divert_used: f64, // 誘導で消費した速度変化量 [m/s]
+    #[serde(default)]
+    pub time_since_launch: f64, // 発射指示からの経過時間 [s]
+    #[serde(default)]
+    pub divert_pulses_used: u32, // 使用済みのダイバートパルス数
+    #[serde(default)]
+    pub guidance_command: Option<[f64; 3]>, // 保持中の誘導指令加速度 [m/s^2]
+    #[serde(default)]
+    pub time_since_guidance: f64, // 誘導指令を更新してからの経過時間 [s]
+    #[serde(default)]
+    pub inert: bool, // 自爆・無力化済み (以降は運動・迎撃しない)
+    #[serde(default)]
+    pub saturation_time: f64, // 誘導加速度が上限で飽和し続けている時間 [s]
+    #[serde(default)]
+    pub guidance_override: Option<[f64; 3]>, // 外部から与える次のステップの誘導加速度指令 [m/s^2] (誘導則の代わり)
+    #[serde(default)]
+    pub filtered_command: [f64; 3], // 誘導指令フィルタの内部状態 [m/s^2]
+}
+
+/// 原点に静止した未発射の質量 1 kg の状態 (質量0では加速度が発散するため)
+impl Default for InterceptorState {
+    fn default() -> Self {
+        InterceptorState {
+            mass: 1.0,
+            thrust: 0.0,
+            theta: 0.0,
+            psi: 0.0,
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            launched: false,
+            divert_used: 0.0,
+            time_since_launch: 0.0,
+            divert_pulses_used: 0,
+            guidance_command: None,
+            time_since_guidance: 0.0,
+            inert: false,
+            saturation_time: 0.0,
+            guidance_override: None,
+            filtered_command: [0.0; 3],
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -40,6 +364,10 @@ pub struct Interceptor {
 fn vector_sub(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
     [a[0]-b[0], a[1]-b[1], a[2]-b[2]]
 }
+/// 内積
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0]*b[0] + a[1]*b[1] + a[2]*b[2]
+}
 fn vector_norm(v: &[f64; 3]) -> f64 {
     (v[0]*v[0] + v[1]*v[1] + v[2]*v[2]).sqrt()
 }
@@ -52,12 +380,63 @@ fn vector_normalize(v: &[f64; 3]) -> [f64; 3] {
     }
 }
 
+/// 迎撃ミサイルと目標の相対幾何
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RelGeom {
+    pub range: f64,         // 視線距離 [m]
+    pub los: [f64; 3],      // 視線方向の単位ベクトル (迎撃ミサイル → 目標)
+    pub los_rate: f64,      // 視線角速度の大きさ [rad/s]
+    pub closing_speed: f64, // 接近速度 [m/s] (正: 接近中)
+}
+
+/// 位置・速度から相対幾何を計算する
+pub fn relative_geometry_from(
+    position: &[f64; 3],
+    velocity: &[f64; 3],
+    target_position: &[f64; 3],
+    target_velocity: &[f64; 3],
+) -> RelGeom {
+    let r = vector_sub(target_position, position);
+    let v = vector_sub(target_velocity, velocity);
+    let range = vector_norm(&r);
+    if range < 1e-9 {
+        return RelGeom {
+            range,
+            los: [0.0, 0.0, 0.0],
+            los_rate: 0.0,
+            closing_speed: 0.0,
+        };
+    }
+    // 視線角速度 |r x v| / |r|^2、接近速度 -(r . v) / |r|
+    let cross = [
+        r[1] * v[2] - r[2] * v[1],
+        r[2] * v[0] - r[0] * v[2],
+        r[0] * v[1] - r[1] * v[0],
+    ];
+    RelGeom {
+        range,
+        los: vector_normalize(&r),
+        los_rate: vector_norm(&cross) / (range * range),
+        closing_speed: -(r[0] * v[0] + r[1] * v[1] + r[2] * v[2]) / range,
+    }
+}
+
+/// 迎撃ミサイルと目標ミサイルの相対幾何
+pub fn relative_geometry(interceptor: &InterceptorState, missile: &MissileState) -> RelGeom {
+    relative_geometry_from(
+        &interceptor.position,
+        &interceptor.velocity,
+        &missile.position,
+        &missile.velocity,
+    )
+}
+
 /// 比例航法 (最簡易版: lambda_dot=0として誘導加速度=0にし、実装サンプル用とする)
-fn guidance(_state: &InterceptorState, target_pos: &[f64; 3], constants: &GuidanceConstants) -> [f64; 3] {
+fn guidance(state: &InterceptorState, target_pos: &[f64; 3], constants: &GuidanceConstants) -> [f64; 3] {
     // ここでは、あまり詳しく実装しないサンプル
     // もし本格的にやるなら LOS角速度を計算し a_guidance = N * V_rel * lambda_dot * ...
-    let rel = vector_sub(target_pos, &_state.position);
-    let rel_u = vector_normalize(&rel);
+    // (目標速度は未知のため視線方向のみを使用)
+    let rel_u = relative_geometry_from(&state.position, &state.velocity, target_pos, &[0.0; 3]).los;
     // ダミーで少しだけ誘導加速度を加える
     // (N が小さいほど誘導が弱い)
     [
@@ -67,21 +446,463 @@ fn guidance(_state: &InterceptorState, target_pos: &[f64; 3], constants: &Guidan
     ]
 }
 
-/// 迎撃ミサイルの運動更新
+/// 誘導則に応じた誘導加速度指令 (比例航法は目標速度 target_velocity との相対速度を使う)
+pub fn guidance_law_command(
+    law: GuidanceLaw,
+    state: &InterceptorState,
+    target_pos: &[f64; 3],
+    target_velocity: &[f64; 3],
+    constants: &GuidanceConstants,
+) -> [f64; 3] {
+    match law {
+        GuidanceLaw::Pursuit => guidance(state, target_pos, constants),
+        GuidanceLaw::ProportionalNavigation => {
+            let geom = relative_geometry_from(&state.position, &state.velocity, target_pos, target_velocity);
+            if geom.range < 1e-9 {
+                return [0.0, 0.0, 0.0];
+            }
+            // 視線角速度ベクトル ω = (r × v_rel) / |r|^2、指令 a = N Vc (ω × 視線方向)
+            let r = vector_sub(target_pos, &state.position);
+            let v = vector_sub(target_velocity, &state.velocity);
+            let r2 = geom.range * geom.range;
+            let omega = [
+                (r[1] * v[2] - r[2] * v[1]) / r2,
+                (r[2] * v[0] - r[0] * v[2]) / r2,
+                (r[0] * v[1] - r[1] * v[0]) / r2,
+            ];
+            let u = geom.los;
+            let gain = constants.n * geom.closing_speed;
+            [
+                gain * (omega[1] * u[2] - omega[2] * u[1]),
+                gain * (omega[2] * u[0] - omega[0] * u[2]),
+                gain * (omega[0] * u[1] - omega[1] * u[0]),
+            ]
+        }
+    }
+}
+
+/// 目標を追い越した場合の再捕捉 (旋回) 指令
+/// 速度に垂直で視線方向側を向く一定の大きさ magnitude の加速度を返す。
+/// 目標が真後ろにあり旋回方向が定まらない場合は上方向 (水平飛行中は +z) に旋回する。
+pub fn turn_around_command(state: &InterceptorState, target_pos: &[f64; 3], magnitude: f64) -> [f64; 3] {
+    let v_hat = vector_normalize(&state.velocity);
+    let los = vector_normalize(&vector_sub(target_pos, &state.position));
+    let along = los[0] * v_hat[0] + los[1] * v_hat[1] + los[2] * v_hat[2];
+    let mut perpendicular = [los[0] - along * v_hat[0], los[1] - along * v_hat[1], los[2] - along * v_hat[2]];
+    if vector_norm(&perpendicular) < 1e-6 {
+        // 速度と視線が平行: 鉛直方向から速度成分を除いた向き (鉛直飛行中は +x)
+        let up = if v_hat[2].abs() < 0.99 { [0.0, 0.0, 1.0] } else { [1.0, 0.0, 0.0] };
+        let along_up = up[0] * v_hat[0] + up[1] * v_hat[1] + up[2] * v_hat[2];
+        perpendicular = [up[0] - along_up * v_hat[0], up[1] - along_up * v_hat[1], up[2] - along_up * v_hat[2]];
+    }
+    let direction = vector_normalize(&perpendicular);
+    [magnitude * direction[0], magnitude * direction[1], magnitude * direction[2]]
+}
+
+/// 予測会合点 (PIP) の計算
+/// 目標が等速直線運動し、迎撃ミサイルが速さ interceptor_speed で直進すると仮定して
+/// |r + v_t t| = s t を満たす最小の正の t における目標位置を返す。
+/// 速さが足りず会合できない場合は None
+pub fn compute_pip(interceptor: &InterceptorState, target: &MissileState, interceptor_speed: f64) -> Option<[f64; 3]> {
+    let r = vector_sub(&target.position, &interceptor.position);
+    let v = target.velocity;
+    // (|v|^2 - s^2) t^2 + 2 (r . v) t + |r|^2 = 0
+    let a = v[0] * v[0] + v[1] * v[1] + v[2] * v[2] - interceptor_speed * interceptor_speed;
+    let b = 2.0 * (r[0] * v[0] + r[1] * v[1] + r[2] * v[2]);
+    let c = r[0] * r[0] + r[1] * r[1] + r[2] * r[2];
+    let t = if a.abs() < 1e-9 {
+        if b >= 0.0 {
+            return None;
+        }
+        -c / b
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let sqrt_d = discriminant.sqrt();
+        let roots = [(-b - sqrt_d) / (2.0 * a), (-b + sqrt_d) / (2.0 * a)];
+        roots.into_iter().filter(|&t| t >= 0.0).fold(None, |min: Option<f64>, t| {
+            Some(min.map_or(t, |m| m.min(t)))
+        })?
+    };
+    Some([
+        target.position[0] + v[0] * t,
+        target.position[1] + v[1] * t,
+        target.position[2] + v[2] * t,
+    ])
+}
+
+/// 目標位置を目標速度で time [s] だけ外挿した目標状態
+pub fn extrapolate_target(target: &MissileState, time: f64) -> MissileState {
+    let v = target.velocity;
+    MissileState {
+        position: [
+            target.position[0] + v[0] * time,
+            target.position[1] + v[1] * time,
+            target.position[2] + v[2] * time,
+        ],
+        ..target.clone()
+    }
+}
+
+/// 誘導で目指す点 (PIP を求められない場合は目標の現在位置)
+/// target が計測 (measurement_age [s] 前の計測) の場合は、target_extrapolation_time
+/// (None なら measurement_age) だけ外挿した予測位置とし、計測遅れによる視線の遅れを補償する。
+/// 計測遅れの補償はここだけで行う。target が真値 (measurement_age が None) の場合は外挿しない
+pub fn guidance_target(
+    params: &InterceptorParams,
+    state: &InterceptorState,
+    target: &MissileState,
+    measurement_age: Option<f64>,
+) -> [f64; 3] {
+    let extrapolation_time = measurement_age.map_or(0.0, |age| params.target_extrapolation_time.unwrap_or(age));
+    let extrapolated;
+    let target = if extrapolation_time != 0.0 {
+        extrapolated = extrapolate_target(target, extrapolation_time);
+        &extrapolated
+    } else {
+        target
+    };
+    match params.aim_point {
+        AimPoint::Target => target.position,
+        AimPoint::PredictedInterceptPoint => {
+            compute_pip(state, target, vector_norm(&state.velocity)).unwrap_or(target.position)
+        }
+    }
+}
+
+/// 推奨する比例航法定数の下限・上限
+pub const MIN_NAV_CONSTANT: f64 = 3.0;
+pub const MAX_NAV_CONSTANT: f64 = 8.0;
+/// 推奨比例航法定数の算出に用いる基準時間 [s]
+const NAV_CONSTANT_REFERENCE_TIME: f64 = 10.0;
+
+/// 交戦条件に対して推奨する比例航法定数 N
+/// 目標機動加速度 target_maneuver を、残り時間 time_to_go で修正できる加速度の目安
+/// closing_speed * time_to_go / T^2 (T: 基準時間) と比べ、その比だけ N を下限 3 から増やす。
+/// 機動が大きいほど・残り時間が短いほど大きな N を推奨し、誘導加速度の飽和を避ける。
+/// 結果は MAX_NAV_CONSTANT で頭打ちにする (接近速度・残り時間が0以下の場合も上限を返す)。
+/// g_limit がある場合は、現在の視線角速度 los_rate での比例航法指令 N Vc λ̇ が
+/// 上限 max_acceleration を超えないよう N をさらに制限する (この制限は下限 MIN_NAV_CONSTANT より優先する)
+pub fn recommended_nav_constant(
+    closing_speed: f64,
+    los_rate: f64,
+    target_maneuver: f64,
+    time_to_go: f64,
+    g_limit: Option<&GLimit>,
+) -> f64 {
+    let n = if closing_speed <= 0.0 || time_to_go <= 0.0 {
+        MAX_NAV_CONSTANT
+    } else {
+        let correctable = closing_speed * time_to_go / (NAV_CONSTANT_REFERENCE_TIME * NAV_CONSTANT_REFERENCE_TIME);
+        (MIN_NAV_CONSTANT + target_maneuver.abs() / correctable).min(MAX_NAV_CONSTANT)
+    };
+    let demand_per_n = closing_speed * los_rate.abs();
+    match g_limit {
+        Some(limit) if demand_per_n > 0.0 => n.min(limit.max_acceleration / demand_per_n),
+        _ => n,
+    }
+}
+
+/// 目標距離に対して有効な誘導則 (handover_range 以下で終末誘導)
+pub fn active_guidance_law(phased: &PhasedGuidance, range: f64) -> GuidanceLaw {
+    if range <= phased.handover_range {
+        phased.terminal
+    } else {
+        phased.midcourse
+    }
+}
+
+/// 中間誘導で目指す点 (ロフト高度の指定があれば目標の真上のロフト高度、目標の方が高ければ目標)
+pub fn midcourse_aim_point(phased: &PhasedGuidance, target_pos: &[f64; 3]) -> [f64; 3] {
+    match phased.loft_altitude {
+        Some(altitude) => [target_pos[0], target_pos[1], target_pos[2].max(altitude)],
+        None => *target_pos,
+    }
+}
+
+/// 中間誘導で目指す点の速度 (ロフト高度で目指す場合は目標の水平移動に追従し、鉛直成分は 0)
+pub fn midcourse_aim_velocity(phased: &PhasedGuidance, target_pos: &[f64; 3], target_velocity: &[f64; 3]) -> [f64; 3] {
+    match phased.loft_altitude {
+        Some(altitude) if altitude > target_pos[2] => [target_velocity[0], target_velocity[1], 0.0],
+        _ => *target_velocity,
+    }
+}
+
+/// 飛翔段階に応じた誘導加速度指令 (混合区間では中間誘導と終末誘導を線形に混合)
+/// 飛翔段階は目標までの距離で判定し、中間誘導の指令は midcourse_aim_point に対して計算する
+pub fn phased_guidance_command(
+    phased: &PhasedGuidance,
+    state: &InterceptorState,
+    target_pos: &[f64; 3],
+    target_velocity: &[f64; 3],
+    constants: &GuidanceConstants,
+) -> [f64; 3] {
+    let range = vector_norm(&vector_sub(target_pos, &state.position));
+    let blend_end = phased.handover_range + phased.blend_range;
+    let midcourse_pos = midcourse_aim_point(phased, target_pos);
+    let midcourse_vel = midcourse_aim_velocity(phased, target_pos, target_velocity);
+    let midcourse = || guidance_law_command(phased.midcourse, state, &midcourse_pos, &midcourse_vel, constants);
+    let terminal = || guidance_law_command(phased.terminal, state, target_pos, target_velocity, constants);
+    if range <= phased.handover_range {
+        return terminal();
+    }
+    if phased.blend_range <= 0.0 || range >= blend_end {
+        return midcourse();
+    }
+    // 終末誘導の重み (blend_end で 0、handover_range で 1)
+    let w = (blend_end - range) / phased.blend_range;
+    let (midcourse, terminal) = (midcourse(), terminal());
+    [
+        (1.0 - w) * midcourse[0] + w * terminal[0],
+        (1.0 - w) * midcourse[1] + w * terminal[1],
+        (1.0 - w) * midcourse[2] + w * terminal[2],
+    ]
+}
+
+/// 誘導指令の不感帯
+/// 視線角速度の微小な雑音による舵のばたつきを抑えるため、大きさが deadband 未満の指令は0とし、
+/// それ以上の指令はそのまま通す
+pub fn apply_guidance_deadband(a_command: &[f64; 3], deadband: f64) -> [f64; 3] {
+    if vector_norm(a_command) < deadband {
+        [0.0, 0.0, 0.0]
+    } else {
+        *a_command
+    }
+}
+
+/// 誘導指令の1次遅れフィルタの更新
+/// 内部状態 x を x += (1 - exp(-dt/tau)) (a - x) で更新する。
+/// フィルタなし・時定数0以下では指令をそのまま返す。戻り値はフィルタの出力
+pub fn apply_command_filter(
+    a_command: &[f64; 3],
+    filter: Option<&CommandFilter>,
+    filtered: &[f64; 3],
+    dt: f64,
+) -> [f64; 3] {
+    let filter = match filter {
+        Some(filter) if filter.time_constant > 0.0 => filter,
+        _ => return *a_command,
+    };
+    let gain = 1.0 - (-dt / filter.time_constant).exp();
+    core::array::from_fn(|i| filtered[i] + gain * (a_command[i] - filtered[i]))
+}
+
+/// 誘導加速度の上限による制限と飽和時間の更新
+/// 指令が上限を超えた場合は上限の大きさに縮小し、飽和時間を dt 加算する。
+/// 飽和していなければ飽和時間を0に戻す。戻り値は (制限後の指令, 更新後の飽和時間)
+pub fn apply_g_limit(
+    a_command: &[f64; 3],
+    g_limit: Option<&GLimit>,
+    saturation_time: f64,
+    dt: f64,
+) -> ([f64; 3], f64) {
+    let limit = match g_limit {
+        Some(limit) => limit,
+        None => return (*a_command, 0.0),
+    };
+    let magnitude = vector_norm(a_command);
+    if magnitude <= limit.max_acceleration {
+        return (*a_command, 0.0);
+    }
+    let scale = limit.max_acceleration / magnitude;
+    (
+        [a_command[0] * scale, a_command[1] * scale, a_command[2] * scale],
+        saturation_time + dt,
+    )
+}
+
+/// ホーミング過負荷 (誘導加速度の飽和が overload_time 以上継続) により
+/// 迎撃失敗が見込まれるかどうか。火器管制は該当する迎撃ミサイルを交戦中に数えず、目標へ追加発射する
+pub fn is_homing_saturated(interceptor: &Interceptor) -> bool {
+    match &interceptor.params.g_limit {
+        Some(limit) => interceptor.state.saturation_time >= limit.overload_time,
+        None => false,
+    }
+}
+
+/// ダイバート予算による誘導加速度の制限
+/// 消費量は |a| * dt とし、予算が尽きた後は誘導加速度を0にする。
+/// 戻り値は (制限後の誘導加速度, 更新後の消費量)
+pub fn apply_divert_budget(
+    a_guidance: &[f64; 3],
+    divert_used: f64,
+    divert_budget: Option<f64>,
+    dt: f64,
+) -> ([f64; 3], f64) {
+    let budget = match divert_budget {
+        Some(budget) => budget,
+        None => return (*a_guidance, divert_used),
+    };
+    let remaining = budget - divert_used;
+    if remaining <= 0.0 {
+        return ([0.0, 0.0, 0.0], divert_used);
+    }
+    let cost = vector_norm(a_guidance) * dt;
+    if cost <= remaining {
+        (*a_guidance, divert_used + cost)
+    } else {
+        // 残り予算分だけ誘導して使い切る
+        let scale = remaining / cost;
+        (
+            [a_guidance[0] * scale, a_guidance[1] * scale, a_guidance[2] * scale],
+            budget,
+        )
+    }
+}
+
+/// ダイバートスラスタのパルス噴射
+/// 誘導加速度の速度ベクトルに垂直な成分の方向へ、力積/質量 の速度変化を与える。
+/// パルスを使い切った場合や垂直成分が無い場合は速度を変えない。
+/// 戻り値は (噴射後の速度, 更新後の使用パルス数)
+pub fn apply_divert_pulse(
+    thruster: &DivertThruster,
+    pulses_used: u32,
+    velocity: &[f64; 3],
+    a_guidance: &[f64; 3],
+    mass: f64,
+) -> ([f64; 3], u32) {
+    if pulses_used >= thruster.count || mass <= 0.0 {
+        return (*velocity, pulses_used);
+    }
+    let v_u = vector_normalize(velocity);
+    let along = a_guidance[0] * v_u[0] + a_guidance[1] * v_u[1] + a_guidance[2] * v_u[2];
+    let lateral = [
+        a_guidance[0] - along * v_u[0],
+        a_guidance[1] - along * v_u[1],
+        a_guidance[2] - along * v_u[2],
+    ];
+    if vector_norm(&lateral) < 1e-9 {
+        return (*velocity, pulses_used);
+    }
+    let direction = vector_normalize(&lateral);
+    let delta_v = thruster.impulse_per_command / mass;
+    (
+        [
+            velocity[0] + delta_v * direction[0],
+            velocity[1] + delta_v * direction[1],
+            velocity[2] + delta_v * direction[2],
+        ],
+        pulses_used + 1,
+    )
+}
+
+/// 誘導指令の零次ホールド
+/// 更新レートごとにのみ新しい指令を採用し、その間は前回の指令を保持する。
+/// 戻り値は (採用する指令, 更新後の経過時間)
+pub fn hold_guidance_command(
+    guidance_rate_hz: Option<f64>,
+    held_command: Option<[f64; 3]>,
+    time_since_guidance: f64,
+    fresh_command: [f64; 3],
+    dt: f64,
+) -> ([f64; 3], f64) {
+    let (rate, held) = match (guidance_rate_hz, held_command) {
+        (Some(rate), Some(held)) if rate > 0.0 => (rate, held),
+        _ => return (fresh_command, dt),
+    };
+    // 浮動小数点の丸めで更新周期を取りこぼさないよう許容誤差を設ける
+    if time_since_guidance + 1e-9 >= 1.0 / rate {
+        (fresh_command, dt)
+    } else {
+        (held, time_since_guidance + dt)
+    }
+}
+
+/// シーカが目標を捕捉済みか (発射遅れ + シーカ捕捉時間を経過したか)
+pub fn seeker_acquired(params: &InterceptorParams, time_since_launch: f64) -> bool {
+    time_since_launch + 1e-9 >= params.launch_delay + params.seeker_warmup
+}
+
+/// 誘導を行えるか (シーカ捕捉済みで、かつ舵が効く速度に達しているか)
+pub fn guidance_enabled(params: &InterceptorParams, state: &InterceptorState) -> bool {
+    seeker_acquired(params, state.time_since_launch) && vector_norm(&state.velocity) >= params.min_control_speed
+}
+
+/// 現在の状態に対する誘導指令
+/// 接近速度は目標速度との相対速度から求める。
+/// turn_around_acceleration が正で接近速度が負 (目標を追い越した・目標が遠ざかる) 場合は
+/// 誘導則を適用せず、旋回して再捕捉する。
+/// nav_schedule がある場合は残り時間 (距離 / 接近速度) から比例航法定数を補間する
+fn fresh_guidance_command(
+    params: &InterceptorParams,
+    state: &InterceptorState,
+    target_pos: &[f64; 3],
+    target_velocity: &[f64; 3],
+) -> [f64; 3] {
+    let geom = relative_geometry_from(&state.position, &state.velocity, target_pos, target_velocity);
+    if params.turn_around_acceleration > 0.0 && geom.closing_speed < 0.0 {
+        return turn_around_command(state, target_pos, params.turn_around_acceleration);
+    }
+    let scheduled = params
+        .nav_schedule
+        .as_ref()
+        .and_then(|schedule| schedule.nav_constant(geom.range / geom.closing_speed))
+        .map(|n| GuidanceConstants { n });
+    let constants = scheduled.as_ref().unwrap_or(&params.guidance_constants);
+    match &params.phased_guidance {
+        Some(phased) => phased_guidance_command(phased, state, target_pos, target_velocity, constants),
+        None => guidance(state, target_pos, constants),
+    }
+}
+
+/// 迎撃ミサイルの速度積分器 (x, y, z) をパラメータの integrator_damping で生成する
+pub fn new_interceptor_integrators(params: &InterceptorParams) -> [AdamsBashforthIntegrator; 3] {
+    core::array::from_fn(|_| AdamsBashforthIntegrator::with_damping(params.integrator_damping))
+}
+
+/// 迎撃ミサイルの運動更新 (target_pos: 誘導で目指す点、target_velocity: 目標の速度)
 pub fn update_interceptor(
     params: &InterceptorParams,
     state: &InterceptorState,
     target_pos: &[f64; 3],
+    target_velocity: &[f64; 3],
     integrators: &mut [AdamsBashforthIntegrator; 3],
     filters: &mut [LowPassFilter; 3],
     dt: f64,
 ) -> InterceptorState {
-    if !state.launched {
+    if !state.launched || state.inert {
         return state.clone();
     }
 
-    // 誘導加速度
-    let a_guidance = guidance(state, target_pos, &params.guidance_constants);
+    // 発射遅れの間は静止したまま時間だけ進める
+    if state.time_since_launch < params.launch_delay {
+        return InterceptorState {
+            time_since_launch: state.time_since_launch + dt,
+            ..state.clone()
+        };
+    }
+
+    // 誘導指令 (更新レートに応じて零次ホールド)。シーカ捕捉前・低速時は誘導しない
+    // 外部からの誘導指令がある場合はこのステップのみそれを用いる
+    let guidance_active = guidance_enabled(params, state) || state.guidance_override.is_some();
+    let (a_command, time_since_guidance) = match state.guidance_override {
+        Some(command) => (command, 0.0),
+        None if guidance_active => hold_guidance_command(
+            params.guidance_rate_hz,
+            state.guidance_command,
+            state.time_since_guidance,
+            fresh_guidance_command(params, state, target_pos, target_velocity),
+            dt,
+        ),
+        None => ([0.0, 0.0, 0.0], state.time_since_guidance),
+    };
+    // 誘導指令の不感帯、軸ごとの制限、応答遅れと誘導加速度の上限
+    let a_command = apply_guidance_deadband(&a_command, params.guidance_deadband);
+    let a_command =
+        apply_channel_limits(&a_command, params.guidance_frame, &state.velocity, params.channel_limits.as_ref());
+    let a_command = apply_command_filter(&a_command, params.command_filter.as_ref(), &state.filtered_command, dt);
+    let (a_command, saturation_time) =
+        apply_g_limit(&a_command, params.g_limit.as_ref(), state.saturation_time, dt);
+    // アンチワインドアップ: フィルタの内部状態を上限で制限した指令に戻す
+    let filtered_command = a_command;
+
+    // 誘導加速度 (ダイバート予算で制限)。ダイバートスラスタ使用時は空力誘導を行わない
+    let (a_guidance, divert_used) = match params.divert_thruster {
+        Some(_) => ([0.0, 0.0, 0.0], state.divert_used),
+        None => apply_divert_budget(&a_command, state.divert_used, params.divert_budget, dt),
+    };
 
     // 速度ノルム
     let speed = vector_norm(&state.velocity);
@@ -99,7 +920,7 @@ pub fn update_interceptor(
     };
 
     // 重力
-    let gravity_vec = [0.0, 0.0, -params.g * state.mass];
+    let gravity_vec = params.gravity().force(state.mass);
 
     // 推力ベクトル
     let thrust_vec = [
@@ -129,6 +950,18 @@ pub fn update_interceptor(
         new_velocity[i] = filters[i].apply(new_velocity[i]);
     }
 
+    // ダイバートスラスタのパルス噴射
+    let (new_velocity, divert_pulses_used) = match &params.divert_thruster {
+        Some(thruster) => apply_divert_pulse(
+            thruster,
+            state.divert_pulses_used,
+            &new_velocity,
+            &a_command,
+            state.mass,
+        ),
+        None => (new_velocity, state.divert_pulses_used),
+    };
+
     // 位置更新
     let new_position = [
         state.position[0] + new_velocity[0] * dt,
@@ -151,13 +984,153 @@ pub fn update_interceptor(
         position: new_position,
         velocity: new_velocity,
         launched: state.launched,
+        divert_used,
+        time_since_launch: state.time_since_launch + dt,
+        divert_pulses_used,
+        guidance_command: if guidance_active {
+            Some(a_command)
+        } else {
+            None
+        },
+        time_since_guidance,
+        // 降下中に最低交戦高度を下回ったら自爆
+        inert: below_engagement_altitude(&new_position, params.min_engagement_altitude)
+            && new_velocity[2] < 0.0,
+        saturation_time,
+        guidance_override: None,
+        filtered_command,
+    }
+}
+
+/// 目標が撃破された迎撃ミサイルの再割り当て先
+/// engage_order (脅威の高い順) のうち、生存していて (active) 迎撃ミサイルから retarget_range 以内にある最初の目標。
+/// 該当する目標がなければ None (迎撃ミサイルは無力化する)
+pub fn retarget(
+    state: &InterceptorState,
+    engage_order: &[usize],
+    missiles: &[Missile],
+    retarget_range: f64,
+) -> Option<usize> {
+    engage_order.iter().copied().find(|&m| {
+        missiles.get(m).is_some_and(|missile| {
+            missile.state.active && vector_norm(&vector_sub(&missile.state.position, &state.position)) <= retarget_range
+        })
+    })
+}
+
+/// 目標を変更した迎撃ミサイルの状態
+/// 前の目標に対する誘導の内部状態 (保持中の誘導指令、指令フィルタ、飽和時間) を初期化する
+pub fn retargeted_state(state: &InterceptorState) -> InterceptorState {
+    InterceptorState {
+        guidance_command: None,
+        time_since_guidance: 0.0,
+        saturation_time: 0.0,
+        filtered_command: [0.0; 3],
+        ..state.clone()
+    }
+}
+
+/// 推力を出しているか (発射後、発射遅れを経過し、推力があり質量が残っている)
+pub fn is_thrusting(params: &InterceptorParams, state: &InterceptorState) -> bool {
+    state.launched && state.time_since_launch >= params.launch_delay && state.thrust > 0.0 && state.mass > 0.0
+}
+
+/// 位置が最低交戦高度を下回っているかどうか
+pub fn below_engagement_altitude(position: &[f64; 3], min_engagement_altitude: Option<f64>) -> bool {
+    match min_engagement_altitude {
+        Some(min_altitude) => position[2] < min_altitude,
+        None => false,
+    }
+}
+
+/// 交戦可否の判定 (性能包絡線)
+/// 現在位置から予測迎撃点へ向かう直線飛翔を、推力・燃料消費・抗力・重力を考慮して
+/// 数値積分し、与えられた時間内に到達できるかを判定する。
+pub fn can_engage(
+    state: &InterceptorState,
+    params: &InterceptorParams,
+    predicted_intercept_point: &[f64; 3],
+    time_available: f64,
+) -> bool {
+    let rel = vector_sub(predicted_intercept_point, &state.position);
+    let range = vector_norm(&rel);
+    if range < 1e-9 {
+        return true;
+    }
+    let los = vector_normalize(&rel);
+
+    // 発射遅れの残りは飛翔できない
+    let remaining_delay = if state.launched {
+        (params.launch_delay - state.time_since_launch).max(0.0)
+    } else {
+        params.launch_delay
+    };
+    let flight_time = time_available - remaining_delay;
+    if flight_time <= 0.0 {
+        return false;
+    }
+
+    // 視線方向の1次元飛翔 (初速は視線方向成分)
+    let step: f64 = 0.01;
+    let gravity = params.gravity().acceleration;
+    let mut speed = (state.velocity[0] * los[0] + state.velocity[1] * los[1] + state.velocity[2] * los[2]).max(0.0);
+    let mut mass = state.mass;
+    let mut distance = 0.0;
+    let mut t = 0.0;
+    while t < flight_time {
+        let h = step.min(flight_time - t);
+        // 燃料が尽きたら推力なし
+        let burn = params.alpha * state.thrust * h;
+        let thrust = if mass - burn > 0.0 { state.thrust } else { 0.0 };
+        if thrust > 0.0 {
+            mass -= burn;
+        }
+        let drag = 0.5 * params.cd * params.area * speed * speed;
+        let accel = (thrust - drag) / mass + dot(&gravity, &los);
+        speed = (speed + accel * h).max(0.0);
+        distance += speed * h;
+        if distance >= range {
+            return true;
+        }
+        t += h;
+    }
+    false
+}
+
+/// 発射母体 (艦艇・航空機など)。固定発射機は速度0
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Launcher {
+    pub position: [f64; 3], // 発射母体の位置 [m]
+    pub velocity: [f64; 3], // 発射母体の速度 [m/s]
+}
+
+impl Launcher {
+    /// 等速で dt だけ移動させる
+    pub fn advance(&self, dt: f64) -> Launcher {
+        Launcher {
+            position: [
+                self.position[0] + self.velocity[0] * dt,
+                self.position[1] + self.velocity[1] * dt,
+                self.position[2] + self.velocity[2] * dt,
+            ],
+            velocity: self.velocity,
+        }
     }
 }
 
 /// 迎撃ミサイルを発射状態にする
-pub fn launch_interceptor(state: &InterceptorState) -> InterceptorState {
+/// 発射指示の時点を経過時間の起点とし、launch_delay 経過後に運動を開始する。
+/// 発射母体がある場合、速度は 発射速度 (state.velocity) + 母体速度 となる
+pub fn launch_interceptor(state: &InterceptorState, launcher: Option<&Launcher>) -> InterceptorState {
+    let platform_velocity = launcher.map_or([0.0; 3], |l| l.velocity);
     InterceptorState {
         launched: true,
+        time_since_launch: 0.0,
+        velocity: [
+            state.velocity[0] + platform_velocity[0],
+            state.velocity[1] + platform_velocity[1],
+            state.velocity[2] + platform_velocity[2],
+        ],
         ..*state
     }
 }
@@ -168,7 +1141,1368 @@ pub fn check_interception(
     missile: &MissileState,
     intercept_dist: f64
 ) -> bool {
+    if interceptor.inert {
+        return false;
+    }
     let diff = vector_sub(&interceptor.position, &missile.position);
     let dist = vector_norm(&diff);
     dist <= intercept_dist
-}
\ No newline at end of file
+}
+
+/// 同一ステップ内の迎撃判定の解決方法
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionMode {
+    /// 全エンティティを更新した後 (ステップ終了時点) の位置の組で全迎撃ミサイルを同時に判定し、判定中は状態を変更しない。
+    /// 同じミサイルに複数が到達した場合は最も近い迎撃ミサイル (同距離なら番号の小さい方) の迎撃とする
+    #[default]
+    Simultaneous,
+    /// 同じくステップ終了時点の位置で迎撃ミサイルの番号順に判定し、先に迎撃されたミサイルは以降の判定から除く
+    Sequential,
+}
+
+/// 同一ステップ内で地表着弾と迎撃の両方が起こり得る場合の優先規則
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactPrecedence {
+    /// 常に着弾を優先する (着弾したミサイルは迎撃判定から除く)
+    #[default]
+    Impact,
+    /// 常に迎撃を優先する (ステップ内で迎撃判定距離に入れば迎撃とする)
+    Intercept,
+    /// ステップ内で補間した発生時刻の早い方を優先する (同時刻なら着弾)
+    Earliest,
+}
+
+impl ImpactPrecedence {
+    /// ステップ開始からの着弾時刻 impact_time と迎撃時刻 intercept_time [s] から迎撃を採用するか判定する
+    pub fn intercept_wins(self, impact_time: f64, intercept_time: f64) -> bool {
+        match self {
+            ImpactPrecedence::Impact => false,
+            ImpactPrecedence::Intercept => true,
+            ImpactPrecedence::Earliest => intercept_time < impact_time,
+        }
+    }
+}
+
+/// ステップ内で相対位置が rel_start から rel_end へ線形に変化するとき、
+/// 距離が radius 以下になる最初の時刻の割合 (0〜1) を返す。到達しなければ None
+pub fn first_contact_fraction(rel_start: &[f64; 3], rel_end: &[f64; 3], radius: f64) -> Option<f64> {
+    let d = vector_sub(rel_end, rel_start);
+    let c = dot(rel_start, rel_start) - radius * radius;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    let a = dot(&d, &d);
+    let b = dot(rel_start, &d);
+    let disc = b * b - a * c;
+    if a == 0.0 || disc < 0.0 {
+        return None;
+    }
+    let s = (-b - disc.sqrt()) / a;
+    (0.0..=1.0).contains(&s).then_some(s)
+}
+
+/// ステップ内で相対位置が rel_start から rel_end へ線形に変化するときの最接近
+/// 戻り値は (最接近時刻の割合 0〜1, 最接近距離 [m])
+pub fn closest_approach(rel_start: &[f64; 3], rel_end: &[f64; 3]) -> (f64, f64) {
+    let d = vector_sub(rel_end, rel_start);
+    let dd = dot(&d, &d);
+    let s = if dd > 0.0 { (-dot(rel_start, &d) / dd).clamp(0.0, 1.0) } else { 0.0 };
+    let closest = [rel_start[0] + d[0] * s, rel_start[1] + d[1] * s, rel_start[2] + d[2] * s];
+    (s, vector_norm(&closest))
+}
+
+/// 近接信管の起爆判定
+/// ステップ内で相対位置が rel_start から rel_end へ線形に変化するとき、距離変化率が負 (接近) から
+/// 正 (離隔) へ変わる最接近点で起爆する。最接近時刻に発射からの経過時間が arming_time に達していなければ起爆しない。
+/// time_since_launch はステップ終了時点の経過時間 [s]。戻り値は起爆時刻の割合 (0〜1)
+pub fn fuze_detonation(
+    arming_time: f64,
+    time_since_launch: f64,
+    rel_start: &[f64; 3],
+    rel_end: &[f64; 3],
+    dt: f64,
+) -> Option<f64> {
+    let d = vector_sub(rel_end, rel_start);
+    // ステップ両端での距離変化率の符号 (d は相対速度 * dt)
+    if dot(rel_start, &d) >= 0.0 || dot(rel_end, &d) <= 0.0 {
+        return None;
+    }
+    let (s, _) = closest_approach(rel_start, rel_end);
+    let armed = time_since_launch - (1.0 - s) * dt + 1e-9 >= arming_time;
+    armed.then_some(s)
+}
+
+/// 迎撃の成立 (迎撃ミサイル番号, ミサイル番号)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interception {
+    pub interceptor: usize,
+    pub missile: usize,
+}
+
+/// 1ステップ分の迎撃判定
+/// targets は各迎撃ミサイルの追尾対象 (ミサイル番号)。未発射・対象なしは None。
+/// 撃破済み (active=false) のミサイルは判定しない。
+/// 戻り値はミサイル番号順の迎撃一覧 (1ミサイルにつき1件)
+pub fn resolve_interceptions(
+    interceptors: &[InterceptorState],
+    targets: &[Option<usize>],
+    missiles: &[MissileState],
+    intercept_dist: f64,
+    mode: ResolutionMode,
+) -> Vec<Interception> {
+    let mut killed: Vec<Option<(usize, f64)>> = vec![None; missiles.len()];
+    for (i, (interceptor, target)) in interceptors.iter().zip(targets).enumerate() {
+        let Some(m) = *target else { continue };
+        let Some(missile) = missiles.get(m) else { continue };
+        if !missile.active || !check_interception(interceptor, missile, intercept_dist) {
+            continue;
+        }
+        let dist = vector_norm(&vector_sub(&interceptor.position, &missile.position));
+        let replace = match (mode, killed[m]) {
+            (_, None) => true,
+            (ResolutionMode::Sequential, Some(_)) => false,
+            (ResolutionMode::Simultaneous, Some((j, best))) => dist < best || (dist == best && i < j),
+        };
+        if replace {
+            killed[m] = Some((i, dist));
+        }
+    }
+    killed
+        .iter()
+        .enumerate()
+        .filter_map(|(m, kill)| kill.map(|(i, _)| Interception { interceptor: i, missile: m }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_params(n: f64, divert_budget: Option<f64>) -> InterceptorParams {
+        InterceptorParams {
+            g: 9.81,
+            alpha_filter: 1.0,
+            guidance_constants: GuidanceConstants { n },
+            divert_budget,
+            ..Default::default()
+        }
+    }
+
+    fn test_state() -> InterceptorState {
+        InterceptorState {
+            mass: 500.0,
+            position: [0.0, 0.0, 1000.0],
+            velocity: [100.0, 0.0, 0.0],
+            launched: true,
+            ..Default::default()
+        }
+    }
+
+    fn new_integrators() -> [AdamsBashforthIntegrator; 3] {
+        core::array::from_fn(|_| AdamsBashforthIntegrator::new())
+    }
+
+    fn new_filters() -> [LowPassFilter; 3] {
+        core::array::from_fn(|_| LowPassFilter::new(1.0))
+    }
+
+    #[test]
+    fn test_divert_budget_unlimited() {
+        let (a, used) = apply_divert_budget(&[30.0, 40.0, 0.0], 0.0, None, 0.1);
+        assert_eq!(a, [30.0, 40.0, 0.0]);
+        assert_eq!(used, 0.0);
+    }
+
+    #[test]
+    fn test_divert_budget_consumes_and_saturates() {
+        // |a| = 50, dt = 0.1 → 1回あたり 5 m/s を消費
+        let (a, used) = apply_divert_budget(&[30.0, 40.0, 0.0], 0.0, Some(8.0), 0.1);
+        assert_eq!(a, [30.0, 40.0, 0.0]);
+        assert!((used - 5.0).abs() < 1e-12);
+
+        // 残り 3 m/s → 加速度を縮小して使い切る
+        let (a, used) = apply_divert_budget(&a, used, Some(8.0), 0.1);
+        assert!((vector_norm(&a) - 30.0).abs() < 1e-9);
+        assert_eq!(used, 8.0);
+
+        // 予算切れ → 誘導なし
+        let (a, used) = apply_divert_budget(&[30.0, 40.0, 0.0], used, Some(8.0), 0.1);
+        assert_eq!(a, [0.0, 0.0, 0.0]);
+        assert_eq!(used, 8.0);
+    }
+
+    #[test]
+    fn test_high_maneuver_exhausts_budget_then_suppresses_guidance() {
+        let params = test_params(200.0, Some(30.0));
+        let target = [0.0, 5000.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+
+        // |a| = 200, dt = 0.1 → 20 m/s/step なので2ステップで予算切れ
+        let mut state = test_state();
+        for _ in 0..2 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        }
+        assert_eq!(state.divert_used, 30.0);
+
+        // 以降の更新は誘導なし (n=0) と一致すること
+        let unguided = test_params(0.0, None);
+        let mut integrators_ref = integrators.clone();
+        let mut filters_ref = filters.clone();
+        let guided_next = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        let unguided_next =
+            update_interceptor(&unguided, &state, &target, &[0.0; 3], &mut integrators_ref, &mut filters_ref, 0.1);
+        assert_eq!(guided_next.velocity, unguided_next.velocity);
+        assert_eq!(guided_next.divert_used, 30.0);
+    }
+
+    #[test]
+    fn test_interceptor_stationary_during_launch_delay() {
+        let params = InterceptorParams {
+            thrust: 10000.0,
+            launch_delay: 1.0,
+            ..test_params(0.1, None)
+        };
+        let target = [0.0, 5000.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let initial = InterceptorState {
+            launched: false,
+            velocity: [0.0, 0.0, 0.0],
+            thrust: 10000.0,
+            theta: std::f64::consts::FRAC_PI_2,
+            ..test_state()
+        };
+
+        // 発射前は動かない
+        let mut state = update_interceptor(&params, &initial, &target, &[0.0; 3], &mut integrators, &mut filters, 0.25);
+        assert_eq!(state.position, initial.position);
+
+        // 発射指示から 1.0 s (4ステップ) は静止
+        state = launch_interceptor(&state, None);
+        for _ in 0..4 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.25);
+            assert_eq!(state.position, initial.position);
+            assert_eq!(state.velocity, [0.0, 0.0, 0.0]);
+        }
+        assert_eq!(state.time_since_launch, 1.0);
+
+        // 遅れ経過後に運動開始
+        state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.25);
+        assert_ne!(state.position, initial.position);
+        assert!(state.velocity[2] > 0.0);
+    }
+
+    #[test]
+    fn test_divert_pulses_change_velocity_until_exhausted() {
+        // 重力・推力・抗力なしで、速度変化はパルスのみ
+        let params = InterceptorParams {
+            g: 0.0,
+            divert_thruster: Some(DivertThruster {
+                impulse_per_command: 1000.0,
+                count: 3,
+            }),
+            ..test_params(1.0, None)
+        };
+        let target = [5000.0, 5000.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = test_state();
+
+        // 1パルスあたり 1000 N s / 500 kg = 2 m/s の横方向速度変化
+        for pulse in 1..=3 {
+            let previous = state.velocity;
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            let dv = vector_sub(&state.velocity, &previous);
+            assert!((vector_norm(&dv) - 2.0).abs() < 1e-9);
+            let along = dv[0] * previous[0] + dv[1] * previous[1] + dv[2] * previous[2];
+            assert!(along.abs() < 1e-6);
+            assert_eq!(state.divert_pulses_used, pulse);
+        }
+
+        // パルスを使い切ると機動しない
+        let exhausted = state.velocity;
+        for _ in 0..3 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            assert!(vector_norm(&vector_sub(&state.velocity, &exhausted)) < 1e-9);
+            assert_eq!(state.divert_pulses_used, 3);
+        }
+    }
+
+    #[test]
+    fn test_divert_pulse_without_lateral_command() {
+        let thruster = DivertThruster {
+            impulse_per_command: 1000.0,
+            count: 3,
+        };
+        // 誘導指令が速度方向のみなら噴射しない
+        let (velocity, used) = apply_divert_pulse(&thruster, 0, &[100.0, 0.0, 0.0], &[5.0, 0.0, 0.0], 500.0);
+        assert_eq!(velocity, [100.0, 0.0, 0.0]);
+        assert_eq!(used, 0);
+    }
+
+    #[test]
+    fn test_low_guidance_rate_holds_command_between_updates() {
+        // 2 Hz, dt = 0.1 → 5ステップごとに指令を更新
+        let params = InterceptorParams {
+            g: 0.0,
+            guidance_rate_hz: Some(2.0),
+            ..test_params(50.0, None)
+        };
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = test_state();
+
+        let mut commands = Vec::new();
+        for step in 0..10 {
+            // 目標は毎ステップ移動するため、毎回計算すれば指令も変わる
+            let target = [1000.0, 500.0 * step as f64, 1000.0];
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            commands.push(state.guidance_command.unwrap());
+        }
+
+        for i in 1..5 {
+            assert_eq!(commands[i], commands[0]);
+            assert_eq!(commands[5 + i], commands[5]);
+        }
+        assert_ne!(commands[5], commands[0]);
+    }
+
+    #[test]
+    fn test_guidance_updates_every_step_without_rate() {
+        let held = Some([1.0, 0.0, 0.0]);
+        let (command, elapsed) = hold_guidance_command(None, held, 0.0, [0.0, 2.0, 0.0], 0.1);
+        assert_eq!(command, [0.0, 2.0, 0.0]);
+        assert_eq!(elapsed, 0.1);
+    }
+
+    #[test]
+    fn test_interceptor_diving_below_min_altitude_goes_inert() {
+        let params = InterceptorParams {
+            min_engagement_altitude: Some(950.0),
+            ..test_params(0.0, None)
+        };
+        let target = [5000.0, 0.0, 0.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = InterceptorState {
+            velocity: [100.0, 0.0, -100.0],
+            ..test_state()
+        };
+
+        // 1000 m から毎秒 100 m 以上で降下 → 1 s 以内に 950 m を下回る
+        for _ in 0..10 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        }
+        assert!(state.inert);
+
+        // 無力化後は動かず、迎撃もしない
+        let frozen = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        assert_eq!(frozen.position, state.position);
+        let mut missile = crate::models::missile::MissileState {
+            mass: 1000.0,
+            position: state.position,
+            ..Default::default()
+        };
+        assert!(!check_interception(&frozen, &missile, 50.0));
+        missile.position[2] += 1.0;
+        assert!(check_interception(&InterceptorState { inert: false, ..frozen }, &missile, 50.0));
+    }
+
+    #[test]
+    fn test_climbing_below_min_altitude_stays_active() {
+        let params = InterceptorParams {
+            min_engagement_altitude: Some(2000.0),
+            ..test_params(0.0, None)
+        };
+        let target = [5000.0, 0.0, 5000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        // 地上付近から上昇中は自爆しない
+        let state = InterceptorState {
+            velocity: [0.0, 0.0, 300.0],
+            ..test_state()
+        };
+        let state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        assert!(!state.inert);
+    }
+
+    #[test]
+    fn test_can_engage_in_envelope() {
+        let params = InterceptorParams {
+            alpha: 0.0001,
+            cd: 0.3,
+            area: 0.1,
+            ..test_params(3.0, None)
+        };
+        let state = InterceptorState {
+            launched: false,
+            thrust: 50000.0,
+            velocity: [0.0, 0.0, 0.0],
+            position: [0.0, 0.0, 0.0],
+            ..test_state()
+        };
+        // 推力重量比 ~10 → 20 s で 10 km 先には十分届く
+        assert!(can_engage(&state, &params, &[8000.0, 0.0, 6000.0], 20.0));
+    }
+
+    #[test]
+    fn test_can_engage_out_of_envelope() {
+        let params = InterceptorParams {
+            alpha: 0.0001,
+            cd: 0.3,
+            area: 0.1,
+            launch_delay: 2.0,
+            ..test_params(3.0, None)
+        };
+        let state = InterceptorState {
+            launched: false,
+            thrust: 50000.0,
+            velocity: [0.0, 0.0, 0.0],
+            position: [0.0, 0.0, 0.0],
+            ..test_state()
+        };
+        // 遠すぎる点、および発射遅れ内に迎撃が必要な点には届かない
+        assert!(!can_engage(&state, &params, &[300000.0, 0.0, 100000.0], 20.0));
+        assert!(!can_engage(&state, &params, &[100.0, 0.0, 100.0], 1.5));
+    }
+
+    /// 目標ミサイルの状態 (位置・速度のみ指定)
+    fn target_state(position: [f64; 3], velocity: [f64; 3]) -> MissileState {
+        MissileState {
+            mass: 100.0,
+            position,
+            velocity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_simultaneous_resolution_independent_of_order() {
+        let missiles = vec![target_state([0.0, 0.0, 1000.0], [0.0; 3])];
+        let near = InterceptorState { position: [10.0, 0.0, 1000.0], ..test_state() };
+        let far = InterceptorState { position: [30.0, 0.0, 1000.0], ..test_state() };
+        let targets = [Some(0), Some(0)];
+
+        let forward = resolve_interceptions(
+            &[far.clone(), near.clone()],
+            &targets,
+            &missiles,
+            50.0,
+            ResolutionMode::Simultaneous,
+        );
+        let reversed = resolve_interceptions(&[near, far], &targets, &missiles, 50.0, ResolutionMode::Simultaneous);
+
+        // どちらの順序でも近い方の迎撃ミサイルによる1件のみ
+        assert_eq!(forward, vec![Interception { interceptor: 1, missile: 0 }]);
+        assert_eq!(reversed, vec![Interception { interceptor: 0, missile: 0 }]);
+    }
+
+    #[test]
+    fn test_sequential_resolution_first_in_order_wins() {
+        let missiles = vec![target_state([0.0, 0.0, 1000.0], [0.0; 3])];
+        let near = InterceptorState { position: [10.0, 0.0, 1000.0], ..test_state() };
+        let far = InterceptorState { position: [30.0, 0.0, 1000.0], ..test_state() };
+
+        let result = resolve_interceptions(
+            &[far, near],
+            &[Some(0), Some(0)],
+            &missiles,
+            50.0,
+            ResolutionMode::Sequential,
+        );
+        assert_eq!(result, vec![Interception { interceptor: 0, missile: 0 }]);
+    }
+
+    #[test]
+    fn test_resolution_skips_destroyed_and_untargeted() {
+        let mut destroyed = target_state([0.0, 0.0, 1000.0], [0.0; 3]);
+        destroyed.active = false;
+        let missiles = vec![destroyed, target_state([0.0, 0.0, 1000.0], [0.0; 3])];
+        let interceptor = InterceptorState { position: [10.0, 0.0, 1000.0], ..test_state() };
+
+        let result = resolve_interceptions(
+            &[interceptor.clone(), interceptor],
+            &[Some(0), None],
+            &missiles,
+            50.0,
+            ResolutionMode::Simultaneous,
+        );
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_relative_geometry_crossing_has_los_rate() {
+        // 目標が視線に直交して横切る
+        let interceptor = InterceptorState {
+            position: [0.0, 0.0, 1000.0],
+            velocity: [0.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let missile = MissileState {
+            position: [1000.0, 0.0, 1000.0],
+            velocity: [0.0, 100.0, 0.0],
+            ..target_state([0.0, 0.0, 0.0], [0.0; 3])
+        };
+        let geom = relative_geometry(&interceptor, &missile);
+        assert_eq!(geom.range, 1000.0);
+        assert!((geom.los_rate - 0.1).abs() < 1e-12);
+        assert!(geom.closing_speed.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_relative_geometry_collision_course_has_zero_los_rate() {
+        // 一定方位で接近 (同一点へ向かう)
+        let interceptor = InterceptorState {
+            position: [0.0, 0.0, 1000.0],
+            velocity: [200.0, 200.0, 0.0],
+            ..test_state()
+        };
+        let missile = MissileState {
+            position: [2000.0, 0.0, 1000.0],
+            velocity: [0.0, 200.0, 0.0],
+            ..target_state([0.0, 0.0, 0.0], [0.0; 3])
+        };
+        let geom = relative_geometry(&interceptor, &missile);
+        assert!(geom.los_rate.abs() < 1e-12);
+        assert!((geom.closing_speed - 200.0).abs() < 1e-9);
+        assert_eq!(geom.los, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_launch_from_moving_platform_inherits_velocity() {
+        let ship = Launcher {
+            position: [0.0, 0.0, 0.0],
+            velocity: [15.0, 5.0, 0.0],
+        };
+        let state = InterceptorState {
+            launched: false,
+            velocity: [0.0, 0.0, 50.0],
+            ..test_state()
+        };
+
+        let launched = launch_interceptor(&state, Some(&ship));
+        assert!(launched.launched);
+        assert_eq!(launched.velocity, [15.0, 5.0, 50.0]);
+
+        // 固定発射機では発射速度のまま
+        assert_eq!(launch_interceptor(&state, None).velocity, [0.0, 0.0, 50.0]);
+    }
+
+    fn run_with_g_limit(n: f64) -> Interceptor {
+        let params = InterceptorParams {
+            g: 0.0,
+            g_limit: Some(GLimit {
+                max_acceleration: 100.0,
+                overload_time: 1.0,
+            }),
+            ..test_params(n, None)
+        };
+        let target = [5000.0, 5000.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut interceptor = Interceptor {
+            params,
+            state: test_state(),
+        };
+        for _ in 0..15 {
+            interceptor.state = update_interceptor(
+                &interceptor.params,
+                &interceptor.state,
+                &target, &[0.0; 3],
+                &mut integrators,
+                &mut filters,
+                0.1,
+            );
+        }
+        interceptor
+    }
+
+    #[test]
+    fn test_sustained_saturation_flags_homing_overload() {
+        // 指令 200 m/s^2 > 上限 100 m/s^2 が 1.5 s 継続
+        let interceptor = run_with_g_limit(200.0);
+        assert!(interceptor.state.saturation_time >= 1.0);
+        assert!(is_homing_saturated(&interceptor));
+    }
+
+    #[test]
+    fn test_benign_geometry_not_saturated() {
+        let interceptor = run_with_g_limit(10.0);
+        assert_eq!(interceptor.state.saturation_time, 0.0);
+        assert!(!is_homing_saturated(&interceptor));
+    }
+
+    #[test]
+    fn test_g_limit_clamps_command() {
+        let limit = GLimit {
+            max_acceleration: 100.0,
+            overload_time: 1.0,
+        };
+        let (a, t) = apply_g_limit(&[300.0, 400.0, 0.0], Some(&limit), 0.2, 0.1);
+        assert!((vector_norm(&a) - 100.0).abs() < 1e-9);
+        assert!((t - 0.3).abs() < 1e-12);
+        // 飽和が解消すればリセット
+        let (_, t) = apply_g_limit(&[30.0, 40.0, 0.0], Some(&limit), t, 0.1);
+        assert_eq!(t, 0.0);
+    }
+
+    #[test]
+    fn test_guidance_deadband_zeroes_small_commands() {
+        assert_eq!(apply_guidance_deadband(&[0.3, 0.4, 0.0], 1.0), [0.0, 0.0, 0.0]);
+        assert_eq!(apply_guidance_deadband(&[3.0, 4.0, 0.0], 1.0), [3.0, 4.0, 0.0]);
+        // 不感帯0では微小な指令もそのまま
+        assert_eq!(apply_guidance_deadband(&[1e-6, 0.0, 0.0], 0.0), [1e-6, 0.0, 0.0]);
+
+        // 追尾誘導の指令 |a| = 2 m/s^2 に対し、不感帯 5 では誘導なしと一致し、不感帯 1 では誘導される
+        let target = [0.0, 5000.0, 1000.0];
+        let step = |params: &InterceptorParams| {
+            update_interceptor(params, &test_state(), &target, &[0.0; 3], &mut new_integrators(), &mut new_filters(), 0.1)
+        };
+        let unguided = step(&test_params(0.0, None));
+        let suppressed = step(&InterceptorParams {
+            guidance_deadband: 5.0,
+            ..test_params(2.0, None)
+        });
+        let passed = step(&InterceptorParams {
+            guidance_deadband: 1.0,
+            ..test_params(2.0, None)
+        });
+        assert_eq!(suppressed.velocity, unguided.velocity);
+        assert!(passed.velocity[1] > unguided.velocity[1]);
+    }
+
+    #[test]
+    fn test_seeker_warmup_flies_ballistic_then_guides() {
+        let params = InterceptorParams {
+            launch_delay: 0.5,
+            seeker_warmup: 1.0,
+            ..test_params(3.0, None)
+        };
+        let target = [0.0, 5000.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = test_state();
+
+        // 発射遅れ (2ステップ) + シーカ捕捉時間 (4ステップ) は誘導なし
+        for _ in 0..6 {
+            assert!(!seeker_acquired(&params, state.time_since_launch));
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.25);
+            assert_eq!(state.guidance_command, None);
+            assert_eq!(state.velocity[1], 0.0);
+        }
+        assert_eq!(state.time_since_launch, 1.5);
+        // 運動開始後は弾道飛行している
+        assert!(state.position[0] > 0.0);
+
+        // 捕捉後は誘導開始
+        assert!(seeker_acquired(&params, state.time_since_launch));
+        state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.25);
+        let command = state.guidance_command.unwrap();
+        assert!(vector_norm(&command) > 0.0);
+        assert!(state.velocity[1] > 0.0);
+    }
+
+    #[test]
+    fn test_guidance_waits_for_min_control_speed() {
+        // 垂直発射 (推力加速度 50 m/s^2、重力込みで約 40 m/s^2)
+        let params = InterceptorParams {
+            thrust: 25000.0,
+            min_control_speed: 100.0,
+            ..test_params(3.0, None)
+        };
+        let target = [0.0, 5000.0, 5000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = InterceptorState {
+            thrust: 25000.0,
+            theta: std::f64::consts::FRAC_PI_2,
+            velocity: [0.0, 0.0, 0.0],
+            ..test_state()
+        };
+
+        // 閾値未満の間は誘導なしで加速する
+        let mut steps = 0;
+        while vector_norm(&state.velocity) < 100.0 {
+            assert!(!guidance_enabled(&params, &state));
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            assert_eq!(state.guidance_command, None);
+            assert_eq!(state.velocity[1], 0.0);
+            steps += 1;
+        }
+        // 約 2.5 s で 100 m/s に到達
+        assert!((24..=27).contains(&steps), "steps = {}", steps);
+
+        // 閾値を超えたら誘導開始
+        assert!(guidance_enabled(&params, &state));
+        state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        assert!(state.guidance_command.is_some());
+        assert!(state.velocity[1] > 0.0);
+    }
+
+    fn phased(blend_range: f64) -> PhasedGuidance {
+        PhasedGuidance {
+            midcourse: GuidanceLaw::Pursuit,
+            terminal: GuidanceLaw::ProportionalNavigation,
+            handover_range: 2000.0,
+            blend_range,
+            loft_altitude: None,
+        }
+    }
+
+    /// 目標 (原点から x 方向 5000 m) に向けて斜めに直進する迎撃ミサイルの各距離での指令
+    fn commands_along_approach(phased: &PhasedGuidance) -> Vec<(f64, [f64; 3])> {
+        let constants = GuidanceConstants { n: 3.0 };
+        let target = [5000.0, 0.0, 0.0];
+        let velocity = [1000.0, 50.0, 0.0];
+        (0..400)
+            .map(|k| {
+                let t = k as f64 * 0.01;
+                let state = InterceptorState {
+                    position: [velocity[0] * t, velocity[1] * t - 200.0, 0.0],
+                    velocity,
+                    ..test_state()
+                };
+                let range = vector_norm(&vector_sub(&target, &state.position));
+                (range, phased_guidance_command(phased, &state, &target, &[0.0; 3], &constants))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_phased_guidance_switches_at_handover_range() {
+        let phased = phased(0.0);
+        assert_eq!(active_guidance_law(&phased, 2500.0), GuidanceLaw::Pursuit);
+        assert_eq!(active_guidance_law(&phased, 2000.0), GuidanceLaw::ProportionalNavigation);
+
+        let constants = GuidanceConstants { n: 3.0 };
+        let target = [5000.0, 0.0, 0.0];
+        let far = InterceptorState {
+            position: [0.0, -200.0, 0.0],
+            velocity: [1000.0, 50.0, 0.0],
+            ..test_state()
+        };
+        // 中間誘導 (追尾) は視線方向、終末誘導 (比例航法) は視線に垂直
+        let a = phased_guidance_command(&phased, &far, &target, &[0.0; 3], &constants);
+        assert_eq!(a, guidance(&far, &target, &constants));
+        let near = InterceptorState {
+            position: [3500.0, -100.0, 0.0],
+            ..far.clone()
+        };
+        let a = phased_guidance_command(&phased, &near, &target, &[0.0; 3], &constants);
+        let los = relative_geometry_from(&near.position, &near.velocity, &target, &[0.0; 3]).los;
+        assert!((a[0] * los[0] + a[1] * los[1] + a[2] * los[2]).abs() < 1e-9);
+        assert!(vector_norm(&a) > 0.0);
+    }
+
+    #[test]
+    fn test_proportional_navigation_uses_target_velocity() {
+        // +x 方向に飛ぶ迎撃ミサイルの正面 3000 m を +y 方向に横切る目標
+        let constants = GuidanceConstants { n: 3.0 };
+        let state = InterceptorState {
+            position: [0.0, 0.0, 0.0],
+            velocity: [300.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let target = [3000.0, 0.0, 0.0];
+        let target_velocity = [0.0, 200.0, 0.0];
+        // 解析解: 視線角速度 λ' = v_t / R、接近速度 Vc = v_i、指令 a_y = N Vc λ'
+        let range = 3000.0;
+        let expected = [0.0, constants.n * 300.0 * (200.0 / range), 0.0];
+
+        let a = guidance_law_command(GuidanceLaw::ProportionalNavigation, &state, &target, &target_velocity, &constants);
+        assert!(vector_norm(&vector_sub(&a, &expected)) < 1e-9, "a = {:?}", a);
+        // 静止目標とみなすと視線は回転せず指令は 0 になる
+        let stationary = guidance_law_command(GuidanceLaw::ProportionalNavigation, &state, &target, &[0.0; 3], &constants);
+        assert!(vector_norm(&stationary) < 1e-9);
+
+        // 終末誘導区間の段階別誘導にも目標速度が渡る
+        let terminal = PhasedGuidance { handover_range: 5000.0, ..phased(0.0) };
+        let a = phased_guidance_command(&terminal, &state, &target, &target_velocity, &constants);
+        assert!(vector_norm(&vector_sub(&a, &expected)) < 1e-9, "a = {:?}", a);
+    }
+
+    #[test]
+    fn test_phased_guidance_blend_bounds_discontinuity() {
+        let max_jump = |commands: &[(f64, [f64; 3])]| {
+            commands
+                .windows(2)
+                .map(|w| vector_norm(&vector_sub(&w[1].1, &w[0].1)))
+                .fold(0.0, f64::max)
+        };
+        // 混合なしでは切り替え時に指令が跳ぶ (追尾 3 m/s^2 → 比例航法 約 40 m/s^2)
+        let abrupt = commands_along_approach(&phased(0.0));
+        assert!(abrupt.iter().any(|(r, _)| *r > 2000.0) && abrupt.iter().any(|(r, _)| *r < 2000.0));
+        assert!(max_jump(&abrupt) > 30.0, "jump {}", max_jump(&abrupt));
+
+        // 混合区間 500 m (約 50 ステップ) では 1 ステップあたりの変化が跳びの 1/10 未満
+        let blended = commands_along_approach(&phased(500.0));
+        assert!(max_jump(&blended) < 0.1 * max_jump(&abrupt), "jump {}", max_jump(&blended));
+    }
+
+    #[test]
+    fn test_overshoot_turns_back_toward_target() {
+        // 目標 (x=1000, y=50) を追い越して +x 方向へ遠ざかる迎撃ミサイル
+        let params = InterceptorParams {
+            g: 0.0,
+            phased_guidance: Some(phased(0.0)),
+            turn_around_acceleration: 50.0,
+            ..test_params(3.0, None)
+        };
+        let target = [1000.0, 50.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = InterceptorState {
+            position: [1100.0, 0.0, 1000.0],
+            velocity: [500.0, 0.0, 0.0],
+            ..test_state()
+        };
+        assert!(relative_geometry_from(&state.position, &state.velocity, &target, &[0.0; 3]).closing_speed < 0.0);
+
+        // 旋回中は常に同じ向き (目標側 +y へ左旋回) に一定の加速度で曲がり続け、指令が振動しない
+        let mut steps = 0;
+        while relative_geometry_from(&state.position, &state.velocity, &target, &[0.0; 3]).closing_speed < 0.0 {
+            let velocity = state.velocity;
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            let command = state.guidance_command.unwrap();
+            let turn_rate_sign = velocity[0] * command[1] - velocity[1] * command[0];
+            assert!(turn_rate_sign > 0.0, "command {:?}", command);
+            assert!((vector_norm(&command) - 50.0).abs() < 1e-9);
+            steps += 1;
+            assert!(steps < 500, "interceptor never turned around");
+        }
+        // 反転して目標方向へ戻っている
+        assert!(state.velocity[0] < 0.0);
+        assert!(relative_geometry_from(&state.position, &state.velocity, &target, &[0.0; 3]).closing_speed > 0.0);
+    }
+
+    #[test]
+    fn test_turn_around_when_target_directly_behind() {
+        let state = InterceptorState {
+            position: [1100.0, 0.0, 1000.0],
+            velocity: [500.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let command = turn_around_command(&state, &[1000.0, 0.0, 1000.0], 50.0);
+        assert_eq!(command, [0.0, 0.0, 50.0]);
+    }
+
+    #[test]
+    fn test_recommended_nav_constant_grows_with_difficulty() {
+        let base = recommended_nav_constant(1000.0, 0.0, 20.0, 10.0, None);
+        assert!(base >= MIN_NAV_CONSTANT);
+        // 機動が大きいほど、残り時間が短いほど大きな N
+        assert!(recommended_nav_constant(1000.0, 0.0, 40.0, 10.0, None) > base);
+        assert!(recommended_nav_constant(1000.0, 0.0, 20.0, 5.0, None) > base);
+        // 非機動目標は下限
+        assert_eq!(recommended_nav_constant(1000.0, 0.0, 0.0, 10.0, None), MIN_NAV_CONSTANT);
+        // 極端な条件でも上限で頭打ち
+        assert_eq!(recommended_nav_constant(1000.0, 0.0, 300.0, 0.5, None), MAX_NAV_CONSTANT);
+        assert_eq!(recommended_nav_constant(1000.0, 0.0, 20.0, 0.0, None), MAX_NAV_CONSTANT);
+    }
+
+    #[test]
+    fn test_recommended_nav_constant_respects_g_limit() {
+        let limit = GLimit {
+            max_acceleration: 50.0,
+            overload_time: 1.0,
+        };
+        // 制限なしでは上限 8、Vc λ̇ = 10 m/s^2 なので 50 m/s^2 に収まる N は 5
+        let (closing_speed, los_rate) = (1000.0, 0.01);
+        assert_eq!(recommended_nav_constant(closing_speed, los_rate, 300.0, 0.5, None), MAX_NAV_CONSTANT);
+        let n = recommended_nav_constant(closing_speed, los_rate, 300.0, 0.5, Some(&limit));
+        assert!((n - 5.0).abs() < 1e-12);
+        assert!(n * closing_speed * los_rate <= limit.max_acceleration + 1e-9);
+        // 余裕があれば制限しない
+        let relaxed = recommended_nav_constant(closing_speed, 0.001, 300.0, 0.5, Some(&limit));
+        assert_eq!(relaxed, MAX_NAV_CONSTANT);
+        // 視線が回転していなければ制限は効かない
+        assert_eq!(recommended_nav_constant(closing_speed, 0.0, 0.0, 10.0, Some(&limit)), MIN_NAV_CONSTANT);
+    }
+
+    #[test]
+    fn test_compute_pip_head_on_and_unreachable() {
+        let state = InterceptorState {
+            position: [0.0, 0.0, 0.0],
+            ..test_state()
+        };
+        // 正面から 100 m/s で接近する目標、迎撃側 400 m/s → 2 s 後に x=800 で会合
+        let target = target_state([1000.0, 0.0, 0.0], [-100.0, 0.0, 0.0]);
+        let pip = compute_pip(&state, &target, 400.0).unwrap();
+        assert!((pip[0] - 800.0).abs() < 1e-9);
+        // 遠ざかる目標より遅い場合は会合できない
+        let receding = target_state([1000.0, 0.0, 0.0], [500.0, 0.0, 0.0]);
+        assert!(compute_pip(&state, &receding, 400.0).is_none());
+    }
+
+    /// 等速で横切る目標に対する最小距離 [m]
+    fn crossing_miss_distance(aim_point: AimPoint) -> f64 {
+        let params = InterceptorParams {
+            g: 0.0,
+            aim_point,
+            ..test_params(100.0, None)
+        };
+        let mut state = InterceptorState {
+            velocity: [300.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let mut target = target_state([4000.0, -2000.0, 1000.0], [0.0, 400.0, 0.0]);
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let dt = 0.01;
+        let mut miss = f64::INFINITY;
+        for _ in 0..2000 {
+            let aim = guidance_target(&params, &state, &target, None);
+            state = update_interceptor(&params, &state, &aim, &[0.0; 3], &mut integrators, &mut filters, dt);
+            for k in 0..3 {
+                target.position[k] += target.velocity[k] * dt;
+            }
+            miss = miss.min(vector_norm(&vector_sub(&target.position, &state.position)));
+        }
+        miss
+    }
+
+    #[test]
+    fn test_pip_guidance_reduces_miss_against_target_state() {
+        let pursuit_miss = crossing_miss_distance(AimPoint::Target);
+        let pip_miss = crossing_miss_distance(AimPoint::PredictedInterceptPoint);
+        assert!(pip_miss < pursuit_miss, "PIP {} / pursuit {}", pip_miss, pursuit_miss);
+    }
+
+    #[test]
+    fn test_guidance_target_extrapolates_measurements_only() {
+        let params = test_params(100.0, None);
+        let state = test_state();
+        let measured = target_state([4000.0, -2000.0, 1000.0], [0.0, 400.0, 0.0]);
+        // 計測は経過時間だけ外挿し、真値は外挿しない
+        assert_eq!(guidance_target(&params, &state, &measured, Some(0.5)), [4000.0, -1800.0, 1000.0]);
+        assert_eq!(guidance_target(&params, &state, &measured, None), measured.position);
+        // 外挿時間を指定した場合は経過時間によらずその時間だけ外挿する
+        let fixed = InterceptorParams {
+            target_extrapolation_time: Some(0.0),
+            ..params
+        };
+        assert_eq!(guidance_target(&fixed, &state, &measured, Some(0.5)), measured.position);
+    }
+
+    #[test]
+    fn test_fuze_detonates_at_range_rate_sign_flip() {
+        // x = -100 から +100 へ通過 (y = 10): 中点で接近から離隔へ変わる
+        let s = fuze_detonation(1.0, 5.0, &[-100.0, 10.0, 0.0], &[100.0, 10.0, 0.0], 0.1).unwrap();
+        assert!((s - 0.5).abs() < 1e-12);
+        // 接近中のみ・離隔中のみのステップでは起爆しない
+        assert_eq!(fuze_detonation(1.0, 5.0, &[-300.0, 10.0, 0.0], &[-100.0, 10.0, 0.0], 0.1), None);
+        assert_eq!(fuze_detonation(1.0, 5.0, &[100.0, 10.0, 0.0], &[300.0, 10.0, 0.0], 0.1), None);
+        // 最接近時刻 (ステップ終了の 0.05 s 前) に安全解除されていなければ起爆しない
+        assert_eq!(fuze_detonation(1.0, 1.04, &[-100.0, 10.0, 0.0], &[100.0, 10.0, 0.0], 0.1), None);
+        assert!(fuze_detonation(1.0, 1.05, &[-100.0, 10.0, 0.0], &[100.0, 10.0, 0.0], 0.1).is_some());
+    }
+
+    #[test]
+    fn test_first_contact_fraction() {
+        // x = -100 から +100 へ通過、半径 50 には 1/4 の時点で入る
+        let s = first_contact_fraction(&[-100.0, 0.0, 0.0], &[100.0, 0.0, 0.0], 50.0).unwrap();
+        assert!((s - 0.25).abs() < 1e-12);
+        assert_eq!(first_contact_fraction(&[10.0, 0.0, 0.0], &[100.0, 0.0, 0.0], 50.0), Some(0.0));
+        // 横にずれて通過する / 届かない
+        assert_eq!(first_contact_fraction(&[-100.0, 60.0, 0.0], &[100.0, 60.0, 0.0], 50.0), None);
+        assert_eq!(first_contact_fraction(&[-200.0, 0.0, 0.0], &[-100.0, 0.0, 0.0], 50.0), None);
+    }
+
+    #[test]
+    fn test_impact_precedence_rules() {
+        assert!(!ImpactPrecedence::Impact.intercept_wins(0.05, 0.01));
+        assert!(ImpactPrecedence::Intercept.intercept_wins(0.01, 0.05));
+        assert!(ImpactPrecedence::Earliest.intercept_wins(0.05, 0.01));
+        assert!(!ImpactPrecedence::Earliest.intercept_wins(0.01, 0.05));
+        assert!(!ImpactPrecedence::Earliest.intercept_wins(0.05, 0.05));
+    }
+
+    #[test]
+    fn test_closest_approach_within_step() {
+        // y = 30 m の直線上を x = -100 → +100 と通過: 中点で 30 m
+        let (s, distance) = closest_approach(&[-100.0, 30.0, 0.0], &[100.0, 30.0, 0.0]);
+        assert!((s - 0.5).abs() < 1e-12);
+        assert!((distance - 30.0).abs() < 1e-12);
+        // 遠ざかる場合はステップ開始時点
+        assert_eq!(closest_approach(&[10.0, 0.0, 0.0], &[20.0, 0.0, 0.0]), (0.0, 10.0));
+        assert_eq!(closest_approach(&[0.0, 5.0, 0.0], &[0.0, 5.0, 0.0]), (0.0, 5.0));
+    }
+
+    /// 高度 1 km から高度 10 km の静止目標へ向けて直進方向に発射した迎撃ミサイルの軌跡と最小距離
+    fn loft_engagement(loft_altitude: Option<f64>) -> (Vec<[f64; 3]>, f64) {
+        let params = InterceptorParams {
+            g: 0.0,
+            phased_guidance: Some(PhasedGuidance {
+                midcourse: GuidanceLaw::Pursuit,
+                terminal: GuidanceLaw::ProportionalNavigation,
+                handover_range: 6000.0,
+                blend_range: 0.0,
+                loft_altitude,
+            }),
+            ..test_params(4.0, None)
+        };
+        let target = [20000.0, 0.0, 10000.0];
+        let mut state = InterceptorState {
+            position: [0.0, 0.0, 1000.0],
+            velocity: [1000.0 * 20.0 / 409f64.sqrt(), 0.0, 1000.0 * 9.0 / 409f64.sqrt()],
+            ..test_state()
+        };
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut path = vec![state.position];
+        let mut min_range = f64::INFINITY;
+        for _ in 0..400 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.05);
+            path.push(state.position);
+            min_range = min_range.min(vector_norm(&vector_sub(&target, &state.position)));
+        }
+        (path, min_range)
+    }
+
+    #[test]
+    fn test_loft_climbs_above_direct_line_then_homes() {
+        // 発射点と目標を結ぶ直線の高度 (x に対する一次式) からの高さ
+        let above_line = |p: &[f64; 3]| p[2] - (1000.0 + 9000.0 * p[0] / 20000.0);
+        let (direct, direct_miss) = loft_engagement(None);
+        let (lofted, lofted_miss) = loft_engagement(Some(15000.0));
+
+        // 中間誘導中 (目標まで handover_range より遠い区間) の直線からの最大の高さ
+        let max_above = |path: &[[f64; 3]]| {
+            path.iter()
+                .filter(|p| vector_norm(&vector_sub(&[20000.0, 0.0, 10000.0], p)) > 6000.0)
+                .map(above_line)
+                .fold(f64::MIN, f64::max)
+        };
+        assert!(max_above(&direct) < 1.0, "direct {}", max_above(&direct));
+        assert!(max_above(&lofted) > 100.0, "lofted {}", max_above(&lofted));
+        // ロフトしても終末誘導で目標に収束する
+        assert!(direct_miss < 50.0, "direct miss {}", direct_miss);
+        assert!(lofted_miss < 50.0, "lofted miss {}", lofted_miss);
+    }
+
+    #[test]
+    fn test_nav_schedule_interpolates_over_time_to_go() {
+        let schedule = NavConstantSchedule {
+            points: vec![(1.0, 6.0), (3.0, 4.0), (5.0, 3.0)],
+        };
+        assert_eq!(schedule.nav_constant(0.2), Some(6.0));
+        assert_eq!(schedule.nav_constant(2.0), Some(5.0));
+        assert_eq!(schedule.nav_constant(4.0), Some(3.5));
+        assert_eq!(schedule.nav_constant(f64::INFINITY), Some(3.0));
+        assert_eq!(NavConstantSchedule { points: vec![] }.nav_constant(1.0), None);
+    }
+
+    /// 比例航法で横行目標を追う交戦の (最小距離, 誘導加速度の最大値)
+    fn scheduled_pn_engagement(nav_schedule: Option<NavConstantSchedule>) -> (f64, f64) {
+        let params = InterceptorParams {
+            g: 0.0,
+            phased_guidance: Some(PhasedGuidance {
+                midcourse: GuidanceLaw::ProportionalNavigation,
+                terminal: GuidanceLaw::ProportionalNavigation,
+                handover_range: 0.0,
+                blend_range: 0.0,
+                loft_altitude: None,
+            }),
+            g_limit: Some(GLimit {
+                max_acceleration: 300.0,
+                overload_time: 1.0,
+            }),
+            nav_schedule,
+            ..test_params(3.0, None)
+        };
+        let mut state = InterceptorState {
+            velocity: [600.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let mut target = target_state([6000.0, -1500.0, 1000.0], [0.0, 100.0, 0.0]);
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let dt = 0.01;
+        let (mut miss, mut max_command) = (f64::INFINITY, 0.0f64);
+        for _ in 0..2000 {
+            let rel_start = vector_sub(&target.position, &state.position);
+            state = update_interceptor(&params, &state, &target.position, &[0.0; 3], &mut integrators, &mut filters, dt);
+            max_command = max_command.max(state.guidance_command.map_or(0.0, |a| vector_norm(&a)));
+            // 目標は y 方向に 20 m/s^2 で加速しながら横行する
+            target.velocity[1] += 20.0 * dt;
+            for k in 0..3 {
+                target.position[k] += target.velocity[k] * dt;
+            }
+            let rel_end = vector_sub(&target.position, &state.position);
+            miss = miss.min(closest_approach(&rel_start, &rel_end).1);
+        }
+        (miss, max_command)
+    }
+
+    #[test]
+    fn test_nav_schedule_sharpens_terminal_homing_within_g_limit() {
+        let (constant_miss, _) = scheduled_pn_engagement(None);
+        let (scheduled_miss, max_command) = scheduled_pn_engagement(Some(NavConstantSchedule {
+            points: vec![(1.0, 6.0), (4.0, 4.0), (8.0, 3.0)],
+        }));
+        // 一定の N = 3 では終末で加速する目標に追従しきれない
+        assert!(scheduled_miss < constant_miss, "scheduled {} / constant {}", scheduled_miss, constant_miss);
+        assert!(scheduled_miss < 1.0);
+        assert!(max_command <= 300.0 + 1e-9);
+    }
+
+    #[test]
+    fn test_guidance_frame_round_trip_is_consistent() {
+        let velocity = [300.0, 400.0, 100.0];
+        let a = [12.0, -30.0, 45.0];
+        for frame in [GuidanceFrame::Inertial, GuidanceFrame::Velocity] {
+            let components = to_guidance_frame(frame, &velocity, &a);
+            let back = from_guidance_frame(frame, &velocity, &components);
+            for k in 0..3 {
+                assert!((back[k] - a[k]).abs() < 1e-9, "{:?}: {:?}", frame, back);
+            }
+            // 変換で大きさは変わらない
+            assert!((vector_norm(&components) - vector_norm(&a)).abs() < 1e-9);
+        }
+        // 速度方向成分は速度ベクトルへの射影
+        let along = to_guidance_frame(GuidanceFrame::Velocity, &velocity, &a)[0];
+        assert!((along - dot(&a, &velocity) / vector_norm(&velocity)).abs() < 1e-9);
+
+        // 制限なしでは座標系によらず同じ運動になる
+        let target = [0.0, 5000.0, 1000.0];
+        let step = |guidance_frame: GuidanceFrame| {
+            let params = InterceptorParams {
+                guidance_frame,
+                ..test_params(50.0, None)
+            };
+            update_interceptor(&params, &test_state(), &target, &[0.0; 3], &mut new_integrators(), &mut new_filters(), 0.1)
+        };
+        assert_eq!(step(GuidanceFrame::Inertial).velocity, step(GuidanceFrame::Velocity).velocity);
+    }
+
+    #[test]
+    fn test_channel_limits_apply_in_chosen_frame() {
+        // 速度 +x で +y 方向に飛ぶ: 速度座標系では横方向 (左 = +y) の成分
+        let velocity = [100.0, 0.0, 0.0];
+        let a = [30.0, 40.0, -20.0];
+        let limits = [5.0, 10.0, 100.0];
+        let inertial = apply_channel_limits(&a, GuidanceFrame::Inertial, &velocity, Some(&limits));
+        assert_eq!(inertial, [5.0, 10.0, -20.0]);
+        // 速度座標系の軸は (速度方向 +x, 横方向 +y, 法線方向 +z) で、同じ制限が同じ軸に掛かる
+        assert_eq!(to_guidance_frame(GuidanceFrame::Velocity, &velocity, &[0.0, 1.0, 0.0]), [0.0, 1.0, 0.0]);
+        let velocity_frame = apply_channel_limits(&a, GuidanceFrame::Velocity, &velocity, Some(&limits));
+        for k in 0..3 {
+            assert!((velocity_frame[k] - inertial[k]).abs() < 1e-9, "{:?}", velocity_frame);
+        }
+
+        // 斜めに上昇中の速度座標系: 速度方向の加速を禁止すると指令は速度に垂直になる
+        let climbing = [100.0, 0.0, 100.0];
+        let limited = apply_channel_limits(&a, GuidanceFrame::Velocity, &climbing, Some(&[0.0, 1000.0, 1000.0]));
+        assert!(dot(&limited, &climbing).abs() < 1e-9);
+        let components = to_guidance_frame(GuidanceFrame::Velocity, &climbing, &a);
+        let limited_components = to_guidance_frame(GuidanceFrame::Velocity, &climbing, &limited);
+        assert!((limited_components[1] - components[1]).abs() < 1e-9);
+        assert!((limited_components[2] - components[2]).abs() < 1e-9);
+        assert_eq!(apply_channel_limits(&a, GuidanceFrame::Velocity, &climbing, None), a);
+    }
+
+    /// 硬い比例航法 (大きな N) で静止目標へ向かうときの横方向速度の列
+    fn stiff_engagement_vy(integrator_damping: f64) -> (Vec<f64>, InterceptorState) {
+        let params = InterceptorParams {
+            phased_guidance: Some(PhasedGuidance {
+                midcourse: GuidanceLaw::ProportionalNavigation,
+                terminal: GuidanceLaw::ProportionalNavigation,
+                blend_range: 0.0,
+                handover_range: 0.0,
+                loft_altitude: None,
+            }),
+            integrator_damping,
+            ..test_params(160.0, None)
+        };
+        let target = [6000.0, 600.0, 1000.0];
+        let mut integrators = new_interceptor_integrators(&params);
+        let mut filters = new_filters();
+        let mut state = InterceptorState {
+            velocity: [300.0, 0.0, 0.0],
+            position: [0.0, 0.0, 1000.0],
+            ..test_state()
+        };
+        let mut vy = vec![state.velocity[1]];
+        for _ in 0..50 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            vy.push(state.velocity[1]);
+        }
+        (vy, state)
+    }
+
+    #[test]
+    fn test_integrator_damping_reduces_velocity_ringing() {
+        // 隣接ステップ間の振動成分 (2階差分) の最大値
+        let ringing = |vy: &[f64]| vy.windows(3).map(|w| (w[2] - 2.0 * w[1] + w[0]).abs()).skip(1).fold(0.0, f64::max);
+        let (plain, plain_state) = stiff_engagement_vy(0.0);
+        let (damped, damped_state) = stiff_engagement_vy(1.0);
+        assert!(ringing(&damped) < 0.2 * ringing(&plain), "{} {}", ringing(&damped), ringing(&plain));
+        // 平均的な軌道は変わらない
+        let mean = |vy: &[f64]| vy[vy.len() - 20..].iter().sum::<f64>() / 20.0;
+        assert!((mean(&plain) - mean(&damped)).abs() < 0.1);
+        for k in 0..3 {
+            assert!((plain_state.position[k] - damped_state.position[k]).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_command_filter_lags_and_passes_through_without_time_constant() {
+        let filter = CommandFilter { time_constant: 0.5 };
+        // 1ステップで (1 - e^{-0.2}) だけ指令に近づく
+        let x = apply_command_filter(&[100.0, 0.0, 0.0], Some(&filter), &[0.0, 0.0, 0.0], 0.1);
+        assert!((x[0] - 100.0 * (1.0 - (-0.2_f64).exp())).abs() < 1e-9);
+        // フィルタなし・時定数0では指令そのまま
+        assert_eq!(apply_command_filter(&[1.0, 2.0, 3.0], None, &x, 0.1), [1.0, 2.0, 3.0]);
+        let instant = CommandFilter { time_constant: 0.0 };
+        assert_eq!(apply_command_filter(&[1.0, 2.0, 3.0], Some(&instant), &x, 0.1), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_command_filter_anti_windup_recovers_after_saturation() {
+        // 指令 1000 m/s^2 (上限 100 m/s^2) で 3 s 飽和させた後、指令を 50 m/s^2 に下げる
+        let params = InterceptorParams {
+            g: 0.0,
+            g_limit: Some(GLimit {
+                max_acceleration: 100.0,
+                overload_time: 10.0,
+            }),
+            command_filter: Some(CommandFilter { time_constant: 0.5 }),
+            ..test_params(3.0, None)
+        };
+        let target = [5000.0, 0.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = test_state();
+        let mut recovery = Vec::new();
+        for step in 0..50 {
+            let command = if step < 30 { 1000.0 } else { 50.0 };
+            state.guidance_override = Some([0.0, command, 0.0]);
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+            // 内部状態は常に上限で制限した指令以下
+            assert!(vector_norm(&state.filtered_command) <= 100.0 + 1e-9);
+            if step >= 30 {
+                recovery.push(state.guidance_command.unwrap()[1]);
+            }
+        }
+        // 飽和解消直後から 50 m/s^2 へ単調に近づく
+        assert!(recovery[0] < 100.0);
+        assert!(recovery.iter().all(|&a| (50.0..=100.0).contains(&a)));
+        assert!(recovery.windows(2).all(|w| w[1] <= w[0]));
+        assert!((recovery[14] - 50.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_turn_around_disabled_by_default() {
+        let params: InterceptorParams = serde_yaml::from_str(
+            "alpha: 0.0\ncd: 0.0\narea: 0.0\ng: 0.0\nthrust: 0.0\nalpha_filter: 1.0\n\
+             guidance_constants: { n: 3.0 }\ndivert_budget: null\ndivert_thruster: null\n\
+             guidance_rate_hz: null\nmin_engagement_altitude: null\ng_limit: null\nphased_guidance: null\n",
+        )
+        .unwrap();
+        assert_eq!(params.turn_around_acceleration, 0.0);
+
+        // 目標を追い越しても旋回せず、誘導則 (追尾誘導: 視線方向に N) を適用する
+        let state = InterceptorState {
+            position: [1100.0, 0.0, 1000.0],
+            velocity: [500.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let command = fresh_guidance_command(&params, &state, &[1000.0, 0.0, 1000.0], &[0.0; 3]);
+        assert_eq!(command, [-3.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_turn_around_uses_relative_velocity() {
+        let params = InterceptorParams {
+            turn_around_acceleration: 50.0,
+            ..test_params(3.0, None)
+        };
+        // 目標は後方 100 m にあるが、迎撃ミサイルより速く追いついてくる (相対的には接近中)
+        let state = InterceptorState {
+            position: [1100.0, 0.0, 1000.0],
+            velocity: [500.0, 0.0, 0.0],
+            ..test_state()
+        };
+        let target = [1000.0, 0.0, 1000.0];
+        let command = fresh_guidance_command(&params, &state, &target, &[800.0, 0.0, 0.0]);
+        assert_eq!(command, [-3.0, 0.0, 0.0]);
+        // 静止目標なら遠ざかっているので旋回する
+        let command = fresh_guidance_command(&params, &state, &target, &[0.0; 3]);
+        assert_eq!(command, [0.0, 0.0, 50.0]);
+    }
+
+    #[test]
+    fn test_nav_schedule_rejects_unsorted_points() {
+        let sorted: NavConstantSchedule = serde_yaml::from_str("points: [[1.0, 3.0], [10.0, 5.0]]").unwrap();
+        assert_eq!(sorted.points, vec![(1.0, 3.0), (10.0, 5.0)]);
+        assert!(serde_yaml::from_str::<NavConstantSchedule>("points: [[10.0, 5.0], [1.0, 3.0]]").is_err());
+        assert!(serde_yaml::from_str::<NavConstantSchedule>("points: [[1.0, 3.0], [1.0, 5.0]]").is_err());
+        assert!(serde_yaml::from_str::<NavConstantSchedule>("points: [[1.0, .nan]]").is_err());
+    }
+
+    #[test]
+    fn test_nav_schedule_time_to_go_uses_relative_velocity() {
+        let params = InterceptorParams {
+            nav_schedule: Some(NavConstantSchedule {
+                points: vec![(1.0, 3.0), (10.0, 5.0)],
+            }),
+            ..test_params(0.0, None)
+        };
+        let state = InterceptorState {
+            position: [0.0, 0.0, 1000.0],
+            velocity: [500.0, 0.0, 0.0],
+            ..test_state()
+        };
+        // 目標も 500 m/s で接近: 接近速度 1000 m/s、残り時間 1 s → N = 3 (静止目標とみなすと 2 s)
+        let command = fresh_guidance_command(&params, &state, &[1000.0, 0.0, 1000.0], &[-500.0, 0.0, 0.0]);
+        assert_eq!(command, [3.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_integrator_damping_and_alpha_filter_are_validated_on_load() {
+        let yaml = |damping: &str| {
+            format!(
+                "alpha: 0.0\ncd: 0.0\narea: 0.0\ng: 0.0\nthrust: 0.0\nalpha_filter: 1.0\n\
+                 guidance_constants: {{ n: 3.0 }}\ndivert_budget: null\ndivert_thruster: null\n\
+                 guidance_rate_hz: null\nmin_engagement_altitude: null\ng_limit: null\nphased_guidance: null\n\
+                 integrator_damping: {}\n",
+                damping
+            )
+        };
+        let params: InterceptorParams = serde_yaml::from_str(&yaml("0.5")).unwrap();
+        assert_eq!(params.integrator_damping, 0.5);
+        for invalid in ["1.0", "-0.1", ".nan"] {
+            assert!(serde_yaml::from_str::<InterceptorParams>(&yaml(invalid)).is_err(), "{}", invalid);
+        }
+        // alpha_filter も [0, 1] の範囲外・NaN はエラー
+        for invalid in ["1.5", "-0.1", ".nan"] {
+            let alpha_filter = yaml("0.5").replace("alpha_filter: 1.0", &format!("alpha_filter: {}", invalid));
+            assert!(serde_yaml::from_str::<InterceptorParams>(&alpha_filter).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_horizontal_gravity_deflects_coasting_interceptor() {
+        // 誘導・推力・抗力なしで、重力だけが +y 方向に働く
+        let params = InterceptorParams {
+            gravity_vector: Some([0.0, 3.0, 0.0]),
+            ..test_params(0.0, None)
+        };
+        let target = [5000.0, 0.0, 1000.0];
+        let mut integrators = new_integrators();
+        let mut filters = new_filters();
+        let mut state = test_state();
+        for _ in 0..100 {
+            state = update_interceptor(&params, &state, &target, &[0.0; 3], &mut integrators, &mut filters, 0.1);
+        }
+        // 10 s で +y 方向に 30 m/s、変位は約 0.5 * 3 * 10^2 = 150 m。鉛直方向は変化しない
+        assert!((state.velocity[1] - 30.0).abs() < 0.3, "vy {}", state.velocity[1]);
+        assert!((state.position[1] - 150.0).abs() < 5.0, "y {}", state.position[1]);
+        assert!((state.velocity[0] - 100.0).abs() < 1e-9);
+        assert_eq!(state.velocity[2], 0.0);
+        assert_eq!(state.position[2], 1000.0);
+    }
+}
@@ -1,9 +1,12 @@
 use serde_derive::Deserialize;
 use crate::math::integrator::AdamsBashforthIntegrator;
-use crate::math::low_pass_filter::LowPassFilter;
+use crate::math::low_pass_filter::{validate_alpha, LowPassFilter};
+use crate::models::force::{default_force_models, net_force, ForceEnvironment, ForceModel};
+use crate::models::gravity::GravityModel;
 
-/// ミサイルのパラメータ
+/// ミサイルのパラメータ (Default は推力・抗力・重力なし、フィルタなし。テストでは必要なフィールドだけを指定して使う)
 #[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "MissileParamsConfig")]
 pub struct MissileParams {
     pub alpha: f64,        // 燃料消費率係数
     pub cd: f64,           // 空気抵抗係数
@@ -11,7 +14,148 @@ pub struct MissileParams {
     pub rho0: f64,         // 大気密度の基準値 [kg/m^3]
     pub h: f64,            // 大気密度のスケール高度 [m]
     pub g: f64,            // 重力加速度 [m/s^2]
+    pub gravity_vector: Option<[f64; 3]>, // 重力加速度ベクトル [m/s^2] (None: -z 方向に g)
     pub alpha_filter: f64, // ローパスフィルタalpha
+    pub gravity_turn: Option<GravityTurn>, // 重力ターン発射プログラム (None: 姿勢固定)
+    pub cruise_speed_hold: Option<CruiseSpeedHold>, // 巡航速度保持 (None: 常に推力あり)
+    pub density_floor: f64, // これ未満の大気密度は 0 (真空) とみなす [kg/m^3]
+    pub midpoint_density: bool, // true: 抗力の大気密度をステップ中点の予測高度で評価する
+    pub ignition_time: f64, // 点火から公称推力に達するまでの立ち上がり時間 [s] (0: 瞬時)
+    pub dart: bool, // true: 推力・燃料消費のない無動力の子弾 (抗力と重力のみ)
+}
+
+/// 設定ファイル上のミサイルパラメータ
+/// 断面積は area を直接指定するか、直径 diameter から area = pi*(d/2)^2 として求める。
+/// dart: true (無動力の子弾) の場合は燃料消費率係数 alpha を省略でき、0 とする
+#[derive(Clone, Debug, Deserialize)]
+struct MissileParamsConfig {
+    alpha: Option<f64>,
+    cd: f64,
+    area: Option<f64>,
+    diameter: Option<f64>,
+    rho0: f64,
+    h: f64,
+    g: f64,
+    #[serde(default)]
+    gravity_vector: Option<[f64; 3]>,
+    alpha_filter: f64,
+    gravity_turn: Option<GravityTurn>,
+    cruise_speed_hold: Option<CruiseSpeedHold>,
+    #[serde(default = "default_density_floor")]
+    density_floor: f64,
+    #[serde(default)]
+    midpoint_density: bool,
+    #[serde(default)]
+    ignition_time: f64,
+    #[serde(default)]
+    dart: bool,
+}
+
+fn default_density_floor() -> f64 {
+    VACUUM_DENSITY_THRESHOLD
+}
+
+/// 燃料消費・抗力・重力のないパラメータ
+/// alpha_filter は検証を通る 1 (フィルタなし)、設定ファイルで省略できる項目は設定ファイルと同じ既定値
+impl Default for MissileParams {
+    fn default() -> Self {
+        MissileParams {
+            alpha: 0.0,
+            cd: 0.0,
+            area: 0.0,
+            rho0: 0.0,
+            h: 0.0,
+            g: 0.0,
+            gravity_vector: None,
+            alpha_filter: 1.0,
+            gravity_turn: None,
+            cruise_speed_hold: None,
+            density_floor: default_density_floor(),
+            midpoint_density: false,
+            ignition_time: 0.0,
+            dart: false,
+        }
+    }
+}
+
+/// 直径から断面積を計算
+pub fn area_from_diameter(diameter: f64) -> f64 {
+    std::f64::consts::PI * (diameter / 2.0).powi(2)
+}
+
+/// area と diameter から断面積を決定する
+/// 両方指定された場合は相対誤差 1e-6 以内で一致することを要求する
+fn resolve_area(area: Option<f64>, diameter: Option<f64>) -> Result<f64, String> {
+    match (area, diameter) {
+        (Some(area), None) => Ok(area),
+        (None, Some(diameter)) => Ok(area_from_diameter(diameter)),
+        (Some(area), Some(diameter)) => {
+            let derived = area_from_diameter(diameter);
+            if (area - derived).abs() <= 1e-6 * derived.abs().max(area.abs()) {
+                Ok(area)
+            } else {
+                Err(format!(
+                    "area ({}) と diameter ({}) から求めた断面積 ({}) が一致しません",
+                    area, diameter, derived
+                ))
+            }
+        }
+        (None, None) => Err("area または diameter のいずれかを指定してください".to_string()),
+    }
+}
+
+impl MissileParams {
+    /// 重力モデル
+    pub fn gravity(&self) -> GravityModel {
+        GravityModel::from_params(self.g, self.gravity_vector)
+    }
+}
+
+impl TryFrom<MissileParamsConfig> for MissileParams {
+    type Error = String;
+
+    fn try_from(config: MissileParamsConfig) -> Result<Self, Self::Error> {
+        let alpha = match (config.alpha, config.dart) {
+            (Some(alpha), _) => alpha,
+            (None, true) => 0.0,
+            (None, false) => return Err("alpha (燃料消費率係数) を指定してください".to_string()),
+        };
+        Ok(MissileParams {
+            alpha,
+            cd: config.cd,
+            area: resolve_area(config.area, config.diameter)?,
+            rho0: config.rho0,
+            h: config.h,
+            g: config.g,
+            gravity_vector: config.gravity_vector,
+            alpha_filter: validate_alpha(config.alpha_filter).map_err(|e| e.to_string())?,
+            gravity_turn: config.gravity_turn,
+            cruise_speed_hold: config.cruise_speed_hold,
+            density_floor: config.density_floor,
+            midpoint_density: config.midpoint_density,
+            ignition_time: config.ignition_time,
+            dart: config.dart,
+        })
+    }
+}
+
+/// 重力ターンによる上昇プログラム
+/// 発射直後は垂直に保持し、ピッチオーバー後は速度ベクトルに沿って推力を向ける
+#[derive(Clone, Debug, Deserialize)]
+pub struct GravityTurn {
+    pub pitch_over_time: f64,  // ピッチオーバー開始時刻 [s]
+    pub pitch_over_rate: f64,  // ピッチオーバー角速度 [rad/s]
+    pub pitch_over_angle: f64, // ピッチオーバーで傾ける角度 [rad]
+    #[serde(default)]
+    pub min_thrust_to_weight: Option<f64>, // ピッチオーバーに必要な推力重量比 (None: pitch_over_time で開始)
+}
+
+/// 巡航速度保持 (簡易オートスロットル)
+/// 速度が target_speed + band を超えたら推力を止め、target_speed - band を下回ったら再点火する
+#[derive(Clone, Debug, Deserialize)]
+pub struct CruiseSpeedHold {
+    pub target_speed: f64, // 目標速度 [m/s]
+    pub band: f64,         // 目標速度からの許容幅 [m/s]
 }
 
 /// ミサイルの動的状態
@@ -23,6 +167,39 @@ pub struct MissileState {
     pub psi: f64,
     pub position: [f64; 3],
     pub velocity: [f64; 3],
+    #[serde(default)]
+    pub flight_time: f64, // 発射からの経過時間 [s]
+    #[serde(default = "default_active")]
+    pub active: bool, // false: 着弾または迎撃済みで運動更新しない
+    #[serde(default)]
+    pub thrust_cutoff: bool, // true: 巡航速度保持により推力停止中
+    #[serde(default)]
+    pub intercepted: bool, // true: 迎撃により撃破された (地表着弾ではない)
+    #[serde(default)]
+    pub pitch_over_start: Option<f64>, // 重力ターンのピッチオーバーを開始した経過時間 [s] (None: 未開始)
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// 原点に静止した飛翔中 (active) の質量 1 kg の状態 (質量0では加速度が発散するため)
+impl Default for MissileState {
+    fn default() -> Self {
+        MissileState {
+            mass: 1.0,
+            thrust: 0.0,
+            theta: 0.0,
+            psi: 0.0,
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            flight_time: 0.0,
+            active: default_active(),
+            thrust_cutoff: false,
+            intercepted: false,
+            pitch_over_start: None,
+        }
+    }
 }
 
 /// ミサイル本体 (パラメータ & 状態)
@@ -32,57 +209,148 @@ pub struct Missile {
     pub state: MissileState,
 }
 
+impl Missile {
+    /// 運動エネルギー 0.5 * m * |v|^2 [J]
+    pub fn kinetic_energy(&self) -> f64 {
+        let speed = vector_norm(&self.state.velocity);
+        0.5 * self.state.mass * speed * speed
+    }
+}
+
 /// ベクトル演算用ヘルパー関数
 fn vector_norm(v: &[f64; 3]) -> f64 {
     (v[0]*v[0] + v[1]*v[1] + v[2]*v[2]).sqrt()
 }
 
-/// 加速度計算
-fn calculate_acceleration(params: &MissileParams, state: &MissileState) -> [f64; 3] {
-    // 大気密度
-    let rho = params.rho0 * (-state.position[2] / params.h).exp();
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// 推力重量比 T / (m |g|) (推力は点火の立ち上がりを考慮した値、重力は gravity_vector を含む)。重量が0以下なら無限大
+pub fn thrust_to_weight(params: &MissileParams, state: &MissileState) -> f64 {
+    let weight = state.mass * vector_norm(&params.gravity().acceleration);
+    if weight <= 0.0 {
+        f64::INFINITY
+    } else {
+        thrust_at(params, state) / weight
+    }
+}
 
-    // 速度ノルム
+/// ピッチオーバーの開始時刻 [s] (未開始なら None)
+/// 推力重量比の条件がなければ pitch_over_time。条件がある場合は、pitch_over_time 以降で
+/// 推力重量比が min_thrust_to_weight 以上となった最初の時刻 (それまでは垂直に保持する)
+pub fn pitch_over_start(program: &GravityTurn, params: &MissileParams, state: &MissileState) -> Option<f64> {
+    if state.pitch_over_start.is_some() {
+        return state.pitch_over_start;
+    }
+    if state.flight_time < program.pitch_over_time {
+        return None;
+    }
+    match program.min_thrust_to_weight {
+        None => Some(program.pitch_over_time),
+        Some(min) if thrust_to_weight(params, state) >= min => Some(state.flight_time),
+        Some(_) => None,
+    }
+}
+
+/// 重力ターンのピッチ角指令
+/// - ピッチオーバー前: 垂直 (pi/2)
+/// - ピッチオーバー中: pi/2 から pitch_over_rate で pitch_over_angle まで傾ける
+/// - ピッチオーバー後: 傾けた姿勢を保持し、速度ベクトルの経路角 asin(vz/|v|) が
+///   それを下回ったら経路角に追従
+///
+/// ピッチオーバーは state.pitch_over_start から開始する (未記録なら pitch_over_time。
+/// 推力重量比の条件がある場合は開始が記録されるまで垂直)
+pub fn gravity_turn_pitch(program: &GravityTurn, state: &MissileState) -> f64 {
+    let vertical = std::f64::consts::FRAC_PI_2;
+    let start = match (state.pitch_over_start, program.min_thrust_to_weight) {
+        (Some(start), _) => start,
+        (None, None) => program.pitch_over_time,
+        (None, Some(_)) => return vertical,
+    };
+    if state.flight_time < start {
+        return vertical;
+    }
+    let kick = program.pitch_over_rate * (state.flight_time - start);
+    if kick < program.pitch_over_angle {
+        return vertical - kick;
+    }
+    let hold = vertical - program.pitch_over_angle;
     let speed = vector_norm(&state.velocity);
     if speed < 1e-9 {
-        // 速度がほぼ0なら抗力0
-        // (厳密には速度0でも抗力方向は定義できるが、NaN回避のためこうする)
-        let gravity = [0.0, 0.0, -params.g * state.mass];
-        let thrust_vec = [
-            state.thrust * state.theta.cos() * state.psi.cos(),
-            state.thrust * state.theta.cos() * state.psi.sin(),
-            state.thrust * state.theta.sin(),
-        ];
-        return [
-            thrust_vec[0] + gravity[0],
-            thrust_vec[1] + gravity[1],
-            thrust_vec[2] + gravity[2],
-        ];
+        hold
+    } else {
+        (state.velocity[2] / speed).asin().min(hold)
     }
+}
 
-    // 抗力
-    let drag = 0.5 * rho * params.cd * params.area * speed * speed;
-    let drag_vec = [
-        -drag * (state.velocity[0] / speed),
-        -drag * (state.velocity[1] / speed),
-        -drag * (state.velocity[2] / speed),
-    ];
+/// 巡航速度保持による推力停止の判定 (ヒステリシス付き)
+/// 帯域内では現在の状態を維持する。戻り値は更新後の推力停止フラグ
+pub fn cruise_thrust_cutoff(hold: &CruiseSpeedHold, speed: f64, thrust_cutoff: bool) -> bool {
+    if speed >= hold.target_speed + hold.band {
+        true
+    } else if speed <= hold.target_speed - hold.band {
+        false
+    } else {
+        thrust_cutoff
+    }
+}
 
-    // 重力
-    let gravity_vec = [0.0, 0.0, -params.g * state.mass];
+/// 抗力を無視する大気密度の閾値の既定値 [kg/m^3]
+pub const VACUUM_DENSITY_THRESHOLD: f64 = 1e-5;
 
-    // 推力
-    let thrust_vec = [
-        state.thrust * state.theta.cos() * state.psi.cos(),
-        state.thrust * state.theta.cos() * state.psi.sin(),
-        state.thrust * state.theta.sin(),
-    ];
+/// 高度 z における大気密度 [kg/m^3]
+/// 指数大気 rho0 * exp(-z/h) を、地表下 (z < 0) では海面値 rho0 で頭打ちにし、
+/// density_floor 未満となる高高度では 0 とする
+pub fn atmospheric_density(params: &MissileParams, z: f64) -> f64 {
+    let rho = params.rho0 * (-z.max(0.0) / params.h).exp();
+    if rho < params.density_floor {
+        0.0
+    } else {
+        rho
+    }
+}
 
-    [
-        thrust_vec[0] + drag_vec[0] + gravity_vec[0],
-        thrust_vec[1] + drag_vec[1] + gravity_vec[1],
-        thrust_vec[2] + drag_vec[2] + gravity_vec[2],
-    ]
+/// 抗力の大気密度を評価する高度 [m]
+/// midpoint_density が有効な場合は、現在の鉛直速度で dt/2 進めたステップ中点の予測高度とする
+/// (鉛直速度が大きいとステップ内で密度が大きく変わるため、ステップ平均の抗力に近づける)
+fn drag_altitude(params: &MissileParams, state: &MissileState, dt: f64) -> f64 {
+    if params.midpoint_density {
+        state.position[2] + 0.5 * state.velocity[2] * dt
+    } else {
+        state.position[2]
+    }
+}
+
+/// 点火からの経過時間 flight_time [s] における推力 [N]
+/// ignition_time の間はモータの立ち上がりとして公称推力 state.thrust まで線形に増加させる。
+/// 無動力の子弾 (dart) は常に0
+pub fn thrust_at(params: &MissileParams, state: &MissileState) -> f64 {
+    if params.dart {
+        return 0.0;
+    }
+    if params.ignition_time <= 0.0 {
+        return state.thrust;
+    }
+    state.thrust * (state.flight_time / params.ignition_time).clamp(0.0, 1.0)
+}
+
+/// 合力計算 (力モデルのパイプラインの合計)
+fn calculate_acceleration(
+    params: &MissileParams,
+    state: &MissileState,
+    forces: &[Box<dyn ForceModel>],
+    dt: f64,
+) -> [f64; 3] {
+    let environment = ForceEnvironment {
+        air_density: atmospheric_density(params, drag_altitude(params, state, dt)),
+        thrust: [
+            state.thrust * state.theta.cos() * state.psi.cos(),
+            state.thrust * state.theta.cos() * state.psi.sin(),
+            state.thrust * state.theta.sin(),
+        ],
+    };
+    net_force(forces, state, &environment)
 }
 
 /// 質量更新
@@ -95,7 +363,7 @@ fn update_mass(params: &MissileParams, state: &MissileState, dt: f64) -> f64 {
     }
 }
 
-/// ミサイルの状態を更新 (Adams-Bashforth 2段法 + ローパスフィルタ)
+/// ミサイルの状態を更新 (推力 + 抗力 + 重力の既定の力モデル)
 pub fn update_missile(
     params: &MissileParams,
     state: &MissileState,
@@ -103,8 +371,50 @@ pub fn update_missile(
     filters: &mut [LowPassFilter; 3],
     dt: f64,
 ) -> MissileState {
+    update_missile_with_forces(params, state, &default_force_models(params), integrators, filters, dt)
+}
+
+/// 力モデルのパイプラインを指定してミサイルの状態を更新 (Adams-Bashforth 2段法 + ローパスフィルタ)
+pub fn update_missile_with_forces(
+    params: &MissileParams,
+    state: &MissileState,
+    forces: &[Box<dyn ForceModel>],
+    integrators: &mut [AdamsBashforthIntegrator; 3],
+    filters: &mut [LowPassFilter; 3],
+    dt: f64,
+) -> MissileState {
+    // 着弾・迎撃済みのミサイルは着弾点に留める
+    if !state.active {
+        return state.clone();
+    }
+
+    // 発射プログラムによるピッチ角 (推力重量比の条件を満たしたらピッチオーバーを開始)
+    let (theta, pitch_over_start) = match &params.gravity_turn {
+        Some(program) => {
+            let start = pitch_over_start(program, params, state);
+            let state = MissileState {
+                pitch_over_start: start,
+                ..*state
+            };
+            (gravity_turn_pitch(program, &state), start)
+        }
+        None => (state.theta, state.pitch_over_start),
+    };
+    // 巡航速度保持による推力の停止・再点火
+    let thrust_cutoff = match &params.cruise_speed_hold {
+        Some(hold) => cruise_thrust_cutoff(hold, vector_norm(&state.velocity), state.thrust_cutoff),
+        None => false,
+    };
+    let nominal_thrust = state.thrust;
+    let state = &MissileState {
+        theta,
+        pitch_over_start,
+        thrust: if thrust_cutoff { 0.0 } else { thrust_at(params, state) },
+        ..*state
+    };
+
     // 加速度
-    let acc = calculate_acceleration(params, state);
+    let acc = calculate_acceleration(params, state, forces, dt);
 
     // 速度更新 (AB2段法)
     let mut new_velocity = [0.0; 3];
@@ -118,27 +428,1011 @@ pub fn update_missile(
     }
 
     // 位置更新
-    let new_position = [
+    let mut new_position = [
         state.position[0] + new_velocity[0] * dt,
         state.position[1] + new_velocity[1] * dt,
         state.position[2] + new_velocity[2] * dt,
     ];
 
+    // 地表 (z=0) を下回った場合は交差点に位置を補間して停止する
+    let impacted = new_position[2] < 0.0;
+    if impacted {
+        new_position = ground_crossing(&state.position, &new_position);
+    }
+
     // 質量更新
     let new_mass = update_mass(params, state, dt);
 
     // 新しい状態
     MissileState {
         mass: new_mass,
-        thrust: state.thrust, // 必要に応じて制御
+        thrust: nominal_thrust, // 推力停止中も公称推力を保持
         theta: state.theta,   // 必要に応じて制御
         psi: state.psi,       // 必要に応じて制御
         position: new_position,
         velocity: new_velocity,
+        flight_time: state.flight_time + dt,
+        active: !impacted,
+        thrust_cutoff,
+        intercepted: false,
+        pitch_over_start: state.pitch_over_start,
+    }
+}
+
+/// 2点間の線分と地表 (z=0) の交差点を線形補間で求める
+fn ground_crossing(start: &[f64; 3], end: &[f64; 3]) -> [f64; 3] {
+    let dz = start[2] - end[2];
+    let ratio = if dz > 1e-12 { (start[2] / dz).clamp(0.0, 1.0) } else { 1.0 };
+    [
+        start[0] + (end[0] - start[0]) * ratio,
+        start[1] + (end[1] - start[1]) * ratio,
+        0.0,
+    ]
+}
+
+/// 着弾角 (水平面からの降下角) [rad]
+/// 鉛直に落下する場合 pi/2、水平に接地する場合 0
+pub fn impact_angle(velocity: &[f64; 3]) -> f64 {
+    let horizontal = (velocity[0] * velocity[0] + velocity[1] * velocity[1]).sqrt();
+    (-velocity[2]).atan2(horizontal)
+}
+
+/// ステップ内で地表 (z=0) に達するまでの時間 [s]
+/// 位置は1ステップの間 速度 vz で線形に変化するとして補間する (0..=dt に制限)
+pub fn ground_crossing_time(z_start: f64, vz: f64, dt: f64) -> f64 {
+    if vz >= 0.0 {
+        return dt;
+    }
+    (z_start / -vz).clamp(0.0, dt)
+}
+
+/// 時間刻みの安定性に関する警告
+#[derive(Clone, Debug, PartialEq)]
+pub enum StabilityWarning {
+    /// 抗力・推力の時定数に対して dt が大きすぎる (AB2の安定条件 |lambda| dt < 1 を満たさない)
+    StepTooLarge { dt: f64, max_stable_dt: f64 },
+    /// ローパスフィルタの alpha が (0, 1] の範囲外
+    FilterAlpha { alpha: f64 },
+}
+
+impl std::fmt::Display for StabilityWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StabilityWarning::StepTooLarge { dt, max_stable_dt } => write!(
+                f,
+                "dt = {} s は安定限界 {:.4} s を超えています (数値積分が発散する可能性があります)",
+                dt, max_stable_dt
+            ),
+            StabilityWarning::FilterAlpha { alpha } => {
+                write!(f, "alpha_filter = {} は (0, 1] の範囲外です", alpha)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StabilityWarning {}
+
+/// 抗力と推力に対する安定な時間刻みの上限 [s]
+/// 抗力 dv/dt = -(rho cd A / 2m) v^2 を線形化した固有値 rho cd A v / m と、
+/// 燃料消費による推力加速度 T/m の相対変化率 alpha T / m の和を lambda として、
+/// AB2 の実軸上の安定領域 -1 < -lambda dt < 0 から dt < 1 / lambda
+pub fn max_stable_dt(params: &MissileParams, state: &MissileState, max_speed: f64) -> f64 {
+    let drag = params.rho0 * params.cd * params.area * max_speed / state.mass;
+    let thrust = if params.dart { 0.0 } else { params.alpha * state.thrust / state.mass };
+    let lambda = drag + thrust;
+    if lambda <= 0.0 {
+        f64::INFINITY
+    } else {
+        1.0 / lambda
+    }
+}
+
+/// 抗力と重力が釣り合う終端速度 [m/s] sqrt(2 m g / (rho cd A))
+/// 抗力パラメータの妥当性確認用。抗力が働かない (rho cd A <= 0) 場合は無限大
+pub fn terminal_velocity(mass: f64, cd: f64, area: f64, air_density: f64, g: f64) -> f64 {
+    let drag_factor = air_density * cd * area;
+    if drag_factor <= 0.0 {
+        return f64::INFINITY;
+    }
+    (2.0 * mass * g / drag_factor).sqrt()
+}
+
+/// 起動時の時間刻みの安定性チェック
+/// 最も速いダイナミクス (地表密度・想定最大速度での抗力と、初期状態の推力による質量減少) とフィルタ係数から判定する
+pub fn check_stability(
+    params: &MissileParams,
+    state: &MissileState,
+    max_speed: f64,
+    dt: f64,
+) -> Result<(), StabilityWarning> {
+    if params.alpha_filter <= 0.0 || params.alpha_filter > 1.0 {
+        return Err(StabilityWarning::FilterAlpha {
+            alpha: params.alpha_filter,
+        });
+    }
+    let max_stable_dt = max_stable_dt(params, state, max_speed);
+    if dt >= max_stable_dt {
+        return Err(StabilityWarning::StepTooLarge { dt, max_stable_dt });
+    }
+    Ok(())
+}
+
+/// 慣性飛行 (推力・抗力なし) 中の保存則の破れ
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConservationViolation {
+    /// 運動量の変化が重力による力積と一致しない
+    Momentum { expected: [f64; 3], actual: [f64; 3] },
+    /// 力学的エネルギー (運動エネルギー + 位置エネルギー) が変化した
+    Energy { before: f64, after: f64 },
+}
+
+impl std::fmt::Display for ConservationViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConservationViolation::Momentum { expected, actual } => write!(
+                f,
+                "運動量の変化 {:?} が重力の力積 {:?} と一致しません",
+                actual, expected
+            ),
+            ConservationViolation::Energy { before, after } => {
+                write!(f, "力学的エネルギーが {} J から {} J に変化しました", before, after)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConservationViolation {}
+
+/// 慣性飛行中 (推力なし、かつ抗力なし) かどうか
+pub fn is_coasting(params: &MissileParams, state: &MissileState) -> bool {
+    let no_thrust = state.thrust == 0.0 || state.thrust_cutoff;
+    let no_drag = params.cd == 0.0 || atmospheric_density(params, state.position[2]) == 0.0;
+    state.active && no_thrust && no_drag
+}
+
+/// 力学的エネルギー (運動エネルギー + 原点基準の位置エネルギー -m g・r) [J]
+/// 重力が -z 方向なら位置エネルギーは地表基準の m g z
+pub fn mechanical_energy(params: &MissileParams, state: &MissileState) -> f64 {
+    let speed = vector_norm(&state.velocity);
+    0.5 * state.mass * speed * speed - state.mass * dot(&params.gravity().acceleration, &state.position)
+}
+
+/// 慣性飛行の1ステップについて保存則を検査する
+/// - 運動量の変化が重力の力積 m g dt (gravity_vector を含む重力加速度ベクトル g) と一致すること
+/// - 力学的エネルギーが保存されること
+///
+/// どちらも相対誤差で判定する (運動量は max(|p|, m |g| dt)、エネルギーは max(|E|, m |g| dt |v|) を基準とする)。
+/// 速度更新後の速度で位置を進めるため、エネルギーは 1 ステップあたり 0.5 m (g dt)^2 だけ
+/// 減少するが、通常の刻みでは許容誤差に比べ十分小さい。
+pub fn check_coast_conservation(
+    params: &MissileParams,
+    before: &MissileState,
+    after: &MissileState,
+    dt: f64,
+    tolerance: f64,
+) -> Result<(), ConservationViolation> {
+    let m = before.mass;
+    let gravity = params.gravity().force(m);
+    let expected = gravity.map(|f| f * dt);
+    let impulse = vector_norm(&expected);
+    let actual = [
+        m * (after.velocity[0] - before.velocity[0]),
+        m * (after.velocity[1] - before.velocity[1]),
+        m * (after.velocity[2] - before.velocity[2]),
+    ];
+    let momentum_scale = (m * vector_norm(&before.velocity)).max(impulse);
+    let error = [actual[0] - expected[0], actual[1] - expected[1], actual[2] - expected[2]];
+    if vector_norm(&error) > tolerance * momentum_scale {
+        return Err(ConservationViolation::Momentum { expected, actual });
+    }
+
+    let energy_before = mechanical_energy(params, before);
+    let energy_after = mechanical_energy(params, after);
+    let energy_scale = energy_before.abs().max(impulse * vector_norm(&before.velocity));
+    if (energy_after - energy_before).abs() > tolerance * energy_scale {
+        return Err(ConservationViolation::Energy {
+            before: energy_before,
+            after: energy_after,
+        });
+    }
+    Ok(())
+}
+
+/// 現在の状態から着弾点を予測する
+/// 積分器・フィルタの複製を用いて着弾 (active=false) まで update_missile を繰り返す。
+/// max_time [s] 以内に着弾しない場合は None
+pub fn predict_impact(
+    params: &MissileParams,
+    state: &MissileState,
+    integrators: &[AdamsBashforthIntegrator; 3],
+    filters: &[LowPassFilter; 3],
+    dt: f64,
+    max_time: f64,
+) -> Option<[f64; 3]> {
+    let mut integrators = integrators.clone();
+    let mut filters = filters.clone();
+    let mut predicted = state.clone();
+    let mut elapsed = 0.0;
+    while predicted.active {
+        if elapsed >= max_time {
+            return None;
+        }
+        predicted = update_missile(params, &predicted, &mut integrators, &mut filters, dt);
+        elapsed += dt;
+    }
+    Some(predicted.position)
+}
+
+/// 推力・抗力なし (2体問題の放物線) での着弾点の解析解
+/// z + vz t - g t^2 / 2 = 0 の正の根から着弾時刻を求める。予測の初期値として積分より安価に使用できる。
+/// 既に z <= 0 なら現在位置、g <= 0 かつ上昇中で着弾しない場合は None を返す
+pub fn ballistic_impact(pos: &[f64; 3], vel: &[f64; 3], g: f64) -> Option<[f64; 3]> {
+    if pos[2] <= 0.0 {
+        return Some(*pos);
+    }
+    let t = if g > 0.0 {
+        (vel[2] + (vel[2] * vel[2] + 2.0 * g * pos[2]).sqrt()) / g
+    } else if vel[2] < 0.0 {
+        -pos[2] / vel[2]
+    } else {
+        return None;
+    };
+    Some([pos[0] + vel[0] * t, pos[1] + vel[1] * t, 0.0])
+}
+
+/// 着弾まで推力・抗力を受けず、重力が -z 方向か (推力なしで、cd・断面積が0または地表でも大気密度が density_floor 未満)
+fn is_ballistic(params: &MissileParams, state: &MissileState) -> bool {
+    let no_thrust = params.dart || state.thrust == 0.0 || state.thrust_cutoff;
+    let no_drag = params.cd == 0.0 || params.area == 0.0 || params.rho0 < params.density_floor;
+    no_thrust && no_drag && params.gravity_vector.is_none()
+}
+
+/// 着弾点の予測
+/// 着弾まで推力・抗力を受けない場合は解析解 ballistic_impact、それ以外は predict_impact の数値積分を用いる
+pub fn impact_point(
+    params: &MissileParams,
+    state: &MissileState,
+    integrators: &[AdamsBashforthIntegrator; 3],
+    filters: &[LowPassFilter; 3],
+    dt: f64,
+    max_time: f64,
+) -> Option<[f64; 3]> {
+    if is_ballistic(params, state) {
+        ballistic_impact(&state.position, &state.velocity, params.g)
+    } else {
+        predict_impact(params, state, integrators, filters, dt, max_time)
     }
 }
 
 /// 衝突判定 (z <= 0)
 pub fn check_collision(state: &MissileState) -> bool {
     state.position[2] <= 0.0
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_params(gravity_turn: Option<GravityTurn>) -> MissileParams {
+        MissileParams {
+            area: 1.0,
+            rho0: 1.225,
+            h: 8500.0,
+            g: 9.81,
+            alpha_filter: 1.0,
+            gravity_turn,
+            density_floor: VACUUM_DENSITY_THRESHOLD,
+            ..Default::default()
+        }
+    }
+
+    const PARAMS_YAML: &str = "alpha: 0.01\ncd: 0.5\nrho0: 1.225\nh: 8500.0\ng: 9.81\nalpha_filter: 0.1\n";
+
+    #[test]
+    fn test_params_area_from_diameter() {
+        let yaml = format!("{}diameter: 0.5\n", PARAMS_YAML);
+        let params: MissileParams = serde_yaml::from_str(&yaml).unwrap();
+        assert!((params.area - 0.19634954084936207).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_params_area_direct() {
+        let yaml = format!("{}area: 1.0\n", PARAMS_YAML);
+        let params: MissileParams = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(params.area, 1.0);
+    }
+
+    #[test]
+    fn test_params_area_and_diameter_consistency() {
+        let consistent = format!("{}area: {}\ndiameter: 0.5\n", PARAMS_YAML, area_from_diameter(0.5));
+        assert!(serde_yaml::from_str::<MissileParams>(&consistent).is_ok());
+
+        let inconsistent = format!("{}area: 1.0\ndiameter: 0.5\n", PARAMS_YAML);
+        assert!(serde_yaml::from_str::<MissileParams>(&inconsistent).is_err());
+
+        assert!(serde_yaml::from_str::<MissileParams>(PARAMS_YAML).is_err());
+    }
+
+    #[test]
+    fn test_params_alpha_filter_is_validated_on_load() {
+        let yaml = |alpha: &str| PARAMS_YAML.replace("alpha_filter: 0.1", &format!("alpha_filter: {}\narea: 1.0", alpha));
+        assert_eq!(serde_yaml::from_str::<MissileParams>(&yaml("1.0")).unwrap().alpha_filter, 1.0);
+        for invalid in ["1.5", "-0.1", ".nan"] {
+            assert!(serde_yaml::from_str::<MissileParams>(&yaml(invalid)).is_err(), "{}", invalid);
+        }
+    }
+
+    fn launch_state() -> MissileState {
+        MissileState {
+            mass: 1000.0,
+            thrust: 15000.0,
+            theta: std::f64::consts::FRAC_PI_2,
+            velocity: [0.0, 0.0, 1.0],
+            ..Default::default()
+        }
+    }
+
+    fn flight_path_angle(state: &MissileState) -> f64 {
+        (state.velocity[2] / vector_norm(&state.velocity)).asin().to_degrees()
+    }
+
+    fn gravity_turn() -> GravityTurn {
+        GravityTurn {
+            pitch_over_time: 5.0,
+            pitch_over_rate: 2.0_f64.to_radians(),
+            pitch_over_angle: 10.0_f64.to_radians(),
+            min_thrust_to_weight: None,
+        }
+    }
+
+    #[test]
+    fn test_gravity_turn_climbs_vertically_before_pitch_over() {
+        let params = test_params(Some(gravity_turn()));
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let mut state = launch_state();
+
+        while state.flight_time < 4.9 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+            assert!(flight_path_angle(&state) > 89.99);
+            assert!((state.theta - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        }
+        assert!(state.position[2] > 0.0);
+    }
+
+    #[test]
+    fn test_gravity_turn_flight_path_angle_decreases_smoothly() {
+        let params = test_params(Some(gravity_turn()));
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let mut state = launch_state();
+
+        while state.flight_time < 5.0 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+        }
+
+        let mut previous = flight_path_angle(&state);
+        while state.flight_time < 60.0 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+            let fpa = flight_path_angle(&state);
+            // 単調減少かつ1ステップあたりの変化が小さいこと
+            assert!(fpa <= previous + 1e-9, "fpa increased: {} -> {}", previous, fpa);
+            assert!(previous - fpa < 1.0, "fpa jumped: {} -> {}", previous, fpa);
+            previous = fpa;
+        }
+        assert!(previous < 80.0, "final fpa {}", previous);
+        assert!(state.velocity[0] > 0.0);
+    }
+
+    #[test]
+    fn test_impacted_missile_stays_at_impact_point() {
+        let params = test_params(None);
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let mut state = MissileState {
+            thrust: 0.0,
+            position: [0.0, 0.0, 100.0],
+            velocity: [50.0, 0.0, -200.0],
+            theta: 0.0,
+            ..launch_state()
+        };
+
+        while state.active {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+            assert!(state.position[2] >= 0.0);
+        }
+        let impact = state.position;
+        assert_eq!(impact[2], 0.0);
+        assert!(impact[0] > 0.0);
+
+        for _ in 0..10 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+            assert_eq!(state.position, impact);
+            assert!(!state.active);
+        }
+    }
+
+    #[test]
+    fn test_predict_impact_matches_simulated_impact() {
+        let params = test_params(None);
+        let integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let state = MissileState {
+            thrust: 0.0,
+            position: [0.0, 0.0, 1000.0],
+            velocity: [100.0, 0.0, 0.0],
+            ..launch_state()
+        };
+
+        let predicted = predict_impact(&params, &state, &integrators, &filters, 0.1, 100.0).unwrap();
+        // 自由落下 sqrt(2h/g) ≈ 14.3 s の間に水平方向へ約 1428 m 進む
+        assert!((predicted[0] - 1428.0).abs() < 20.0);
+        assert_eq!(predicted[2], 0.0);
+
+        assert!(predict_impact(&params, &state, &integrators, &filters, 0.1, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_no_drag_above_atmosphere() {
+        let params = MissileParams {
+            cd: 0.5,
+            ..test_params(None)
+        };
+        let state = MissileState {
+            position: [0.0, 0.0, 200_000.0],
+            velocity: [3000.0, 0.0, 1000.0],
+            theta: 0.3,
+            ..launch_state()
+        };
+
+        let force = calculate_acceleration(&params, &state, &default_force_models(&params), 0.1);
+
+        let thrust = [
+            state.thrust * state.theta.cos() * state.psi.cos(),
+            state.thrust * state.theta.cos() * state.psi.sin(),
+            state.thrust * state.theta.sin(),
+        ];
+        assert_eq!(force, [thrust[0], thrust[1], thrust[2] - params.g * state.mass]);
+    }
+
+    #[test]
+    fn test_ground_crossing_interpolation() {
+        let crossing = ground_crossing(&[0.0, 0.0, 10.0], &[10.0, 20.0, -10.0]);
+        assert_eq!(crossing, [5.0, 10.0, 0.0]);
+    }
+
+    #[test]
+    fn test_gravity_turn_pitch_phases() {
+        let program = gravity_turn();
+        let mut state = launch_state();
+        assert_eq!(gravity_turn_pitch(&program, &state), std::f64::consts::FRAC_PI_2);
+
+        state.flight_time = 6.0;
+        let expected = std::f64::consts::FRAC_PI_2 - 2.0_f64.to_radians();
+        assert!((gravity_turn_pitch(&program, &state) - expected).abs() < 1e-12);
+
+        // 速度ベクトルが傾けた姿勢より立っている間は姿勢を保持
+        state.flight_time = 10.0;
+        state.velocity = [1.0, 0.0, 100.0];
+        let hold = std::f64::consts::FRAC_PI_2 - 10.0_f64.to_radians();
+        assert!((gravity_turn_pitch(&program, &state) - hold).abs() < 1e-12);
+
+        // 以降は速度ベクトルに追従
+        state.velocity = [100.0, 0.0, 100.0];
+        assert!((gravity_turn_pitch(&program, &state) - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cruise_speed_hold_settles_and_cycles_thrust() {
+        // 水平飛行 (重力なし) で抗力により減速、推力で加速
+        let params = MissileParams {
+            g: 0.0,
+            cd: 0.3,
+            cruise_speed_hold: Some(CruiseSpeedHold {
+                target_speed: 250.0,
+                band: 5.0,
+            }),
+            ..test_params(None)
+        };
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let mut state = MissileState {
+            theta: 0.0,
+            thrust: 100000.0,
+            position: [0.0, 0.0, 1000.0],
+            velocity: [200.0, 0.0, 0.0],
+            ..launch_state()
+        };
+
+        let mut toggles = 0;
+        while state.flight_time < 120.0 {
+            let previous_cutoff = state.thrust_cutoff;
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+            if state.thrust_cutoff != previous_cutoff {
+                toggles += 1;
+            }
+            if state.flight_time > 30.0 {
+                let speed = vector_norm(&state.velocity);
+                // 1ステップ分の行き過ぎを許容して帯域内に収まる
+                assert!((speed - 250.0).abs() < 5.0 + 10.0, "speed {}", speed);
+            }
+        }
+        assert!(toggles >= 4, "toggles {}", toggles);
+        assert_eq!(state.thrust, 100000.0);
+    }
+
+    #[test]
+    fn test_cruise_thrust_cutoff_hysteresis() {
+        let hold = CruiseSpeedHold {
+            target_speed: 100.0,
+            band: 10.0,
+        };
+        assert!(cruise_thrust_cutoff(&hold, 110.0, false));
+        assert!(cruise_thrust_cutoff(&hold, 100.0, true));
+        assert!(!cruise_thrust_cutoff(&hold, 100.0, false));
+        assert!(!cruise_thrust_cutoff(&hold, 90.0, true));
+    }
+
+    #[test]
+    fn test_kinetic_energy() {
+        let missile = Missile {
+            params: test_params(None),
+            state: MissileState {
+                mass: 1000.0,
+                velocity: [300.0, 0.0, -400.0],
+                ..launch_state()
+            },
+        };
+        // 0.5 * 1000 * 500^2
+        assert_eq!(missile.kinetic_energy(), 1.25e8);
+    }
+
+    #[test]
+    fn test_impact_angle_and_crossing_time() {
+        assert!((impact_angle(&[0.0, 0.0, -100.0]) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!(impact_angle(&[100.0, 0.0, 0.0]).abs() < 1e-12);
+        assert!((impact_angle(&[100.0, 0.0, -100.0]) - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+
+        // 高度 5 m から 100 m/s で降下 → 0.05 s で接地
+        assert!((ground_crossing_time(5.0, -100.0, 0.1) - 0.05).abs() < 1e-12);
+        assert_eq!(ground_crossing_time(50.0, -100.0, 0.1), 0.1);
+    }
+
+    #[test]
+    fn test_check_stability_flags_large_dt() {
+        let params = MissileParams {
+            cd: 0.5,
+            ..test_params(None)
+        };
+        let state = MissileState {
+            thrust: 0.0,
+            ..launch_state()
+        };
+        // lambda = 1.225 * 0.5 * 1.0 * 3000 / 1000 ≈ 1.84 → 安定限界 ≈ 0.544 s
+        assert!(check_stability(&params, &state, 3000.0, 0.1).is_ok());
+        match check_stability(&params, &state, 3000.0, 1.0) {
+            Err(StabilityWarning::StepTooLarge { max_stable_dt, .. }) => {
+                assert!((max_stable_dt - 1000.0 / (1.225 * 0.5 * 3000.0)).abs() < 1e-12);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_stability_flags_filter_alpha() {
+        let params = MissileParams {
+            alpha_filter: 1.5,
+            ..test_params(None)
+        };
+        assert_eq!(
+            check_stability(&params, &launch_state(), 3000.0, 0.1),
+            Err(StabilityWarning::FilterAlpha { alpha: 1.5 })
+        );
+    }
+
+    #[test]
+    fn test_check_stability_includes_thrust() {
+        // 抗力なしでも、推力による質量減少 alpha T / m = 0.01 * 15000 / 1000 = 0.15 1/s で安定限界 ≈ 6.67 s
+        let params = MissileParams {
+            alpha: 0.01,
+            ..test_params(None)
+        };
+        let state = launch_state();
+        assert!((max_stable_dt(&params, &state, 3000.0) - 1.0 / 0.15).abs() < 1e-9);
+        assert!(check_stability(&params, &state, 3000.0, 1.0).is_ok());
+        assert!(matches!(
+            check_stability(&params, &state, 3000.0, 10.0),
+            Err(StabilityWarning::StepTooLarge { .. })
+        ));
+        // 無動力の子弾・推力なしでは制限しない
+        let dart = MissileParams { dart: true, ..params.clone() };
+        assert_eq!(max_stable_dt(&dart, &state, 3000.0), f64::INFINITY);
+
+        // 抗力と推力の両方があれば固有値の和で制限する
+        let both = MissileParams { cd: 0.5, ..params };
+        let expected = 1.0 / (1.225 * 0.5 * 3000.0 / 1000.0 + 0.15);
+        assert!((max_stable_dt(&both, &state, 3000.0) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_density_vanishes_at_extreme_altitude() {
+        let params = test_params(None);
+        assert_eq!(atmospheric_density(&params, 0.0), 1.225);
+        assert!(atmospheric_density(&params, 10000.0) > 0.0);
+        assert_eq!(atmospheric_density(&params, 200000.0), 0.0);
+    }
+
+    #[test]
+    fn test_density_capped_below_ground() {
+        let params = test_params(None);
+        assert_eq!(atmospheric_density(&params, -100.0), params.rho0);
+        assert_eq!(atmospheric_density(&params, -1.0e6), params.rho0);
+    }
+
+    #[test]
+    fn test_density_floor_from_yaml() {
+        let yaml = format!("{}area: 1.0\ndensity_floor: 0.1\n", PARAMS_YAML);
+        let params: MissileParams = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(params.density_floor, 0.1);
+        // rho0 * exp(-z/h) = 0.1 となる高度 (約 21.3 km) より上は真空
+        assert!(atmospheric_density(&params, 20000.0) > 0.1);
+        assert_eq!(atmospheric_density(&params, 25000.0), 0.0);
+
+        let yaml = format!("{}area: 1.0\n", PARAMS_YAML);
+        let params: MissileParams = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(params.density_floor, VACUUM_DENSITY_THRESHOLD);
+    }
+
+    #[test]
+    fn test_coast_conservation_detects_broken_force() {
+        let params = test_params(None);
+        let before = MissileState {
+            thrust: 0.0,
+            position: [0.0, 0.0, 10000.0],
+            velocity: [200.0, 0.0, 100.0],
+            ..launch_state()
+        };
+        assert!(is_coasting(&params, &before));
+
+        // 正しい重力 (2 ステップ目以降の AB2)
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let warmup = update_missile(&params, &before, &mut integrators, &mut filters, 0.1);
+        let after = update_missile(&params, &warmup, &mut integrators, &mut filters, 0.1);
+        assert_eq!(check_coast_conservation(&params, &warmup, &after, 0.1, 1e-4), Ok(()));
+
+        // 重力を2倍に誤って計算した場合
+        let broken = MissileParams { g: 2.0 * params.g, ..params.clone() };
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let warmup = update_missile(&broken, &before, &mut integrators, &mut filters, 0.1);
+        let after = update_missile(&broken, &warmup, &mut integrators, &mut filters, 0.1);
+        assert!(matches!(
+            check_coast_conservation(&params, &warmup, &after, 0.1, 1e-4),
+            Err(ConservationViolation::Momentum { .. })
+        ));
+    }
+
+    #[test]
+    fn test_coast_conservation_uses_gravity_vector() {
+        // 水平成分を持つ重力 (|g| = 9.81 * sqrt(2))
+        let params = MissileParams {
+            gravity_vector: Some([9.81, 0.0, -9.81]),
+            ..test_params(None)
+        };
+        let before = MissileState {
+            thrust: 0.0,
+            position: [0.0, 0.0, 10000.0],
+            velocity: [200.0, 0.0, 100.0],
+            ..launch_state()
+        };
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let warmup = update_missile(&params, &before, &mut integrators, &mut filters, 0.1);
+        let after = update_missile(&params, &warmup, &mut integrators, &mut filters, 0.1);
+        assert!(after.velocity[0] < warmup.velocity[0] + 1.0 && after.velocity[0] > warmup.velocity[0]);
+        assert_eq!(check_coast_conservation(&params, &warmup, &after, 0.1, 1e-4), Ok(()));
+
+        // 位置エネルギーは -m g・r (x 方向に進むと水平重力の分だけ減る)
+        let moved = MissileState {
+            position: [100.0, 0.0, 10000.0],
+            ..before.clone()
+        };
+        let drop = mechanical_energy(&params, &before) - mechanical_energy(&params, &moved);
+        assert!((drop - before.mass * 9.81 * 100.0).abs() < 1e-6);
+        // 重量は |g| で評価する
+        let thrusting = launch_state();
+        let expected = thrust_at(&params, &thrusting) / (thrusting.mass * 9.81 * 2.0_f64.sqrt());
+        assert!((thrust_to_weight(&params, &thrusting) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_terminal_velocity_analytic() {
+        // sqrt(2 * 1000 * 9.81 / (1.225 * 0.5 * 1.0)) ≈ 178.97 m/s
+        let v = terminal_velocity(1000.0, 0.5, 1.0, 1.225, 9.81);
+        assert!((v - (2.0 * 1000.0 * 9.81 / (1.225 * 0.5 * 1.0f64)).sqrt()).abs() < 1e-12);
+        assert!((v - 178.97).abs() < 0.01);
+        assert_eq!(terminal_velocity(1000.0, 0.0, 1.0, 1.225, 9.81), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_terminal_velocity_scales_with_sqrt_mass() {
+        let light = terminal_velocity(1000.0, 0.5, 1.0, 1.225, 9.81);
+        let heavy = terminal_velocity(2000.0, 0.5, 1.0, 1.225, 9.81);
+        assert!((heavy / light - 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    /// 高度 30 km から 3000 m/s で降下する重いミサイルを duration [s] まで積分した速度 [m/s]
+    /// (速度の変化は小さく、抗力の変化はほぼ大気密度の増加による)
+    fn descent_speed(midpoint_density: bool, dt: f64, duration: f64) -> f64 {
+        let params = MissileParams {
+            cd: 0.5,
+            area: 2.0,
+            midpoint_density,
+            ..test_params(None)
+        };
+        let mut state = MissileState {
+            mass: 100000.0,
+            position: [0.0, 0.0, 30000.0],
+            velocity: [0.0, 0.0, -3000.0],
+            ..Default::default()
+        };
+        let mut integrators = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let steps = (duration / dt).round() as usize;
+        for _ in 0..steps {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, dt);
+        }
+        vector_norm(&state.velocity)
+    }
+
+    #[test]
+    fn test_midpoint_density_drag_closer_to_fine_step_reference() {
+        let reference = descent_speed(false, 0.001, 4.0);
+        let start_error = (descent_speed(false, 1.0, 4.0) - reference).abs();
+        let midpoint_error = (descent_speed(true, 1.0, 4.0) - reference).abs();
+        assert!(midpoint_error < start_error, "midpoint {} / start {}", midpoint_error, start_error);
+        // 細かい刻みでは両者はほぼ一致する
+        assert!((descent_speed(true, 0.001, 4.0) - reference).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_ballistic_impact_matches_fine_step_integration() {
+        let params = test_params(None);
+        let integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        for velocity in [[300.0, -120.0, 150.0], [250.0, 50.0, -80.0], [0.0, 0.0, 0.0]] {
+            let state = MissileState {
+                thrust: 0.0,
+                position: [1000.0, 2000.0, 5000.0],
+                velocity,
+                ..launch_state()
+            };
+            let analytic = ballistic_impact(&state.position, &state.velocity, params.g).unwrap();
+            let integrated = predict_impact(&params, &state, &integrators, &filters, 0.001, 600.0).unwrap();
+            let error = vector_norm(&[analytic[0] - integrated[0], analytic[1] - integrated[1], 0.0]);
+            assert!(error < 1.0, "velocity {:?}: analytic {:?} integrated {:?}", velocity, analytic, integrated);
+        }
+        // 地表以下ではその場、重力なしで上昇中なら着弾しない
+        assert_eq!(ballistic_impact(&[1.0, 2.0, 0.0], &[1.0, 0.0, -1.0], 9.81), Some([1.0, 2.0, 0.0]));
+        assert_eq!(ballistic_impact(&[0.0, 0.0, 100.0], &[1.0, 0.0, 1.0], 0.0), None);
+    }
+
+    #[test]
+    fn test_impact_point_uses_analytic_solution_without_drag() {
+        let integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let state = MissileState {
+            thrust: 0.0,
+            position: [1000.0, 2000.0, 5000.0],
+            velocity: [300.0, -120.0, 150.0],
+            ..launch_state()
+        };
+        // 抗力なし (cd = 0) の惰性飛行は解析解
+        let vacuum = test_params(None);
+        assert_eq!(
+            impact_point(&vacuum, &state, &integrators, &filters, 0.1, 600.0),
+            ballistic_impact(&state.position, &state.velocity, vacuum.g)
+        );
+        // 抗力を受ける場合は数値積分
+        let params = MissileParams {
+            cd: 0.5,
+            ..test_params(None)
+        };
+        assert_eq!(
+            impact_point(&params, &state, &integrators, &filters, 0.1, 600.0),
+            predict_impact(&params, &state, &integrators, &filters, 0.1, 600.0)
+        );
+    }
+
+    #[test]
+    fn test_ignition_ramp_reaches_full_thrust() {
+        let params = MissileParams {
+            ignition_time: 0.5,
+            ..test_params(None)
+        };
+        let at = |flight_time: f64| {
+            thrust_at(
+                &params,
+                &MissileState {
+                    flight_time,
+                    ..launch_state()
+                },
+            )
+        };
+        let nominal = launch_state().thrust;
+        assert_eq!(at(0.0), 0.0);
+        assert!((at(0.125) - 0.25 * nominal).abs() < 1e-9);
+        assert!(at(0.25) > 0.0 && at(0.25) < nominal);
+        assert_eq!(at(0.5), nominal);
+        assert_eq!(at(3.0), nominal);
+        // 立ち上がりなしでは発射直後から公称推力
+        assert_eq!(thrust_at(&test_params(None), &launch_state()), nominal);
+
+        // 立ち上がり中は加速が小さく、燃料消費も少ない
+        let climb = |params: &MissileParams| {
+            let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+            let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+            let mut state = launch_state();
+            for _ in 0..5 {
+                state = update_missile(params, &state, &mut integrators, &mut filters, 0.1);
+            }
+            state
+        };
+        let ramped = climb(&params);
+        let instant = climb(&test_params(None));
+        assert!(ramped.velocity[2] < instant.velocity[2]);
+        assert!(ramped.mass >= instant.mass);
+        assert_eq!(ramped.thrust, nominal);
+    }
+
+    #[test]
+    fn test_dart_params_do_not_require_fuel_rate() {
+        let yaml = "cd: 0.3\narea: 0.01\nrho0: 1.225\nh: 8500.0\ng: 9.81\nalpha_filter: 1.0\ndart: true\n";
+        let params: MissileParams = serde_yaml::from_str(yaml).unwrap();
+        assert!(params.dart);
+        assert_eq!(params.alpha, 0.0);
+        // 通常のミサイルでは alpha が必須
+        assert!(serde_yaml::from_str::<MissileParams>(&yaml.replace("dart: true", "dart: false")).is_err());
+    }
+
+    #[test]
+    fn test_dart_flies_drag_ballistic_arc_with_constant_mass() {
+        let params = MissileParams {
+            alpha: 0.01,
+            cd: 0.3,
+            area: 0.01,
+            dart: true,
+            ..test_params(None)
+        };
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let initial = MissileState {
+            mass: 20.0,
+            thrust: 15000.0, // 推力を設定しても無視される
+            theta: 0.0,
+            position: [0.0, 0.0, 3000.0],
+            velocity: [250.0, 0.0, 50.0],
+            ..launch_state()
+        };
+        let dt = 0.01;
+        let mut state = initial.clone();
+        for _ in 0..1000 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, dt);
+            assert_eq!(state.mass, 20.0);
+        }
+
+        // 手計算の抗力弾道 (dv/dt = -(rho cd A |v| / 2m) v - g ez を細かい刻みで積分)
+        let (mut p, mut v) = (initial.position, initial.velocity);
+        let fine_dt = 1e-4;
+        for _ in 0..100_000 {
+            let rho = 1.225 * (-p[2] / 8500.0).exp();
+            let k = 0.5 * rho * 0.3 * 0.01 * vector_norm(&v) / 20.0;
+            let a = [-k * v[0], -k * v[1], -k * v[2] - 9.81];
+            for i in 0..3 {
+                v[i] += a[i] * fine_dt;
+                p[i] += v[i] * fine_dt;
+            }
+        }
+        let error = vector_norm(&[state.position[0] - p[0], state.position[1] - p[1], state.position[2] - p[2]]);
+        assert!(error < 2.0, "dart {:?} / reference {:?}", state.position, p);
+        // 抗力で減速しており、真空中の放物線より手前に落ちる
+        assert!(state.position[0] < 250.0 * 10.0 - 100.0);
+    }
+
+    /// 推力重量比の条件付き重力ターンで飛翔し、(ピッチオーバー開始時刻, 6 s・9 s・12 s のピッチ角) を返す
+    fn tw_gated_launch(thrust: f64) -> (Option<f64>, Vec<f64>) {
+        let params = MissileParams {
+            alpha: 1.6e-3, // 24 kg/s (推力 15 kN 時) で軽くなる
+            ..test_params(Some(GravityTurn {
+                min_thrust_to_weight: Some(2.0),
+                ..gravity_turn()
+            }))
+        };
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let mut state = MissileState {
+            thrust,
+            ..launch_state()
+        };
+        let mut pitches = Vec::new();
+        for step in 1..=120 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+            if step % 30 == 0 && step > 30 {
+                pitches.push(state.theta);
+            }
+        }
+        (state.pitch_over_start, pitches)
+    }
+
+    #[test]
+    fn test_low_thrust_to_weight_delays_pitch_over() {
+        // T/W = 15000 / (1000 * 9.81) ≈ 1.53。質量が 15000 / (2 * 9.81) ≈ 765 kg となる約 9.8 s まで垂直を保持
+        let (start, pitches) = tw_gated_launch(15000.0);
+        let start = start.unwrap();
+        assert!((start - 9.8).abs() < 0.2, "{}", start);
+        assert_eq!(pitches[0], std::f64::consts::FRAC_PI_2);
+        assert_eq!(pitches[1], std::f64::consts::FRAC_PI_2);
+        assert!(pitches[2] < std::f64::consts::FRAC_PI_2);
+    }
+
+    #[test]
+    fn test_high_thrust_to_weight_pitches_over_on_schedule() {
+        // T/W ≈ 3.06 → pitch_over_time (5 s) で直ちにピッチオーバー
+        let (start, pitches) = tw_gated_launch(30000.0);
+        let start = start.unwrap();
+        assert!((5.0 - 1e-9..5.1 + 1e-9).contains(&start), "{}", start);
+        assert!(pitches.iter().all(|&theta| theta < std::f64::consts::FRAC_PI_2));
+
+        // 条件なしの場合と同じ時刻に開始する
+        let params = test_params(Some(gravity_turn()));
+        let state = MissileState {
+            flight_time: 5.0,
+            ..launch_state()
+        };
+        assert_eq!(pitch_over_start(&gravity_turn(), &params, &state), Some(5.0));
+        assert_eq!(thrust_to_weight(&params, &launch_state()), 15000.0 / 9810.0);
+    }
+
+    #[test]
+    fn test_horizontal_gravity_deflects_coasting_missile() {
+        let params = MissileParams {
+            gravity_vector: Some([2.0, 0.0, 0.0]),
+            ..test_params(None)
+        };
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(1.0));
+        let mut state = MissileState {
+            thrust: 0.0,
+            position: [0.0, 0.0, 1000.0],
+            velocity: [0.0, 100.0, 0.0],
+            ..launch_state()
+        };
+        for _ in 0..100 {
+            state = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+        }
+        // 10 s で +x 方向に 2 m/s^2 * 10 s = 20 m/s、変位は約 0.5 * 2 * 10^2 = 100 m。鉛直方向は変化しない
+        assert!((state.velocity[0] - 20.0).abs() < 0.2, "vx {}", state.velocity[0]);
+        assert!((state.position[0] - 100.0).abs() < 2.0, "x {}", state.position[0]);
+        assert_eq!(state.velocity[2], 0.0);
+        assert_eq!(state.position[2], 1000.0);
+        // 解析的な着弾点は -z 方向の重力を前提とするため使わない
+        assert!(!is_ballistic(&params, &state));
+    }
+
+    #[test]
+    fn test_default_params_and_state_are_physically_valid() {
+        // 既定値のままでも安定性チェックを通り、重力で速度が変化する (質量0・alpha_filter 0 の罠がない)
+        let params = MissileParams { g: 9.81, ..Default::default() };
+        let state = MissileState {
+            position: [0.0, 0.0, 1000.0],
+            velocity: [100.0, 0.0, 0.0],
+            ..Default::default()
+        };
+        assert!(check_stability(&params, &state, 3000.0, 0.1).is_ok());
+        let mut integrators: [AdamsBashforthIntegrator; 3] = core::array::from_fn(|_| AdamsBashforthIntegrator::new());
+        let mut filters: [LowPassFilter; 3] = core::array::from_fn(|_| LowPassFilter::new(params.alpha_filter));
+        let next = update_missile(&params, &state, &mut integrators, &mut filters, 0.1);
+        assert!(next.velocity.iter().chain(next.position.iter()).all(|v| v.is_finite()));
+        assert!(next.velocity[2] < 0.0, "vz = {}", next.velocity[2]);
+    }
+}
@@ -1,3 +1,7 @@
 pub mod missile;
 pub mod radar;
-pub mod interceptor;
\ No newline at end of file
+pub mod interceptor;
+pub mod fire_control;
+pub mod force;
+pub mod gravity;
+pub mod tracker;
\ No newline at end of file
@@ -1,8 +1,12 @@
+use std::collections::VecDeque;
+
 use serde_derive::Deserialize;
+use crate::math::random::SeededRng;
 use crate::models::missile::MissileState;
 
 /// レーダのパラメータ
 #[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "RadarParamsConfig")]
 pub struct RadarParams {
     pub position: [f64; 3],
     pub direction: [f64; 3],
@@ -10,14 +14,326 @@ pub struct RadarParams {
     pub azimuth_range: f64,    // [deg]
     pub elevation_range: f64,  // [deg]
     pub period: f64,
+    pub snr_at_max_range: Option<f64>, // 探知距離 range におけるSNR [dB] (None: SNRを計算しない)
+    pub atan2_eps: f64, // atan2 のゼロ近傍判定の閾値 (水平成分がこれ未満なら探知不可)
+    pub role: RadarRole, // 捜索レーダ / 追尾レーダ
+    pub measurement_sigma: f64, // 位置計測誤差の標準偏差 [m]
+    pub sensor_latency: f64, // 計測から探知結果が報告されるまでの遅れ [s]
+    pub hysteresis: Option<DetectionHysteresis>, // 探知のヒステリシス (None: 毎回の探知結果をそのまま使う)
+    pub pd_min: f64, // 探知範囲内での探知確率の下限
+    pub pd_max: f64, // 探知確率の上限 (至近距離でも超えない)
+    pub max_track_updates: Option<usize>, // 1回の探知処理 (1ステップ) に計測を更新できる追尾目標数 (None: 無制限)
+}
+
+/// 設定ファイル上のレーダパラメータ (読み込み時に探知確率の上下限を検査する)
+#[derive(Clone, Debug, Deserialize)]
+struct RadarParamsConfig {
+    position: [f64; 3],
+    direction: [f64; 3],
+    range: f64,
+    azimuth_range: f64,
+    elevation_range: f64,
+    period: f64,
+    snr_at_max_range: Option<f64>,
+    #[serde(default = "default_atan2_eps")]
+    atan2_eps: f64,
+    #[serde(default)]
+    role: RadarRole,
+    #[serde(default)]
+    measurement_sigma: f64,
+    #[serde(default)]
+    sensor_latency: f64,
+    #[serde(default)]
+    hysteresis: Option<DetectionHysteresis>,
+    #[serde(default)]
+    pd_min: f64,
+    #[serde(default = "default_pd_max")]
+    pd_max: f64,
+    #[serde(default)]
+    max_track_updates: Option<usize>,
+}
+
+impl TryFrom<RadarParamsConfig> for RadarParams {
+    type Error = String;
+
+    fn try_from(config: RadarParamsConfig) -> Result<Self, Self::Error> {
+        let valid = |p: f64| (0.0..=1.0).contains(&p);
+        if !valid(config.pd_min) || !valid(config.pd_max) || config.pd_min > config.pd_max {
+            return Err(format!(
+                "探知確率の上下限は 0 <= pd_min <= pd_max <= 1 としてください (pd_min: {}, pd_max: {})",
+                config.pd_min, config.pd_max
+            ));
+        }
+        Ok(RadarParams {
+            position: config.position,
+            direction: config.direction,
+            range: config.range,
+            azimuth_range: config.azimuth_range,
+            elevation_range: config.elevation_range,
+            period: config.period,
+            snr_at_max_range: config.snr_at_max_range,
+            atan2_eps: config.atan2_eps,
+            role: config.role,
+            measurement_sigma: config.measurement_sigma,
+            sensor_latency: config.sensor_latency,
+            hysteresis: config.hysteresis,
+            pd_min: config.pd_min,
+            pd_max: config.pd_max,
+            max_track_updates: config.max_track_updates,
+        })
+    }
+}
+
+/// 探知のヒステリシス (M-of-N 判定)
+/// 直近 confirm_n 回のうち confirm_m 回探知で追尾を開始し、
+/// 追尾中は直近 drop_n 回のうち drop_m 回非探知で追尾を終了する
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub struct DetectionHysteresis {
+    pub confirm_m: usize,
+    pub confirm_n: usize,
+    pub drop_m: usize,
+    pub drop_n: usize,
+}
+
+/// レーダの役割
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RadarRole {
+    /// 捜索レーダ: 広域を捜索し、探知したミサイルを追尾レーダへ引き継ぐ
+    #[default]
+    Search,
+    /// 追尾レーダ: 引き継いだミサイルを高精度に計測する
+    Track,
 }
 
+fn default_atan2_eps() -> f64 {
+    1e-6
+}
+
+fn default_pd_max() -> f64 {
+    1.0
+}
+
+/// 原点で +x を向いた探知距離0のレーダ (設定ファイルで省略できる項目は設定ファイルと同じ既定値)
+impl Default for RadarParams {
+    fn default() -> Self {
+        RadarParams {
+            position: [0.0; 3],
+            direction: [1.0, 0.0, 0.0],
+            range: 0.0,
+            azimuth_range: 0.0,
+            elevation_range: 0.0,
+            period: 0.0,
+            snr_at_max_range: None,
+            atan2_eps: default_atan2_eps(),
+            role: RadarRole::default(),
+            measurement_sigma: 0.0,
+            sensor_latency: 0.0,
+            hysteresis: None,
+            pd_min: 0.0,
+            pd_max: default_pd_max(),
+            max_track_updates: None,
+        }
+    }
+}
+
+/// 探知確率の計算に用いる誤警報確率
+const PROBABILITY_OF_FALSE_ALARM: f64 = 1e-6;
+
+/// 再訪問の優先度で機動の大きさを評価する基準加速度 [m/s^2]
+const REVISIT_REFERENCE_ACCELERATION: f64 = 9.81;
+
 /// レーダ本体 (パラメータのみ)
 #[derive(Clone, Debug)]
 pub struct Radar {
     pub params: RadarParams,
 }
 
+/// 目標1つ分の探知履歴と追尾状態
+#[derive(Clone, Debug, Default)]
+struct TrackGate {
+    history: VecDeque<bool>, // 直近の探知結果 (新しいものが末尾)
+    tracked: bool,
+    last_measurement: Option<TrackMeasurement>, // 最後に計測を更新した時刻・位置・速度
+    pending: VecDeque<(f64, [f64; 3])>, // 計測遅れのため報告待ちの計測 (計測時刻, 位置)
+    maneuver: f64,     // 連続する計測の速度差から推定した機動加速度 [m/s^2]
+    since_update: f64, // 最後に計測を更新してからの経過時間 [s]
+    updates: usize,    // 計測の更新回数
+}
+
+/// 追尾目標の計測
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TrackMeasurement {
+    time: f64,
+    position: [f64; 3],
+    velocity: Option<[f64; 3]>, // 前回の計測との差分から推定した速度 (計測が1回だけなら None)
+}
+
+/// レーダの目標ごとの追尾状態 (探知のヒステリシスと再訪問の割り当てに使用)
+#[derive(Clone, Debug, Default)]
+pub struct RadarState {
+    gates: Vec<TrackGate>,
+}
+
+impl RadarState {
+    /// 目標数 targets の追尾状態 (全目標が未追尾)
+    pub fn new(targets: usize) -> Self {
+        RadarState {
+            gates: vec![TrackGate::default(); targets],
+        }
+    }
+
+    /// 目標 target が追尾中か
+    pub fn is_tracked(&self, target: usize) -> bool {
+        self.gates.get(target).is_some_and(|gate| gate.tracked)
+    }
+
+    /// 今回の探知結果 detected を記録し、更新後の追尾状態を返す
+    /// hysteresis が None の場合は探知結果をそのまま追尾状態とする
+    pub fn update(&mut self, target: usize, detected: bool, hysteresis: Option<&DetectionHysteresis>) -> bool {
+        if target >= self.gates.len() {
+            self.gates.resize(target + 1, TrackGate::default());
+        }
+        let gate = &mut self.gates[target];
+        let Some(h) = hysteresis else {
+            gate.tracked = detected;
+            return detected;
+        };
+        gate.history.push_back(detected);
+        while gate.history.len() > h.confirm_n.max(h.drop_n) {
+            gate.history.pop_front();
+        }
+        let count_recent = |n: usize, value: bool| gate.history.iter().rev().take(n).filter(|&&d| d == value).count();
+        if gate.tracked {
+            if count_recent(h.drop_n, false) >= h.drop_m {
+                gate.tracked = false;
+            }
+        } else if count_recent(h.confirm_n, true) >= h.confirm_m {
+            gate.tracked = true;
+        }
+        gate.tracked
+    }
+
+    /// 目標 target の再訪問の優先度
+    /// 最後の更新からの経過時間に、機動の大きさ (基準加速度との比) に応じた重みを掛ける
+    pub fn revisit_priority(&self, target: usize, period: f64) -> f64 {
+        self.gates.get(target).map_or(0.0, |gate| {
+            (gate.since_update + period) * (1.0 + gate.maneuver / REVISIT_REFERENCE_ACCELERATION)
+        })
+    }
+
+    /// 追尾中の目標 tracks のうち、今回の探知処理 (前回から period [s] 後) で計測を更新する目標を選ぶ
+    /// 優先度の高い順 (同じなら番号順) に budget 個まで選び、選ばれなかった目標は経過時間を period 加算する。
+    /// budget が None の場合は全目標を更新する。戻り値は選ばれた目標番号 (番号順)
+    pub fn schedule_revisits(&mut self, tracks: &[usize], budget: Option<usize>, period: f64) -> Vec<usize> {
+        let mut ranked = tracks.to_vec();
+        ranked.sort_by(|&a, &b| self.revisit_priority(b, period).total_cmp(&self.revisit_priority(a, period)));
+        ranked.truncate(budget.unwrap_or(usize::MAX));
+        ranked.sort_unstable();
+        for &target in tracks {
+            if target >= self.gates.len() {
+                self.gates.resize(target + 1, TrackGate::default());
+            }
+            if ranked.binary_search(&target).is_err() {
+                self.gates[target].since_update += period;
+            }
+        }
+        ranked
+    }
+
+    /// 時刻 time の計測 measurement (探知していなければ None) を計測遅れ latency [s] の報告待ちに加え、
+    /// 時刻 time までに報告される計測のうち最新のもの (計測時刻, 位置) を返す
+    /// 古い計測は新しい計測と同時に報告されるため捨てる
+    pub fn delay_measurement(
+        &mut self,
+        target: usize,
+        time: f64,
+        measurement: Option<[f64; 3]>,
+        latency: f64,
+    ) -> Option<(f64, [f64; 3])> {
+        if target >= self.gates.len() {
+            self.gates.resize(target + 1, TrackGate::default());
+        }
+        let pending = &mut self.gates[target].pending;
+        if let Some(position) = measurement {
+            pending.push_back((time, position));
+        }
+        let mut reported = None;
+        // 計測時刻の丸め誤差で報告が1ステップ遅れないよう許容誤差を設ける
+        while pending.front().is_some_and(|&(t, _)| t + latency <= time + 1e-9) {
+            reported = pending.pop_front();
+        }
+        reported
+    }
+
+    /// 目標 target の計測を更新する
+    /// 速度は前回の計測との位置の差分から、機動加速度は連続する速度の推定値の差から推定する
+    pub fn record_measurement(&mut self, target: usize, time: f64, position: [f64; 3]) {
+        if target >= self.gates.len() {
+            self.gates.resize(target + 1, TrackGate::default());
+        }
+        let gate = &mut self.gates[target];
+        let mut velocity = None;
+        if let Some(last) = gate.last_measurement {
+            let elapsed = time - last.time;
+            if elapsed > 0.0 {
+                let v: [f64; 3] = core::array::from_fn(|k| (position[k] - last.position[k]) / elapsed);
+                if let Some(last_velocity) = last.velocity {
+                    let dv: [f64; 3] = core::array::from_fn(|k| v[k] - last_velocity[k]);
+                    gate.maneuver = (dv[0] * dv[0] + dv[1] * dv[1] + dv[2] * dv[2]).sqrt() / elapsed;
+                }
+                velocity = Some(v);
+            } else {
+                velocity = last.velocity;
+            }
+        }
+        gate.last_measurement = Some(TrackMeasurement {
+            time,
+            position,
+            velocity,
+        });
+        gate.since_update = 0.0;
+        gate.updates += 1;
+    }
+
+    /// 目標 target の計測の更新回数
+    pub fn update_count(&self, target: usize) -> usize {
+        self.gates.get(target).map_or(0, |gate| gate.updates)
+    }
+
+    /// 目標 target の最後の計測と推定速度 (計測がなければ None)
+    /// 速度を推定できていない (計測が1回だけの) 場合は速度を0とする
+    pub fn track_estimate(&self, target: usize) -> Option<TrackEstimate> {
+        let last = self.gates.get(target)?.last_measurement?;
+        Some(TrackEstimate {
+            time: last.time,
+            position: last.position,
+            velocity: last.velocity.unwrap_or([0.0; 3]),
+        })
+    }
+
+    /// 最後の計測を推定速度の等速直線運動で時刻 now まで外挿した目標位置 (計測がなければ None)
+    pub fn predicted_position(&self, target: usize, now: f64) -> Option<[f64; 3]> {
+        self.track_estimate(target).map(|estimate| estimate.position_at(now))
+    }
+}
+
+/// 追尾目標の推定 (最後の計測と、計測の差分から推定した速度)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackEstimate {
+    pub time: f64,          // 計測時刻 [s]
+    pub position: [f64; 3], // 計測位置 [m]
+    pub velocity: [f64; 3], // 推定速度 [m/s]
+}
+
+impl TrackEstimate {
+    /// 等速直線運動で時刻 now まで外挿した位置
+    pub fn position_at(&self, now: f64) -> [f64; 3] {
+        let age = now - self.time;
+        core::array::from_fn(|k| self.position[k] + self.velocity[k] * age)
+    }
+}
+
 /// 検出結果
 #[derive(Clone, Debug)]
 pub struct DetectionResult {
@@ -25,6 +341,24 @@ pub struct DetectionResult {
     pub missile_position: Option<[f64; 3]>,
     pub missile_orientation: Option<[f64; 3]>, // [theta, psi, phi]
     pub detection_position: Option<[f64; 3]>,
+    pub snr: Option<f64>, // 信号対雑音比 [dB]
+    pub measurement_sigma: Option<f64>, // 計測誤差の標準偏差 [m]
+    pub timestamp: Option<f64>, // 計測時刻 [s] (None: 時刻なし = 最新の計測とみなす)
+}
+
+impl DetectionResult {
+    /// 探知なし
+    pub fn not_detected() -> DetectionResult {
+        DetectionResult {
+            detected: false,
+            missile_position: None,
+            missile_orientation: None,
+            detection_position: None,
+            snr: None,
+            measurement_sigma: None,
+            timestamp: None,
+        }
+    }
 }
 
 fn vector_sub(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
@@ -41,17 +375,13 @@ fn is_atan2_near_zero(x: f64, y: f64, eps: f64) -> bool {
 }
 
 /// ミサイル検出判定
-pub fn detect_missile(radar: &Radar, missile: &MissileState, eps: f64) -> DetectionResult {
+pub fn detect_missile(radar: &Radar, missile: &MissileState) -> DetectionResult {
+    let eps = radar.params.atan2_eps;
     // 距離判定
     let diff = vector_sub(&missile.position, &radar.params.position);
     let dist = vector_norm(&diff);
     if dist > radar.params.range {
-        return DetectionResult {
-            detected: false,
-            missile_position: None,
-            missile_orientation: None,
-            detection_position: None,
-        };
+        return DetectionResult::not_detected();
     }
 
     // 角度判定 (超簡易バージョン: ここでは厳密な方位角差や仰角差は省略し、
@@ -61,24 +391,14 @@ pub fn detect_missile(radar: &Radar, missile: &MissileState, eps: f64) -> Detect
     let radar_norm = vector_norm(&radar.params.direction);
     if radar_norm < 1e-9 {
         // レーダ方向が無効
-        return DetectionResult {
-            detected: false,
-            missile_position: None,
-            missile_orientation: None,
-            detection_position: None,
-        };
+        return DetectionResult::not_detected();
     }
 
     // atan2 のゼロ近傍チェック
     if is_atan2_near_zero(radar.params.direction[0], radar.params.direction[1], eps) &&
        is_atan2_near_zero(diff[0], diff[1], eps)
     {
-        return DetectionResult {
-            detected: false,
-            missile_position: None,
-            missile_orientation: None,
-            detection_position: None,
-        };
+        return DetectionResult::not_detected();
     }
 
     // ここでは「全部OKだったら検出成功」とする
@@ -88,10 +408,550 @@ pub fn detect_missile(radar: &Radar, missile: &MissileState, eps: f64) -> Detect
         missile_position: Some(missile.position),
         missile_orientation: Some([missile.theta, missile.psi, 0.0]),
         detection_position: Some(radar.params.position),
+        snr: radar.params.snr_at_max_range.map(|snr| radar_snr(snr, radar.params.range, dist)),
+        measurement_sigma: Some(radar.params.measurement_sigma),
+        timestamp: None,
+    }
+}
+
+/// 探知確率を考慮した時刻 time のミサイル検出判定 (draw は [0, 1) の一様乱数)
+/// 探知した場合は計測時刻 time を timestamp に設定する。計測が報告されるまでの遅れ (sensor_latency) は
+/// RadarState::delay_measurement で扱う
+pub fn detect_missile_at(radar: &Radar, missile: &MissileState, time: f64, draw: f64) -> DetectionResult {
+    let mut result = detect_missile_with_probability(radar, missile, draw);
+    if result.detected {
+        result.timestamp = Some(time);
     }
+    result
+}
+
+/// 探知位置に計測誤差 (各軸独立に標準偏差 measurement_sigma の正規乱数) を加える
+/// 探知していない、または標準偏差が0以下の場合は乱数を引かずにそのまま返す
+pub fn add_measurement_noise(mut result: DetectionResult, rng: &mut SeededRng) -> DetectionResult {
+    let sigma = result.measurement_sigma.unwrap_or(0.0);
+    if !result.detected || sigma <= 0.0 {
+        return result;
+    }
+    if let Some(position) = result.missile_position.as_mut() {
+        for p in position.iter_mut() {
+            *p += rng.normal(0.0, sigma);
+        }
+    }
+    result
+}
+
+/// 捜索・追尾レーダ間の引き継ぎを含む探知
+/// track_assignment はミサイルを追尾中の追尾レーダ番号。各レーダの探知判定は detect で行う。
+/// - 追尾中でその追尾レーダが探知できれば追尾レーダの計測を返す
+/// - そうでなければ捜索レーダで探知し、探知したら最も近い追尾レーダへ引き継ぐ
+///   (このステップの計測は捜索レーダのもの。複数の捜索レーダが探知した場合は
+///   fuse_detections でSNRにより重み付けした位置を、SNR最大の探知結果の位置とする)
+///
+/// 戻り値は (探知結果, 更新後の追尾割り当て)
+pub fn search_and_track<F>(
+    radars: &[Radar],
+    missile: &MissileState,
+    track_assignment: Option<usize>,
+    mut detect: F,
+) -> (DetectionResult, Option<usize>)
+where
+    F: FnMut(&Radar, &MissileState) -> DetectionResult,
+{
+    if let Some(track) = track_assignment.and_then(|i| radars.get(i).map(|r| (i, r))) {
+        let result = detect(track.1, missile);
+        if result.detected {
+            return (result, Some(track.0));
+        }
+    }
+
+    // 捜索レーダによる探知 (探知した全レーダの計測を融合)
+    let searches: Vec<DetectionResult> = radars
+        .iter()
+        .filter(|r| r.params.role == RadarRole::Search)
+        .map(|r| detect(r, missile))
+        .filter(|result| result.detected)
+        .collect();
+    let Some(fused) = fuse_detections(&searches) else {
+        return (DetectionResult::not_detected(), None);
+    };
+    let snr = |result: &DetectionResult| result.snr.unwrap_or(f64::NEG_INFINITY);
+    let mut result = searches
+        .into_iter()
+        .max_by(|a, b| snr(a).total_cmp(&snr(b)))
+        .unwrap_or_else(DetectionResult::not_detected);
+    result.missile_position = Some(fused);
+
+    // 最も近い追尾レーダへ引き継ぐ
+    let handover = radars
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.params.role == RadarRole::Track)
+        .map(|(i, r)| (i, vector_norm(&vector_sub(&missile.position, &r.params.position))))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i);
+    (result, handover)
+}
+
+/// レーダ方程式によるSNR [dB]
+/// 受信電力は距離の4乗に反比例するため SNR(R) = SNR(R_max) + 40 log10(R_max / R)
+pub fn radar_snr(snr_at_max_range: f64, max_range: f64, distance: f64) -> f64 {
+    snr_at_max_range + 40.0 * (max_range / distance.max(1e-9)).log10()
+}
+
+/// 距離 distance [m] の目標の探知確率
+/// Swerling I 目標の Pd = Pfa^(1 / (1 + SNR)) を pd_min〜pd_max に制限する。
+/// 探知距離外は 0、SNR を計算しない (snr_at_max_range が None) 場合は pd_max とする。
+/// 上下限は設定ファイルの読み込み時に検査するが、不正な値 (pd_min > pd_max, NaN) でも panic せず pd_max を優先する
+pub fn detection_probability(params: &RadarParams, distance: f64) -> f64 {
+    if distance > params.range {
+        return 0.0;
+    }
+    let pd = match params.snr_at_max_range {
+        Some(snr) => {
+            let snr_linear = 10f64.powf(radar_snr(snr, params.range, distance) / 10.0);
+            PROBABILITY_OF_FALSE_ALARM.powf(1.0 / (1.0 + snr_linear))
+        }
+        None => 1.0,
+    };
+    pd.max(params.pd_min).min(params.pd_max)
+}
+
+/// 探知確率を考慮したミサイル検出判定
+/// draw は [0, 1) の一様乱数。幾何的に探知可能で draw が探知確率未満なら探知とする
+pub fn detect_missile_with_probability(radar: &Radar, missile: &MissileState, draw: f64) -> DetectionResult {
+    let result = detect_missile(radar, missile);
+    if !result.detected {
+        return result;
+    }
+    let distance = vector_norm(&vector_sub(&missile.position, &radar.params.position));
+    if draw < detection_probability(&radar.params, distance) {
+        result
+    } else {
+        DetectionResult::not_detected()
+    }
+}
+
+/// 複数レーダの探知位置をSNRで重み付けして融合する
+/// 重みは線形SNR (10^(dB/10))。SNRの無い探知は重み1とする。
+/// 探知が1つも無い場合は None
+pub fn fuse_detections(results: &[DetectionResult]) -> Option<[f64; 3]> {
+    let mut weighted = [0.0; 3];
+    let mut total_weight = 0.0;
+    for result in results.iter().filter(|r| r.detected) {
+        if let Some(position) = result.missile_position {
+            let weight = result.snr.map_or(1.0, |snr| 10f64.powf(snr / 10.0));
+            for i in 0..3 {
+                weighted[i] += weight * position[i];
+            }
+            total_weight += weight;
+        }
+    }
+    if total_weight <= 0.0 {
+        return None;
+    }
+    Some([
+        weighted[0] / total_weight,
+        weighted[1] / total_weight,
+        weighted[2] / total_weight,
+    ])
 }
 
 /// レーダが「発射指示」を出すかどうか
 pub fn generate_fire_command(result: &DetectionResult) -> bool {
     result.detected
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_radar() -> Radar {
+        Radar {
+            params: RadarParams {
+                direction: [1.0, 0.0, 0.0],
+                range: 100000.0,
+                azimuth_range: 45.0,
+                elevation_range: 30.0,
+                period: 0.1,
+                snr_at_max_range: Some(13.0),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn missile_at(position: [f64; 3]) -> MissileState {
+        MissileState {
+            mass: 1000.0,
+            position,
+            ..Default::default()
+        }
+    }
+
+    fn detection(position: [f64; 3], snr: f64) -> DetectionResult {
+        DetectionResult {
+            detected: true,
+            missile_position: Some(position),
+            missile_orientation: None,
+            detection_position: None,
+            snr: Some(snr),
+            measurement_sigma: None,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_snr_decreases_with_range() {
+        let radar = test_radar();
+        let near = detect_missile(&radar, &missile_at([10000.0, 0.0, 1000.0]));
+        let far = detect_missile(&radar, &missile_at([90000.0, 0.0, 1000.0]));
+
+        assert!(near.snr.unwrap() > far.snr.unwrap());
+        // 探知距離の端では設定値、半分の距離で +12 dB (40 log10 2)
+        assert!((radar_snr(13.0, 100000.0, 100000.0) - 13.0).abs() < 1e-12);
+        assert!((radar_snr(13.0, 100000.0, 50000.0) - (13.0 + 40.0 * 2f64.log10())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_snr_absent_when_not_configured() {
+        let mut radar = test_radar();
+        radar.params.snr_at_max_range = None;
+        let result = detect_missile(&radar, &missile_at([10000.0, 0.0, 1000.0]));
+        assert!(result.detected);
+        assert!(result.snr.is_none());
+    }
+
+    #[test]
+    fn test_fusion_favors_higher_snr() {
+        let strong = detection([1000.0, 0.0, 0.0], 30.0);
+        let weak = detection([1100.0, 0.0, 0.0], 10.0);
+
+        let fused = fuse_detections(&[strong, weak]).unwrap();
+
+        // 線形重み 1000:10 → 1000 に近い値
+        assert!((fused[0] - 1000.0).abs() < (fused[0] - 1100.0).abs());
+        assert!((fused[0] - (1000.0 * 1000.0 + 10.0 * 1100.0) / 1010.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fusion_without_detections() {
+        assert!(fuse_detections(&[]).is_none());
+    }
+
+    #[test]
+    fn test_atan2_eps_per_radar() {
+        // 真上を向いたレーダのほぼ直上 (水平成分 10 m) にミサイル
+        let mut radar = test_radar();
+        radar.params.direction = [0.0, 0.0, 1.0];
+        let missile = missile_at([10.0, 10.0, 5000.0]);
+
+        radar.params.atan2_eps = 1e-6;
+        assert!(detect_missile(&radar, &missile).detected);
+
+        radar.params.atan2_eps = 100.0;
+        assert!(!detect_missile(&radar, &missile).detected);
+    }
+
+    #[test]
+    fn test_search_radar_hands_over_to_track_radar() {
+        let search = Radar {
+            params: RadarParams {
+                measurement_sigma: 100.0,
+                sensor_latency: 0.0,
+                ..test_radar().params
+            },
+        };
+        let far_track = Radar {
+            params: RadarParams {
+                position: [-50000.0, 0.0, 0.0],
+                role: RadarRole::Track,
+                measurement_sigma: 5.0,
+                sensor_latency: 0.0,
+                ..test_radar().params
+            },
+        };
+        let near_track = Radar {
+            params: RadarParams {
+                position: [5000.0, 0.0, 0.0],
+                role: RadarRole::Track,
+                measurement_sigma: 5.0,
+                sensor_latency: 0.0,
+                ..test_radar().params
+            },
+        };
+        let radars = vec![far_track, search, near_track];
+        let missile = missile_at([20000.0, 0.0, 1000.0]);
+
+        // 最初は捜索レーダが探知し、最も近い追尾レーダへ引き継ぐ
+        let (first, assignment) = search_and_track(&radars, &missile, None, detect_missile);
+        assert!(first.detected);
+        assert_eq!(first.measurement_sigma, Some(100.0));
+        assert_eq!(assignment, Some(2));
+
+        // 以降は追尾レーダが低い計測誤差で追尾
+        let (tracked, assignment) = search_and_track(&radars, &missile, assignment, detect_missile);
+        assert!(tracked.detected);
+        assert_eq!(tracked.detection_position, Some([5000.0, 0.0, 0.0]));
+        assert!(tracked.measurement_sigma.unwrap() < first.measurement_sigma.unwrap());
+        assert_eq!(assignment, Some(2));
+    }
+
+    #[test]
+    fn test_search_and_track_without_detection() {
+        let radars = vec![test_radar()];
+        let (result, assignment) = search_and_track(&radars, &missile_at([500000.0, 0.0, 1000.0]), None, detect_missile);
+        assert!(!result.detected);
+        assert_eq!(assignment, None);
+    }
+
+    #[test]
+    fn test_sensor_latency_reports_stale_measurement() {
+        let result = detect_missile_at(&test_radar(), &missile_at([10000.0, 0.0, 1000.0]), 3.0, 0.0);
+        assert_eq!(result.timestamp, Some(3.0));
+
+        // 計測は sensor_latency 経過後に報告される
+        let mut state = RadarState::new(1);
+        let position = |t: f64| [10000.0 - 200.0 * t, 0.0, 1000.0];
+        let reported: Vec<Option<(f64, [f64; 3])>> = (0..10)
+            .map(|k| {
+                let t = k as f64 * 0.1;
+                state.delay_measurement(0, t, Some(position(t)), 0.5)
+            })
+            .collect();
+        assert!(reported[..5].iter().all(|r| r.is_none()));
+        assert_eq!(reported[5], Some((0.0, position(0.0))));
+        assert_eq!(reported[9], Some((0.4, position(0.4))));
+        // 探知しなくなっても報告待ちの計測は報告される
+        assert_eq!(state.delay_measurement(0, 1.0, None, 0.5), Some((0.5, position(0.5))));
+    }
+
+    #[test]
+    fn test_zero_sensor_latency_matches_current_position() {
+        let mut state = RadarState::new(1);
+        assert_eq!(state.delay_measurement(0, 3.0, Some([10000.0, 0.0, 1000.0]), 0.0), Some((3.0, [10000.0, 0.0, 1000.0])));
+        assert_eq!(state.delay_measurement(0, 3.1, None, 0.0), None);
+    }
+
+    #[test]
+    fn test_predicted_position_uses_finite_difference_velocity() {
+        let mut state = RadarState::new(1);
+        state.record_measurement(0, 1.0, [0.0, 0.0, 100.0]);
+        assert_eq!(state.predicted_position(0, 1.5), Some([0.0, 0.0, 100.0]));
+        state.record_measurement(0, 2.0, [10.0, 0.0, 100.0]);
+        assert_eq!(state.predicted_position(0, 2.5), Some([15.0, 0.0, 100.0]));
+    }
+
+    /// 探知距離の境界付近を前後に揺れる目標の生の探知結果 (約 1/3 の確率で探知を失う)
+    fn flickering_detections(radar: &Radar, steps: usize) -> Vec<bool> {
+        (0..steps)
+            .map(|k| {
+                let jitter = if (k * 7 + 3) % 10 < 3 { 50.0 } else { -50.0 };
+                detect_missile(radar, &missile_at([radar.params.range + jitter, 0.0, 0.0])).detected
+            })
+            .collect()
+    }
+
+    fn transitions(states: &[bool]) -> usize {
+        states.windows(2).filter(|w| w[0] != w[1]).count()
+    }
+
+    #[test]
+    fn test_hysteresis_stabilizes_flickering_track() {
+        let hysteresis = DetectionHysteresis {
+            confirm_m: 2,
+            confirm_n: 3,
+            drop_m: 3,
+            drop_n: 4,
+        };
+        let radar = Radar {
+            params: RadarParams {
+                hysteresis: Some(hysteresis),
+                ..test_radar().params
+            },
+        };
+        let raw = flickering_detections(&radar, 50);
+        assert!(transitions(&raw) > 10, "raw {:?}", raw);
+
+        let mut state = RadarState::new(1);
+        let tracked: Vec<bool> = raw.iter().map(|&d| state.update(0, d, radar.params.hysteresis.as_ref())).collect();
+        // 追尾開始後は途切れない
+        let first = tracked.iter().position(|&t| t).unwrap();
+        assert!(first <= 3);
+        assert!(tracked[first..].iter().all(|&t| t));
+        assert_eq!(transitions(&tracked), 1);
+
+        // 目標が探知範囲外へ出れば drop_m 回の非探知で追尾を終了する
+        let after: Vec<bool> = (0..3).map(|_| state.update(0, false, Some(&hysteresis))).collect();
+        assert_eq!(after, [true, true, false]);
+        assert!(!state.is_tracked(0));
+    }
+
+    #[test]
+    fn test_without_hysteresis_track_follows_raw_detections() {
+        let radar = test_radar();
+        let raw = flickering_detections(&radar, 20);
+        let mut state = RadarState::new(1);
+        let tracked: Vec<bool> = raw.iter().map(|&d| state.update(0, d, None)).collect();
+        assert_eq!(tracked, raw);
+    }
+
+    fn clamped_radar() -> Radar {
+        Radar {
+            params: RadarParams {
+                pd_min: 0.6,
+                pd_max: 0.95,
+                max_track_updates: None,
+                ..test_radar().params
+            },
+        }
+    }
+
+    #[test]
+    fn test_pd_clamped_to_floor_and_ceiling() {
+        let radar = clamped_radar();
+        // 至近距離でも pd_max、探知範囲の端でも pd_min を下回らない
+        assert_eq!(detection_probability(&radar.params, 0.0), 0.95);
+        assert_eq!(detection_probability(&radar.params, 1000.0), 0.95);
+        let unclamped_edge = detection_probability(&test_radar().params, radar.params.range);
+        assert!(unclamped_edge < 0.6, "edge pd {}", unclamped_edge);
+        assert_eq!(detection_probability(&radar.params, radar.params.range), 0.6);
+        // 探知範囲外は 0
+        assert_eq!(detection_probability(&radar.params, radar.params.range + 1.0), 0.0);
+        for k in 0..=100 {
+            let pd = detection_probability(&radar.params, radar.params.range * k as f64 / 100.0);
+            assert!((0.6..=0.95).contains(&pd));
+        }
+    }
+
+    #[test]
+    fn test_detection_rate_respects_pd_limits_under_fixed_seed() {
+        let radar = clamped_radar();
+        let n = 5000;
+        let rate = |position: [f64; 3]| {
+            let missile = missile_at(position);
+            let mut rng = SeededRng::new(42);
+            let hits = (0..n)
+                .filter(|_| detect_missile_with_probability(&radar, &missile, rng.next_f64()).detected)
+                .count();
+            hits as f64 / n as f64
+        };
+        let near = rate([10.0, 0.0, 0.0]);
+        let edge = rate([radar.params.range - 1.0, 0.0, 0.0]);
+        assert!(near < 0.97 && near > 0.93, "near {}", near);
+        assert!(edge > 0.57 && edge < 0.63, "edge {}", edge);
+        assert_eq!(rate([radar.params.range + 1.0, 0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_revisit_scheduler_favors_maneuvering_track() {
+        // 目標0は 50 m/s^2 で旋回し、目標1は等速直進。1周期に1目標しか更新できない
+        let period = 0.1;
+        let mut state = RadarState::new(2);
+        for step in 0..200 {
+            let t = step as f64 * period;
+            let (s, c) = (0.5 * t).sin_cos();
+            let positions = [[200.0 * s, 200.0 * (1.0 - c), 0.0], [300.0 * t, 0.0, 0.0]];
+            for target in state.schedule_revisits(&[0, 1], Some(1), period) {
+                state.record_measurement(target, t, positions[target]);
+            }
+        }
+        let (maneuvering, benign) = (state.update_count(0), state.update_count(1));
+        assert_eq!(maneuvering + benign, 200);
+        assert!(maneuvering > 3 * benign, "maneuvering {} / benign {}", maneuvering, benign);
+        // 機動しない目標も経過時間により再訪問される
+        assert!(benign > 0);
+    }
+
+    #[test]
+    fn test_revisit_scheduler_without_budget_updates_all() {
+        let mut state = RadarState::new(3);
+        assert_eq!(state.schedule_revisits(&[2, 0], None, 0.1), vec![0, 2]);
+        state.record_measurement(1, 1.0, [0.0, 0.0, 100.0]);
+        assert!(state.predicted_position(1, 1.5).is_some());
+        assert_eq!(state.predicted_position(0, 1.5), None);
+    }
+
+    #[test]
+    fn test_invalid_pd_limits_rejected_on_load() {
+        let yaml = |limits: &str| {
+            format!(
+                "position: [0.0, 0.0, 0.0]\ndirection: [1.0, 0.0, 0.0]\nrange: 1000.0\nazimuth_range: 45.0\n\
+                 elevation_range: 30.0\nperiod: 0.1\nsnr_at_max_range: null\n{}",
+                limits
+            )
+        };
+        let params: RadarParams = serde_yaml::from_str(&yaml("pd_min: 0.6\npd_max: 0.95\n")).unwrap();
+        assert_eq!((params.pd_min, params.pd_max), (0.6, 0.95));
+        let defaults: RadarParams = serde_yaml::from_str(&yaml("")).unwrap();
+        assert_eq!((defaults.pd_min, defaults.pd_max, defaults.atan2_eps), (0.0, 1.0, 1e-6));
+        for limits in ["pd_min: 0.9\npd_max: 0.5\n", "pd_max: 1.5\n", "pd_min: -0.1\n", "pd_max: .nan\n"] {
+            assert!(serde_yaml::from_str::<RadarParams>(&yaml(limits)).is_err(), "{}", limits);
+        }
+
+        // コードで直接与えた不正な上下限でも panic しない
+        let inverted = RadarParams {
+            pd_min: 0.9,
+            pd_max: 0.5,
+            ..test_radar().params
+        };
+        assert_eq!(detection_probability(&inverted, 1000.0), 0.5);
+    }
+
+    #[test]
+    fn test_measurement_noise_statistics() {
+        let radar = Radar {
+            params: RadarParams {
+                measurement_sigma: 20.0,
+                ..test_radar().params
+            },
+        };
+        let truth = [10000.0, 0.0, 1000.0];
+        let mut rng = SeededRng::new(3);
+        let n = 20000;
+        let errors: Vec<[f64; 3]> = (0..n)
+            .map(|_| {
+                let result = add_measurement_noise(detect_missile(&radar, &missile_at(truth)), &mut rng);
+                let p = result.missile_position.unwrap();
+                [p[0] - truth[0], p[1] - truth[1], p[2] - truth[2]]
+            })
+            .collect();
+        for axis in 0..3 {
+            let mean = errors.iter().map(|e| e[axis]).sum::<f64>() / n as f64;
+            let sigma = (errors.iter().map(|e| (e[axis] - mean).powi(2)).sum::<f64>() / n as f64).sqrt();
+            assert!(mean.abs() < 0.5, "axis {} mean {}", axis, mean);
+            assert!((sigma - 20.0).abs() < 0.5, "axis {} sigma {}", axis, sigma);
+        }
+
+        // 誤差0・非探知では乱数を引かず位置も変えない
+        let before = rng.clone();
+        let exact = add_measurement_noise(detect_missile(&test_radar(), &missile_at(truth)), &mut rng);
+        assert_eq!(exact.missile_position, Some(truth));
+        let missed = add_measurement_noise(DetectionResult::not_detected(), &mut rng);
+        assert!(!missed.detected);
+        assert_eq!(rng, before);
+    }
+
+    #[test]
+    fn test_search_detections_are_fused_by_snr() {
+        let strong = test_radar();
+        let weak = Radar {
+            params: RadarParams {
+                position: [0.0, 1.0, 0.0],
+                ..test_radar().params
+            },
+        };
+        // レーダごとに異なる計測位置・SNRを返す探知
+        let detect = |radar: &Radar, _: &MissileState| {
+            if radar.params.position[1] == 0.0 {
+                detection([1000.0, 0.0, 0.0], 30.0)
+            } else {
+                detection([1100.0, 0.0, 0.0], 10.0)
+            }
+        };
+        let (result, assignment) = search_and_track(&[weak, strong], &missile_at([1050.0, 0.0, 0.0]), None, detect);
+        assert!(result.detected);
+        assert_eq!(result.snr, Some(30.0));
+        let fused = fuse_detections(&[detection([1000.0, 0.0, 0.0], 30.0), detection([1100.0, 0.0, 0.0], 10.0)]);
+        assert_eq!(result.missile_position, fused);
+        assert_eq!(assignment, None);
+    }
+}
@@ -0,0 +1,223 @@
+/// 追尾フィルタの運動モデル
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MotionModel {
+    /// 等速度モデル (状態: 位置・速度)。機動しない目標向け
+    ConstantVelocity,
+    /// 等加速度モデル (状態: 位置・速度・加速度)。機動目標向け
+    ConstantAcceleration,
+}
+
+impl MotionModel {
+    /// 1軸あたりの状態数
+    fn dimension(self) -> usize {
+        match self {
+            MotionModel::ConstantVelocity => 2,
+            MotionModel::ConstantAcceleration => 3,
+        }
+    }
+}
+
+/// 1軸分のカルマンフィルタの状態 (等速度モデルでは加速度成分を使わない)
+#[derive(Clone, Debug)]
+struct AxisFilter {
+    x: [f64; 3],      // 状態 [位置, 速度, 加速度]
+    p: [[f64; 3]; 3], // 誤差共分散
+}
+
+/// 位置計測からミサイルの位置・速度 (・加速度) を推定するカルマンフィルタ追尾器
+/// 各軸を独立に扱い、軸ごとにプロセスノイズ (白色雑音のスペクトル密度) を設定できる。
+/// 等速度モデルは加速度、等加速度モデルは加加速度を白色雑音とみなす。
+#[derive(Clone, Debug)]
+pub struct KalmanTracker {
+    model: MotionModel,
+    process_noise: [f64; 3],  // 軸ごとのプロセスノイズ強度
+    measurement_variance: f64, // 位置計測誤差の分散 [m^2]
+    axes: [AxisFilter; 3],
+    initialized: bool,
+}
+
+/// 初期化時の速度・加速度の分散 (事前情報なし)
+const INITIAL_VARIANCE: f64 = 1e8;
+
+impl KalmanTracker {
+    /// 追尾器を生成する
+    /// process_noise は x, y, z 軸ごとのプロセスノイズ強度、measurement_sigma は位置計測誤差の標準偏差 [m]
+    pub fn new(model: MotionModel, process_noise: [f64; 3], measurement_sigma: f64) -> Self {
+        let axis = AxisFilter {
+            x: [0.0; 3],
+            p: [[0.0; 3]; 3],
+        };
+        KalmanTracker {
+            model,
+            process_noise,
+            measurement_variance: measurement_sigma * measurement_sigma,
+            axes: [axis.clone(), axis.clone(), axis],
+            initialized: false,
+        }
+    }
+
+    pub fn model(&self) -> MotionModel {
+        self.model
+    }
+
+    /// 推定位置 [m]
+    pub fn position(&self) -> [f64; 3] {
+        core::array::from_fn(|k| self.axes[k].x[0])
+    }
+
+    /// 推定速度 [m/s]
+    pub fn velocity(&self) -> [f64; 3] {
+        core::array::from_fn(|k| self.axes[k].x[1])
+    }
+
+    /// 推定加速度 [m/s^2] (等速度モデルでは常に0)
+    pub fn acceleration(&self) -> [f64; 3] {
+        core::array::from_fn(|k| self.axes[k].x[2])
+    }
+
+    /// 予測と計測更新を1回行い、更新後の推定位置を返す
+    /// 最初の計測では位置のみで初期化する (速度・加速度は0、分散は大きな値)
+    pub fn update(&mut self, measurement: [f64; 3], dt: f64) -> [f64; 3] {
+        let n = self.model.dimension();
+        if !self.initialized {
+            for (axis, z) in self.axes.iter_mut().zip(measurement) {
+                axis.x = [z, 0.0, 0.0];
+                axis.p = [[0.0; 3]; 3];
+                axis.p[0][0] = self.measurement_variance;
+                for i in 1..n {
+                    axis.p[i][i] = INITIAL_VARIANCE;
+                }
+            }
+            self.initialized = true;
+            return self.position();
+        }
+
+        let f = transition(n, dt);
+        for (k, axis) in self.axes.iter_mut().enumerate() {
+            let q = process_covariance(n, dt, self.process_noise[k]);
+
+            // 予測: x = F x, P = F P F^T + Q
+            // (等速度モデルでは F, Q の加速度成分が0のため、3x3 のまま計算しても加速度は0に保たれる)
+            let mut x: [f64; 3] = core::array::from_fn(|i| (0..3).map(|j| f[i][j] * axis.x[j]).sum());
+            let fp: [[f64; 3]; 3] =
+                core::array::from_fn(|i| core::array::from_fn(|j| (0..3).map(|l| f[i][l] * axis.p[l][j]).sum()));
+            let mut p: [[f64; 3]; 3] = core::array::from_fn(|i| {
+                core::array::from_fn(|j| (0..3).map(|l| fp[i][l] * f[j][l]).sum::<f64>() + q[i][j])
+            });
+
+            // 計測更新 (H = [1, 0, 0])
+            let s = p[0][0] + self.measurement_variance;
+            let gain: [f64; 3] = core::array::from_fn(|i| p[i][0] / s);
+            let innovation = measurement[k] - x[0];
+            let row0 = p[0];
+            for (i, (x_i, p_i)) in x.iter_mut().zip(p.iter_mut()).enumerate() {
+                *x_i += gain[i] * innovation;
+                for (p_ij, row0_j) in p_i.iter_mut().zip(row0) {
+                    *p_ij -= gain[i] * row0_j;
+                }
+            }
+            axis.x = x;
+            axis.p = p;
+        }
+        self.position()
+    }
+}
+
+/// 状態遷移行列
+fn transition(n: usize, dt: f64) -> [[f64; 3]; 3] {
+    let mut f = [[1.0, dt, 0.5 * dt * dt], [0.0, 1.0, dt], [0.0, 0.0, 1.0]];
+    if n == 2 {
+        f[0][2] = 0.0;
+        f[1][2] = 0.0;
+        f[2][2] = 0.0;
+    }
+    f
+}
+
+/// 離散化したプロセスノイズ共分散 (等速度: 白色加速度、等加速度: 白色加加速度)
+fn process_covariance(n: usize, dt: f64, q: f64) -> [[f64; 3]; 3] {
+    let (dt2, dt3) = (dt * dt, dt * dt * dt);
+    if n == 2 {
+        [
+            [q * dt3 / 3.0, q * dt2 / 2.0, 0.0],
+            [q * dt2 / 2.0, q * dt, 0.0],
+            [0.0, 0.0, 0.0],
+        ]
+    } else {
+        let (dt4, dt5) = (dt3 * dt, dt3 * dt2);
+        [
+            [q * dt5 / 20.0, q * dt4 / 8.0, q * dt3 / 6.0],
+            [q * dt4 / 8.0, q * dt3 / 3.0, q * dt2 / 2.0],
+            [q * dt3 / 6.0, q * dt2 / 2.0, q * dt],
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 再現可能な擬似計測雑音 ([-sigma, sigma] の一様分布に近い系列)
+    fn noise(k: usize, axis: usize, sigma: f64) -> f64 {
+        let h = (k as u64 * 2654435761 + axis as u64 * 40503) % 1000;
+        sigma * (h as f64 / 500.0 - 1.0)
+    }
+
+    /// 真の軌道 truth(t) を計測雑音付きで追尾し、後半の平均位置誤差 [m] を返す
+    fn mean_tracking_error(tracker: &mut KalmanTracker, truth: impl Fn(f64) -> [f64; 3]) -> f64 {
+        let dt = 0.1;
+        let steps = 300;
+        let mut total = 0.0;
+        for k in 0..steps {
+            let t = k as f64 * dt;
+            let actual = truth(t);
+            let measurement = core::array::from_fn(|axis| actual[axis] + noise(k, axis, 10.0));
+            let estimate = tracker.update(measurement, dt);
+            if k >= steps / 2 {
+                let error: f64 = (0..3).map(|i| (estimate[i] - actual[i]).powi(2)).sum();
+                total += error.sqrt();
+            }
+        }
+        total / (steps / 2) as f64
+    }
+
+    fn trackers() -> (KalmanTracker, KalmanTracker) {
+        (
+            KalmanTracker::new(MotionModel::ConstantVelocity, [0.1; 3], 10.0),
+            KalmanTracker::new(MotionModel::ConstantAcceleration, [0.1; 3], 10.0),
+        )
+    }
+
+    #[test]
+    fn test_constant_velocity_tracks_straight_line_better() {
+        let straight = |t: f64| [1000.0 + 300.0 * t, -200.0 * t, 5000.0 + 50.0 * t];
+        let (mut cv, mut ca) = trackers();
+        let cv_error = mean_tracking_error(&mut cv, straight);
+        let ca_error = mean_tracking_error(&mut ca, straight);
+        assert!(cv_error < ca_error, "CV {} / CA {}", cv_error, ca_error);
+        assert!((cv.velocity()[0] - 300.0).abs() < 5.0);
+        assert_eq!(cv.acceleration(), [0.0; 3]);
+    }
+
+    #[test]
+    fn test_constant_acceleration_tracks_maneuver_better() {
+        // y 方向に 30 m/s^2 で旋回機動する目標
+        let maneuver = |t: f64| [1000.0 + 300.0 * t, 15.0 * t * t, 5000.0];
+        let (mut cv, mut ca) = trackers();
+        let cv_error = mean_tracking_error(&mut cv, maneuver);
+        let ca_error = mean_tracking_error(&mut ca, maneuver);
+        assert!(ca_error < cv_error, "CV {} / CA {}", cv_error, ca_error);
+        assert!((ca.acceleration()[1] - 30.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_per_axis_process_noise() {
+        // z 軸だけプロセスノイズを大きくすると、z 軸は計測に強く追従する
+        let mut tracker = KalmanTracker::new(MotionModel::ConstantVelocity, [0.01, 0.01, 1.0e6], 10.0);
+        tracker.update([0.0, 0.0, 0.0], 0.1);
+        let estimate = tracker.update([100.0, 100.0, 100.0], 0.1);
+        assert!(estimate[2] > 99.0);
+        assert!(estimate[0] < estimate[2]);
+        assert_eq!(estimate[0], estimate[1]);
+    }
+}
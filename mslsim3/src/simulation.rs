@@ -0,0 +1,2166 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::math::integrator::AdamsBashforthIntegrator;
+use crate::math::low_pass_filter::LowPassFilter;
+use crate::math::random::SeededRng;
+use crate::models;
+use crate::models::fire_control::{
+    c2_decision_ready, classify_intercept, filter_by_flyout, filter_by_min_altitude, impact_damages, prioritize_targets, select_target, DamageScaling,
+    DefendedPoint, FlyoutTable, ImpactDamage, InterceptTiming,
+};
+use crate::models::interceptor::{
+    closest_approach, first_contact_fraction, new_interceptor_integrators, fuze_detonation, guidance_target, is_homing_saturated, is_thrusting, retarget, retargeted_state, launch_interceptor, relative_geometry, resolve_interceptions, ImpactPrecedence,
+    Interceptor, InterceptorParams, Launcher, RelGeom, ResolutionMode,
+};
+use crate::models::missile::{
+    check_coast_conservation, check_collision as check_missile_collision, ground_crossing_time, impact_angle,
+    is_coasting, impact_point, Missile, MissileParams, MissileState,
+};
+use crate::models::radar::{
+    add_measurement_noise, detect_missile_at, generate_fire_command, search_and_track, Radar,
+    RadarParams, RadarState, TrackEstimate,
+};
+use crate::utils::yaml_parser::Scenario;
+use crate::utils::clock::{Clock, SystemClock};
+use crate::utils::event_log::RunMetadata;
+
+/// シミュレーション中に発生する離散イベント
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimEvent {
+    /// 迎撃ミサイルの発射指示
+    Launch { time: f64, interceptor: usize, missile: usize },
+    /// 迎撃成功
+    Intercept { time: f64, interceptor: usize, missile: usize },
+    /// ミサイルの地表着弾 (時刻・位置はステップ内で補間した地表交差点)
+    GroundImpact {
+        time: f64,
+        missile: usize,
+        position: [f64; 3],
+        velocity: [f64; 3], // 着弾速度 [m/s]
+        impact_angle: f64,  // 水平面からの着弾角 [rad]
+    },
+    /// 迎撃ミサイルの無力化 (最低交戦高度を下回った、近接信管が迎撃判定距離外で起爆した、
+    /// または目標が撃破され再割り当てできる目標がなかった)
+    Dud { time: f64, interceptor: usize },
+    /// 目標が撃破された迎撃ミサイルの追尾対象の再割り当て
+    Retarget { time: f64, interceptor: usize, missile: usize },
+}
+
+impl SimEvent {
+    /// 発生時刻 [s]
+    pub fn time(&self) -> f64 {
+        match self {
+            SimEvent::Launch { time, .. }
+            | SimEvent::Intercept { time, .. }
+            | SimEvent::GroundImpact { time, .. }
+            | SimEvent::Dud { time, .. }
+            | SimEvent::Retarget { time, .. } => *time,
+        }
+    }
+
+    /// イベント種別名
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimEvent::Launch { .. } => "launch",
+            SimEvent::Intercept { .. } => "intercept",
+            SimEvent::GroundImpact { .. } => "impact",
+            SimEvent::Dud { .. } => "dud",
+            SimEvent::Retarget { .. } => "retarget",
+        }
+    }
+
+    /// 関係する迎撃ミサイル番号
+    pub fn interceptor(&self) -> Option<usize> {
+        match self {
+            SimEvent::Launch { interceptor, .. }
+            | SimEvent::Intercept { interceptor, .. }
+            | SimEvent::Dud { interceptor, .. }
+            | SimEvent::Retarget { interceptor, .. } => Some(*interceptor),
+            SimEvent::GroundImpact { .. } => None,
+        }
+    }
+
+    /// 関係するミサイル番号
+    pub fn missile(&self) -> Option<usize> {
+        match self {
+            SimEvent::Launch { missile, .. }
+            | SimEvent::Intercept { missile, .. }
+            | SimEvent::GroundImpact { missile, .. }
+            | SimEvent::Retarget { missile, .. } => Some(*missile),
+            SimEvent::Dud { .. } => None,
+        }
+    }
+}
+
+/// 外部からの制御指令 (閉ループ実験・独自のオートパイロット用)
+/// 推力・ピッチ角は状態を書き換えるため以降も保持され、誘導指令は次のステップのみ有効
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ControlCommand {
+    /// ミサイルの推力 [N]
+    MissileThrust { missile: usize, thrust: f64 },
+    /// ミサイルのピッチ角 [rad] (重力ターンの発射プログラムがある場合はそちらが優先)
+    MissilePitch { missile: usize, theta: f64 },
+    /// 迎撃ミサイルの推力 [N]
+    InterceptorThrust { interceptor: usize, thrust: f64 },
+    /// 迎撃ミサイルの誘導加速度指令 [m/s^2] (誘導則の代わりに用いる)
+    GuidanceOverride { interceptor: usize, acceleration: [f64; 3] },
+}
+
+/// 迎撃ミサイルと目標の最接近
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MissDistance {
+    pub distance: f64, // 最小距離 [m]
+    pub time: f64,     // 最接近時刻 [s] (ステップ内で補間)
+}
+
+/// 迎撃の記録
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InterceptRecord {
+    pub time: f64,
+    pub interceptor: usize,
+    pub missile: usize,
+    pub position: [f64; 3],      // 迎撃時のミサイル位置 [m]
+    pub timing: InterceptTiming, // 防衛線に対する迎撃のタイミング
+    pub remaining_mass_fraction: f64, // 迎撃時の迎撃ミサイルの質量 / 初期質量 (燃料の余裕)
+    pub thrusting: bool,              // 迎撃時に迎撃ミサイルが推力を出していたか
+}
+
+/// 実行結果の集計
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunSummary {
+    /// 迎撃ミサイルごとの目標への最接近 (迎撃の成否によらない。未発射は None)
+    pub min_miss_distances: Vec<Option<MissDistance>>,
+    /// 迎撃の記録 (発生順)
+    pub intercepts: Vec<InterceptRecord>,
+    /// 防衛線を越えてからの迎撃数 (着弾と同様に防御失敗とみなす)
+    pub late_intercepts: usize,
+    /// 防護範囲に着弾したミサイルの着弾点と被害半径
+    pub impact_damages: Vec<ImpactDamage>,
+}
+
+impl RunSummary {
+    /// 迎撃ミサイル全体での最小ミス距離 [m] (発射していなければ None)
+    pub fn best_miss_distance(&self) -> Option<f64> {
+        self.min_miss_distances
+            .iter()
+            .flatten()
+            .map(|miss| miss.distance)
+            .min_by(f64::total_cmp)
+    }
+
+    /// 防御に失敗したか (防護範囲への着弾、または防衛線を越えてからの迎撃があった)
+    pub fn has_leaker(&self) -> bool {
+        !self.impact_damages.is_empty() || self.late_intercepts > 0
+    }
+}
+
+/// アンサンブル (複数回の実行) の統計
+/// ミス距離の統計と迎撃確率は迎撃を試みた (迎撃ミサイルを発射した) 実行のみ、漏洩率は全実行を対象とする
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnsembleStats {
+    pub runs: usize,                         // 実行数
+    pub attempts: usize,                     // 迎撃を試みた実行数
+    pub mean_miss_distance: Option<f64>,     // 最小ミス距離の平均 [m] (試行がなければ None)
+    pub median_miss_distance: Option<f64>,   // 最小ミス距離の中央値 [m]
+    pub p95_miss_distance: Option<f64>,      // 最小ミス距離の95パーセンタイル [m]
+    pub intercept_probability: Option<f64>,  // 迎撃に成功した実行の割合 (試行がなければ None)
+    pub leaker_rate: Option<f64>,            // 防御に失敗した実行の割合 (実行がなければ None)
+}
+
+/// 昇順に並んだ値の p 分位点 (0 <= p <= 1、隣接順位の線形補間)。値がなければ None
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = p.clamp(0.0, 1.0) * last as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64))
+}
+
+impl From<&[RunSummary]> for EnsembleStats {
+    fn from(outcomes: &[RunSummary]) -> Self {
+        let mut misses: Vec<f64> = outcomes.iter().filter_map(RunSummary::best_miss_distance).collect();
+        misses.sort_by(f64::total_cmp);
+        let attempts = misses.len();
+        let intercepted = outcomes
+            .iter()
+            .filter(|run| run.best_miss_distance().is_some() && !run.intercepts.is_empty())
+            .count();
+        let leakers = outcomes.iter().filter(|run| run.has_leaker()).count();
+        let ratio = |count: usize, total: usize| (total > 0).then(|| count as f64 / total as f64);
+        EnsembleStats {
+            runs: outcomes.len(),
+            attempts,
+            mean_miss_distance: (attempts > 0).then(|| misses.iter().sum::<f64>() / attempts as f64),
+            median_miss_distance: percentile(&misses, 0.5),
+            p95_miss_distance: percentile(&misses, 0.95),
+            intercept_probability: ratio(intercepted, attempts),
+            leaker_rate: ratio(leakers, outcomes.len()),
+        }
+    }
+}
+
+/// シードを変えて同じシナリオを runs 回実行し、結果を集計する
+/// i 回目の実行はシナリオの seed + i で乱数を初期化し、時刻 duration [s] まで (生存中のミサイルがなくなれば終了) 進める
+pub fn run_ensemble(
+    missile_params: &MissileParams,
+    radar_params: &RadarParams,
+    interceptor_params: &InterceptorParams,
+    scenario: &Scenario,
+    dt: f64,
+    duration: f64,
+    runs: usize,
+) -> EnsembleStats {
+    let outcomes: Vec<RunSummary> = (0..runs as u64)
+        .map(|i| {
+            let seeded = Scenario {
+                seed: scenario.seed.wrapping_add(i),
+                ..scenario.clone()
+            };
+            let mut sim = Simulation::new(missile_params, radar_params, interceptor_params, &seeded, dt);
+            while sim.is_running() && sim.time < duration - 1e-9 {
+                sim.step();
+            }
+            sim.summary()
+        })
+        .collect();
+    EnsembleStats::from(outcomes.as_slice())
+}
+
+/// 巻き戻し用に保存するステップ開始時点の状態 (積分器・フィルタの履歴を含む)
+#[derive(Clone, Debug)]
+pub struct SimSnapshot {
+    pub missiles: Vec<Missile>,
+    pub interceptors: Vec<Interceptor>,
+    pub missile_integrators: Vec<[AdamsBashforthIntegrator; 3]>,
+    pub missile_filters: Vec<[LowPassFilter; 3]>,
+    pub interceptor_integrators: Vec<[AdamsBashforthIntegrator; 3]>,
+    pub interceptor_filters: Vec<[LowPassFilter; 3]>,
+    pub interceptor_targets: Vec<usize>,
+    pub detected: Vec<bool>,
+    pub track_assignments: Vec<Option<usize>>,
+    pub radar_state: RadarState,
+    pub first_detection_time: Vec<Option<f64>>,
+    pub launcher: Option<Launcher>,
+    pub missile_coasting: Vec<bool>,
+    pub min_miss_distances: Vec<Option<MissDistance>>,
+    pub intercepts: Vec<InterceptRecord>,
+    pub rng: SeededRng,
+    pub time: f64,
+}
+
+/// シミュレーション全体の状態
+pub struct Simulation {
+    pub missiles: Vec<Missile>,
+    pub radars: Vec<Radar>, // レーダ (先頭は radar_params の主レーダ、以降はシナリオの radars)
+    pub interceptors: Vec<Interceptor>,
+    pub missile_integrators: Vec<[AdamsBashforthIntegrator; 3]>,
+    pub missile_filters: Vec<[LowPassFilter; 3]>,
+    pub interceptor_integrators: Vec<[AdamsBashforthIntegrator; 3]>,
+    pub interceptor_filters: Vec<[LowPassFilter; 3]>,
+    pub interceptor_initial_masses: Vec<f64>, // 各迎撃ミサイルの初期質量 [kg]
+    pub interceptor_targets: Vec<usize>, // 各迎撃ミサイルの追尾対象 (ミサイル番号)。発射時に交戦優先順位から割り当てる
+    pub detected: Vec<bool>,             // 直近のステップで探知 (追尾) していたミサイル
+    pub track_assignments: Vec<Option<usize>>, // 各ミサイルを追尾中の追尾レーダ (radars の番号)
+    pub radar_state: RadarState,         // 探知のヒステリシスによる目標ごとの追尾状態
+    pub target_estimates: Vec<Option<TrackEstimate>>, // 直近のステップで誘導に用いた目標の推定 (None: 未追尾)
+    pub first_detection_time: Vec<Option<f64>>, // 各ミサイルを初めて探知した時刻 [s]
+    pub impact_predictions: Vec<Option<Option<[f64; 3]>>>, // 各ミサイルの予測着弾点 (外側の None: 未計算。制御指令・巻き戻しで破棄する)
+    pub c2_latency: f64,                 // 初探知から発射判断までの指揮統制の遅れ [s]
+    pub max_interceptors_per_target: Option<usize>, // 1目標に割り当てる迎撃ミサイル数の上限
+    pub damage_scaling: DamageScaling, // 着弾の運動エネルギーから被害半径を求めるスケーリング則
+    pub flyout_table: Option<FlyoutTable>, // 迎撃ミサイルの到達範囲表 (None: 到達性で候補を絞らない)
+    pub defended_point: Option<DefendedPoint>,
+    pub launcher: Option<Launcher>, // 移動する発射母体 (None: 固定発射機)
+    pub resolution_mode: ResolutionMode,
+    pub impact_precedence: ImpactPrecedence, // 同一ステップ内の着弾と迎撃の優先規則
+    pub intercept_distance: f64, // 迎撃判定距離 [m]
+    pub conservation_tolerance: Option<f64>, // 慣性飛行中の保存則チェックの相対許容誤差 (None: 無効)
+    pub missile_coasting: Vec<bool>, // 直前のステップで慣性飛行していたミサイル
+    pub min_miss_distances: Vec<Option<MissDistance>>, // 迎撃ミサイルごとの目標への最接近
+    pub intercepts: Vec<InterceptRecord>, // 迎撃の記録 (発生順)
+    pub history_depth: usize,         // step_back で巻き戻せるステップ数 (0: 保存しない)
+    pub history: VecDeque<SimSnapshot>, // 各ステップ開始時点の状態 (新しいものが末尾)
+    pub seed: u64, // 乱数シード
+    pub rng: SeededRng, // 探知・計測誤差の乱数 (seed で初期化し、巻き戻しでは系列も戻す)
+    pub clock: Box<dyn Clock + Send>, // run_with_timeout の実時間の取得元
+    pub dt: f64,
+    pub time: f64,
+}
+
+/// run_with_timeout の終了状態
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunStatus {
+    /// 指定時刻に達した、または生存中のミサイルがなくなった
+    Completed,
+    /// 実時間の上限を超えたため打ち切った
+    TimedOut,
+}
+
+impl Simulation {
+    /// パラメータとシナリオからシミュレーションを生成
+    pub fn new(
+        missile_params: &MissileParams,
+        radar_params: &RadarParams,
+        interceptor_params: &InterceptorParams,
+        scenario: &Scenario,
+        dt: f64,
+    ) -> Self {
+        let missiles: Vec<Missile> = scenario
+            .initial_conditions
+            .missiles
+            .iter()
+            .map(|init_state| Missile {
+                params: missile_params.clone(),
+                state: init_state.clone(),
+            })
+            .collect();
+
+        let interceptors: Vec<Interceptor> = scenario
+            .initial_conditions
+            .interceptors
+            .iter()
+            .map(|init_state| Interceptor {
+                params: interceptor_params.clone(),
+                state: init_state.clone(),
+            })
+            .collect();
+
+        // Adams-Bashforth およびローパスフィルタ用のインスタンス (エンティティごと)
+        let missile_integrators = (0..missiles.len())
+            .map(|_| core::array::from_fn(|_| AdamsBashforthIntegrator::new()))
+            .collect();
+        let missile_filters =
+            vec![core::array::from_fn(|_| LowPassFilter::new(missile_params.alpha_filter)); missiles.len()];
+        let interceptor_integrators =
+            interceptors.iter().map(|intc| new_interceptor_integrators(&intc.params)).collect();
+        let interceptor_filters =
+            vec![core::array::from_fn(|_| LowPassFilter::new(interceptor_params.alpha_filter)); interceptors.len()];
+
+        Simulation {
+            detected: vec![false; missiles.len()],
+            track_assignments: vec![None; missiles.len()],
+            target_estimates: vec![None; missiles.len()],
+            radar_state: RadarState::new(missiles.len()),
+            first_detection_time: vec![None; missiles.len()],
+            c2_latency: scenario.c2_latency,
+            max_interceptors_per_target: scenario.max_interceptors_per_target,
+            damage_scaling: scenario.damage_scaling,
+            flyout_table: scenario
+                .flyout
+                .as_ref()
+                .zip(interceptors.first())
+                .map(|(config, first)| config.generate(interceptor_params, &first.state, dt)),
+            seed: scenario.seed,
+            rng: SeededRng::new(scenario.seed),
+            missile_coasting: vec![false; missiles.len()],
+            impact_predictions: vec![None; missiles.len()],
+            interceptor_initial_masses: interceptors.iter().map(|intc| intc.state.mass).collect(),
+            interceptor_targets: vec![0; interceptors.len()],
+            min_miss_distances: vec![None; interceptors.len()],
+            intercepts: Vec::new(),
+            missiles,
+            radars: std::iter::once(radar_params)
+                .chain(&scenario.radars)
+                .map(|params| Radar { params: params.clone() })
+                .collect(),
+            interceptors,
+            missile_integrators,
+            missile_filters,
+            interceptor_integrators,
+            interceptor_filters,
+            defended_point: scenario.defended_point.clone(),
+            launcher: scenario.launcher.clone(),
+            resolution_mode: scenario.resolution_mode,
+            impact_precedence: scenario.impact_precedence,
+            intercept_distance: 50.0,
+            conservation_tolerance: None,
+            history_depth: 0,
+            history: VecDeque::new(),
+            clock: Box::new(SystemClock::new()),
+            dt,
+            time: 0.0,
+        }
+    }
+
+    /// 慣性飛行中 (推力・抗力なし) の運動量・エネルギー保存のチェックを有効にする (テスト・デバッグ用)
+    /// 前後のステップとも慣性飛行しているミサイルについて毎ステップ検査し、
+    /// 破れた場合はデバッグビルドでは panic、リリースビルドでは警告を出力する。
+    /// 推力停止直後や積分開始直後は AB2 の前回微分値が重力と異なるため検査しない。
+    pub fn enable_conservation_checks(&mut self, tolerance: f64) {
+        self.conservation_tolerance = Some(tolerance);
+        self.missile_coasting = vec![false; self.missiles.len()];
+    }
+
+    /// 巻き戻し可能なステップ数を設定する (0 で無効)。保存済みの履歴は古いものから切り詰める
+    pub fn set_history_depth(&mut self, depth: usize) {
+        self.history_depth = depth;
+        while self.history.len() > depth {
+            self.history.pop_front();
+        }
+    }
+
+    /// 現在の状態のスナップショット
+    pub fn snapshot(&self) -> SimSnapshot {
+        SimSnapshot {
+            missiles: self.missiles.clone(),
+            interceptors: self.interceptors.clone(),
+            missile_integrators: self.missile_integrators.clone(),
+            missile_filters: self.missile_filters.clone(),
+            interceptor_integrators: self.interceptor_integrators.clone(),
+            interceptor_filters: self.interceptor_filters.clone(),
+            interceptor_targets: self.interceptor_targets.clone(),
+            detected: self.detected.clone(),
+            track_assignments: self.track_assignments.clone(),
+            radar_state: self.radar_state.clone(),
+            first_detection_time: self.first_detection_time.clone(),
+            launcher: self.launcher.clone(),
+            missile_coasting: self.missile_coasting.clone(),
+            min_miss_distances: self.min_miss_distances.clone(),
+            intercepts: self.intercepts.clone(),
+            rng: self.rng.clone(),
+            time: self.time,
+        }
+    }
+
+    /// スナップショットの状態に戻す
+    pub fn restore(&mut self, snapshot: SimSnapshot) {
+        self.missiles = snapshot.missiles;
+        self.interceptors = snapshot.interceptors;
+        self.missile_integrators = snapshot.missile_integrators;
+        self.missile_filters = snapshot.missile_filters;
+        self.interceptor_integrators = snapshot.interceptor_integrators;
+        self.interceptor_filters = snapshot.interceptor_filters;
+        self.interceptor_targets = snapshot.interceptor_targets;
+        self.detected = snapshot.detected;
+        self.track_assignments = snapshot.track_assignments;
+        self.radar_state = snapshot.radar_state;
+        self.first_detection_time = snapshot.first_detection_time;
+        self.launcher = snapshot.launcher;
+        self.missile_coasting = snapshot.missile_coasting;
+        self.min_miss_distances = snapshot.min_miss_distances;
+        self.intercepts = snapshot.intercepts;
+        self.rng = snapshot.rng;
+        self.time = snapshot.time;
+        self.impact_predictions.iter_mut().for_each(|prediction| *prediction = None);
+    }
+
+    /// 出力に記録する実行設定 (シード・時間刻み・計算時間 duration [s])
+    pub fn run_metadata(&self, duration: f64) -> RunMetadata {
+        RunMetadata {
+            seed: self.seed,
+            dt: self.dt,
+            duration,
+        }
+    }
+
+    /// 実行結果の集計
+    pub fn summary(&self) -> RunSummary {
+        RunSummary {
+            min_miss_distances: self.min_miss_distances.clone(),
+            intercepts: self.intercepts.clone(),
+            late_intercepts: self
+                .intercepts
+                .iter()
+                .filter(|record| record.timing == InterceptTiming::TooLate)
+                .count(),
+            impact_damages: impact_damages(&self.missiles, self.defended_point.as_ref(), &self.damage_scaling),
+        }
+    }
+
+    /// 1ステップ巻き戻す。履歴がなければ何もせず false を返す
+    pub fn step_back(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// シミュレーション時刻 until まで (生存中のミサイルがなくなれば終了) 進め、発生したイベントを返す
+    /// 各ステップの前に clock で経過実時間を確認し、max_wall を超えていれば TimedOut で打ち切る
+    /// (時刻の丸め誤差で余分な1ステップを進めないよう、until との比較には 1e-9 s の許容誤差を設ける)
+    pub fn run_with_timeout(&mut self, until: f64, max_wall: Duration) -> (RunStatus, Vec<SimEvent>) {
+        let start = self.clock.now();
+        let mut events = Vec::new();
+        while self.is_running() && self.time < until - 1e-9 {
+            if self.clock.now().saturating_sub(start) > max_wall {
+                return (RunStatus::TimedOut, events);
+            }
+            events.extend(self.step());
+            self.clock.on_step();
+        }
+        (RunStatus::Completed, events)
+    }
+
+    /// 生存中 (地表に達していない) のミサイルがあるかどうか
+    pub fn is_running(&self) -> bool {
+        self.missiles.iter().any(|m| !check_missile_collision(&m.state))
+    }
+
+    /// 交戦中 (発射済み・無力化前で目標が生存) の迎撃ミサイルと目標の相対幾何
+    /// 戻り値は (迎撃ミサイル番号, ミサイル番号, 相対幾何) の一覧
+    pub fn relative_geometries(&self) -> Vec<(usize, usize, RelGeom)> {
+        self.interceptors
+            .iter()
+            .enumerate()
+            .filter(|(_, intc)| intc.state.launched && !intc.state.inert)
+            .filter_map(|(i, intc)| {
+                let target = self.interceptor_targets[i];
+                let missile = self.missiles.get(target).filter(|m| m.state.active)?;
+                Some((i, target, relative_geometry(&intc.state, &missile.state)))
+            })
+            .collect()
+    }
+
+    /// 迎撃を記録する (防衛線に対するタイミングを判定する)
+    fn record_intercept(&mut self, time: f64, interceptor: usize, missile: usize, position: [f64; 3]) {
+        let Interceptor { params, state } = &self.interceptors[interceptor];
+        let initial_mass = self.interceptor_initial_masses[interceptor];
+        self.intercepts.push(InterceptRecord {
+            time,
+            interceptor,
+            missile,
+            position,
+            timing: classify_intercept(&position, self.defended_point.as_ref()),
+            remaining_mass_fraction: if initial_mass > 0.0 { state.mass / initial_mass } else { 0.0 },
+            thrusting: is_thrusting(params, state),
+        });
+    }
+
+    /// 制御指令を適用する (存在しないエンティティへの指令は無視する)
+    pub fn apply_control(&mut self, commands: &[ControlCommand]) {
+        for command in commands {
+            match *command {
+                ControlCommand::MissileThrust { missile, thrust } => {
+                    if let Some(m) = self.missiles.get_mut(missile) {
+                        m.state.thrust = thrust;
+                        self.impact_predictions[missile] = None;
+                    }
+                }
+                ControlCommand::MissilePitch { missile, theta } => {
+                    if let Some(m) = self.missiles.get_mut(missile) {
+                        m.state.theta = theta;
+                        self.impact_predictions[missile] = None;
+                    }
+                }
+                ControlCommand::InterceptorThrust { interceptor, thrust } => {
+                    if let Some(intc) = self.interceptors.get_mut(interceptor) {
+                        intc.state.thrust = thrust;
+                    }
+                }
+                ControlCommand::GuidanceOverride { interceptor, acceleration } => {
+                    if let Some(intc) = self.interceptors.get_mut(interceptor) {
+                        intc.state.guidance_override = Some(acceleration);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 制御コールバックが現在の状態から返す指令を適用してから1ステップ進める
+    pub fn step_with_control<F>(&mut self, control: &mut F) -> Vec<SimEvent>
+    where
+        F: FnMut(&Simulation) -> Vec<ControlCommand>,
+    {
+        let commands = control(self);
+        self.apply_control(&commands);
+        self.step()
+    }
+
+    /// 1ステップ進め、このステップで発生したイベントを返す
+    /// 発射は時刻 time、迎撃・無力化は運動更新後の時刻 time + dt、着弾はステップ内の地表交差時刻に記録する
+    /// 同じステップで着弾と迎撃判定距離への進入が起きたミサイルは impact_precedence に従いどちらか一方とする
+    pub fn step(&mut self) -> Vec<SimEvent> {
+        if self.history_depth > 0 {
+            if self.history.len() == self.history_depth {
+                self.history.pop_front();
+            }
+            self.history.push_back(self.snapshot());
+        }
+
+        let dt = self.dt;
+        let end_time = self.time + dt;
+        let mut events = Vec::new();
+        // このステップで着弾したミサイル (番号, ステップ内の着弾時刻, ステップ開始位置, 着弾しなかった場合の終端位置)
+        let mut impacts = Vec::new();
+        let missile_starts: Vec<([f64; 3], bool)> =
+            self.missiles.iter().map(|m| (m.state.position, m.state.active)).collect();
+
+        // ===== (1) ミサイルの更新 =====
+        for (i, missile) in self.missiles.iter_mut().enumerate() {
+            // すでに地表衝突 or 迎撃されている場合は更新不要
+            if check_missile_collision(&missile.state) {
+                continue;
+            }
+            let was_active = missile.state.active;
+            let z_start = missile.state.position[2];
+            let before = missile.state.clone();
+            missile.state = models::missile::update_missile(
+                &missile.params,
+                &missile.state,
+                &mut self.missile_integrators[i],
+                &mut self.missile_filters[i],
+                dt,
+            );
+            if let Some(tolerance) = self.conservation_tolerance {
+                let coasting = is_coasting(&missile.params, &before) && missile.state.active;
+                if coasting && self.missile_coasting[i] {
+                    if let Err(violation) =
+                        check_coast_conservation(&missile.params, &before, &missile.state, dt, tolerance)
+                    {
+                        if cfg!(debug_assertions) {
+                            panic!("t={:.3} s: ミサイル {}: {}", self.time, i, violation);
+                        }
+                        eprintln!("警告: t={:.3} s: ミサイル {}: {}", self.time, i, violation);
+                    }
+                }
+                self.missile_coasting[i] = coasting;
+            }
+            if was_active && !missile.state.active {
+                let velocity = missile.state.velocity;
+                let impact_time = ground_crossing_time(z_start, velocity[2], dt);
+                let unclamped_end: [f64; 3] = core::array::from_fn(|k| before.position[k] + velocity[k] * dt);
+                impacts.push((i, impact_time, before.position, unclamped_end));
+                events.push(SimEvent::GroundImpact {
+                    time: self.time + impact_time,
+                    missile: i,
+                    position: missile.state.position,
+                    velocity,
+                    impact_angle: impact_angle(&velocity),
+                });
+            }
+        }
+
+        // ===== (2) レーダ演算 (探知 & 発射指示) =====
+        let mut fire_targets = Vec::new();
+        // 計測遅れを経て今回報告された計測 (計測時刻, 位置)
+        let mut reported: Vec<Option<(f64, [f64; 3])>> = vec![None; self.missiles.len()];
+        let time = self.time;
+        for (missile_id, missile) in self.missiles.iter().enumerate() {
+            self.detected[missile_id] = false;
+            // 衝突 (終了) のミサイルはスキップ
+            if check_missile_collision(&missile.state) {
+                continue;
+            }
+            // 捜索レーダで探知し追尾レーダへ引き継ぐ。探知確率による探知の成否と計測誤差は
+            // シナリオの seed で初期化した乱数で決める
+            let rng = &mut self.rng;
+            let (mut detection_result, assignment) =
+                search_and_track(&self.radars, &missile.state, self.track_assignments[missile_id], |radar, state| {
+                    add_measurement_noise(detect_missile_at(radar, state, time, rng.next_f64()), rng)
+                });
+            self.track_assignments[missile_id] = assignment;
+            // 計測は探知したレーダの計測遅れを経て報告される
+            let latency = self.radars[assignment.unwrap_or(0)].params.sensor_latency;
+            let measurement = detection_result.missile_position.filter(|_| detection_result.detected);
+            reported[missile_id] = self.radar_state.delay_measurement(missile_id, time, measurement, latency);
+            // ヒステリシスにより追尾中の目標は、今回探知できなくても探知扱いとする
+            let hysteresis = self.radars[0].params.hysteresis.as_ref();
+            let tracked = self.radar_state.update(missile_id, detection_result.detected, hysteresis);
+            if tracked {
+                self.detected[missile_id] = true;
+                detection_result.detected = true;
+                let first_detection = *self.first_detection_time[missile_id].get_or_insert(self.time);
+                // 指揮統制の遅れを経過してから発射指示を出す
+                if generate_fire_command(&detection_result)
+                    && c2_decision_ready(Some(first_detection), self.time, self.c2_latency)
+                {
+                    fire_targets.push(missile_id);
+                }
+            }
+        }
+        // 追尾目標の計測の更新 (更新数に上限がある場合は再訪問の割り当てに従う)
+        // 誘導には最後に更新した計測と差分から推定した速度を用いる。計測を更新しなかった目標
+        // (割り当てられなかった目標、ヒステリシスで追尾を続けているが今回探知できなかった目標、
+        // 計測の報告を待っている目標) も前回の計測から外挿して追尾を継続 (コースト) する
+        let mut target_estimates: Vec<Option<TrackEstimate>> = vec![None; self.missiles.len()];
+        let tracks: Vec<usize> = (0..self.missiles.len()).filter(|&m| self.detected[m]).collect();
+        let serviced = match self.radars[0].params.max_track_updates {
+            // 探知処理は毎ステップ行うため、再訪問の間隔 (経過時間の加算) はステップ幅 dt とする
+            Some(budget) => self.radar_state.schedule_revisits(&tracks, Some(budget), self.dt),
+            None => tracks.clone(),
+        };
+        for m in tracks {
+            if let Some((measured_at, position)) = reported[m].filter(|_| serviced.binary_search(&m).is_ok()) {
+                self.radar_state.record_measurement(m, measured_at, position);
+            }
+            target_estimates[m] = self.radar_state.track_estimate(m);
+        }
+        self.target_estimates = target_estimates;
+
+        // 交戦優先順位 (防護地点がある場合は着弾予測により脅威となるミサイルのみ)
+        // 運動は決定論的なため、着弾予測は初回と制御指令で推力・姿勢が変わったときだけ計算し直す
+        let candidates: Vec<(usize, Option<[f64; 3]>)> = fire_targets
+            .iter()
+            .map(|&mid| {
+                let impact = self.defended_point.as_ref().and_then(|_| {
+                    *self.impact_predictions[mid].get_or_insert_with(|| {
+                        impact_point(
+                            &self.missiles[mid].params,
+                            &self.missiles[mid].state,
+                            &self.missile_integrators[mid],
+                            &self.missile_filters[mid],
+                            dt,
+                            600.0,
+                        )
+                    })
+                });
+                (mid, impact)
+            })
+            .collect();
+        // 最低交戦高度を下回るミサイルとは交戦しない
+        let target_positions: Vec<[f64; 3]> = self.missiles.iter().map(|m| m.state.position).collect();
+        let min_engagement_altitude = self
+            .interceptors
+            .first()
+            .and_then(|intc| intc.params.min_engagement_altitude);
+        let candidates = filter_by_min_altitude(&candidates, &target_positions, min_engagement_altitude);
+        // 到達範囲表がある場合は、未発射の迎撃ミサイルが表の時間内に届く目標のみ
+        let launch_position = self
+            .interceptors
+            .iter()
+            .find(|intc| !intc.state.launched)
+            .map(|intc| intc.state.position);
+        let candidates = match (&self.flyout_table, launch_position) {
+            (Some(table), Some(position)) => filter_by_flyout(&candidates, &self.missiles, &position, table),
+            _ => candidates,
+        };
+        let engage_order = prioritize_targets(&candidates, self.defended_point.as_ref());
+
+        // 目標ごとの交戦中 (発射済み・無力化前) の迎撃ミサイル数
+        // ホーミング過負荷で迎撃失敗が見込まれる迎撃ミサイルは数えず、その目標へ再交戦 (追加発射) する
+        let mut committed = vec![0; self.missiles.len()];
+        for (interceptor, &target) in self.interceptors.iter().zip(&self.interceptor_targets) {
+            if interceptor.state.launched && !interceptor.state.inert && !is_homing_saturated(interceptor) {
+                committed[target] += 1;
+            }
+        }
+
+        // ===== (3) 迎撃ミサイルの更新 =====
+        let interceptor_starts: Vec<[f64; 3]> = self.interceptors.iter().map(|intc| intc.state.position).collect();
+        for (i, interceptor) in self.interceptors.iter_mut().enumerate() {
+            if !interceptor.state.launched {
+                // まだ発射していない → 上限に達していない優先度の最も高いミサイルに対して発射
+                if let Some(target) = select_target(&engage_order, &committed, self.max_interceptors_per_target) {
+                    interceptor.state = launch_interceptor(&interceptor.state, self.launcher.as_ref());
+                    self.interceptor_targets[i] = target;
+                    committed[target] += 1;
+                    events.push(SimEvent::Launch {
+                        time: self.time,
+                        interceptor: i,
+                        missile: target,
+                    });
+                }
+            }
+            // 目標が撃破・着弾していれば、到達できる生存目標に再割り当てし、なければ無力化する
+            if let Some(retarget_range) = interceptor.params.retarget_range {
+                let target = self.interceptor_targets[i];
+                let target_dead = self.missiles.get(target).is_none_or(|m| !m.state.active);
+                if interceptor.state.launched && !interceptor.state.inert && target_dead {
+                    let available: Vec<usize> = engage_order
+                        .iter()
+                        .copied()
+                        .filter(|&m| self.max_interceptors_per_target.is_none_or(|max| committed[m] < max))
+                        .collect();
+                    match retarget(&interceptor.state, &available, &self.missiles, retarget_range) {
+                        Some(next) => {
+                            // 前の目標に対する最接近と誘導の内部状態は新しい目標に引き継がない
+                            interceptor.state = retargeted_state(&interceptor.state);
+                            self.min_miss_distances[i] = None;
+                            self.interceptor_targets[i] = next;
+                            committed[next] += 1;
+                            events.push(SimEvent::Retarget {
+                                time: self.time,
+                                interceptor: i,
+                                missile: next,
+                            });
+                        }
+                        None => {
+                            interceptor.state.inert = true;
+                            events.push(SimEvent::Dud {
+                                time: self.time,
+                                interceptor: i,
+                            });
+                        }
+                    }
+                }
+            }
+            // インターセプタの運動更新
+            if interceptor.state.launched {
+                let target = self.interceptor_targets[i];
+                if let Some(target_missile) = self.missiles.get(target) {
+                    let was_inert = interceptor.state.inert;
+                    // 追尾できていれば最後の計測と推定速度 (計測遅れは guidance_target で補償する)、
+                    // 計測がなければ真値を使う
+                    let (estimated_target, measurement_age) = match self.target_estimates[target] {
+                        Some(estimate) => (
+                            MissileState {
+                                position: estimate.position,
+                                velocity: estimate.velocity,
+                                ..target_missile.state.clone()
+                            },
+                            Some(self.time - estimate.time),
+                        ),
+                        None => (target_missile.state.clone(), None),
+                    };
+                    let aim =
+                        guidance_target(&interceptor.params, &interceptor.state, &estimated_target, measurement_age);
+                    interceptor.state = models::interceptor::update_interceptor(
+                        &interceptor.params,
+                        &interceptor.state,
+                        &aim,
+                        &estimated_target.velocity,
+                        &mut self.interceptor_integrators[i],
+                        &mut self.interceptor_filters[i],
+                        dt,
+                    );
+                    if !was_inert && interceptor.state.inert {
+                        events.push(SimEvent::Dud {
+                            time: end_time,
+                            interceptor: i,
+                        });
+                    }
+                }
+            }
+        }
+
+        // 発射母体の移動 (未発射の迎撃ミサイルは母体に搭載されたまま移動する)
+        if let Some(launcher) = self.launcher.as_mut() {
+            *launcher = launcher.advance(dt);
+            for interceptor in self.interceptors.iter_mut().filter(|intc| !intc.state.launched) {
+                for k in 0..3 {
+                    interceptor.state.position[k] += launcher.velocity[k] * dt;
+                }
+            }
+        }
+
+        // 目標への最接近の更新 (ステップ開始時点で目標が生存していた迎撃ミサイル)
+        for (i, interceptor) in self.interceptors.iter().enumerate().filter(|(_, intc)| intc.state.launched) {
+            let target = self.interceptor_targets[i];
+            let (Some(&(missile_start, true)), Some(missile)) = (missile_starts.get(target), self.missiles.get(target))
+            else {
+                continue;
+            };
+            let rel_start = core::array::from_fn(|k| interceptor_starts[i][k] - missile_start[k]);
+            let rel_end = core::array::from_fn(|k| interceptor.state.position[k] - missile.state.position[k]);
+            let (s, distance) = closest_approach(&rel_start, &rel_end);
+            let best = &mut self.min_miss_distances[i];
+            if best.is_none_or(|b| distance < b.distance) {
+                *best = Some(MissDistance {
+                    distance,
+                    time: self.time + s * dt,
+                });
+            }
+        }
+
+        // このステップで着弾したミサイルの迎撃判定 (ステップ内の軌跡を線形補間して迎撃判定距離への進入時刻を求める)
+        if self.impact_precedence != ImpactPrecedence::Impact {
+            for &(m, impact_time, missile_start, missile_end) in &impacts {
+                let contact = self
+                    .interceptors
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, intc)| {
+                        intc.state.launched && !intc.state.inert && self.interceptor_targets[*i] == m
+                    })
+                    .filter_map(|(i, intc)| {
+                        let rel_start = core::array::from_fn(|k| interceptor_starts[i][k] - missile_start[k]);
+                        let rel_end = core::array::from_fn(|k| intc.state.position[k] - missile_end[k]);
+                        first_contact_fraction(&rel_start, &rel_end, self.intercept_distance).map(|s| (i, s * dt))
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+                let Some((interceptor, intercept_time)) = contact else { continue };
+                if self.impact_precedence.intercept_wins(impact_time, intercept_time) {
+                    // 迎撃はステップ内で迎撃判定距離に入った時刻・位置で記録する
+                    let s = intercept_time / dt;
+                    let position = core::array::from_fn(|k| missile_start[k] + (missile_end[k] - missile_start[k]) * s);
+                    self.record_intercept(self.time + intercept_time, interceptor, m, position);
+                    self.missiles[m].state.intercepted = true;
+                    events.retain(|e| !matches!(e, SimEvent::GroundImpact { missile, .. } if *missile == m));
+                    events.push(SimEvent::Intercept {
+                        time: self.time + intercept_time,
+                        interceptor,
+                        missile: m,
+                    });
+                }
+            }
+        }
+
+        // 近接信管を持つ迎撃ミサイルは、最接近点で起爆し、その距離が迎撃判定距離以下なら迎撃とする
+        for (i, interceptor_start) in interceptor_starts.iter().enumerate() {
+            let state = &self.interceptors[i].state;
+            let Some(arming_time) = self.interceptors[i].params.arming_time else { continue };
+            let m = self.interceptor_targets[i];
+            if !state.launched || state.inert || !self.missiles.get(m).is_some_and(|missile| missile.state.active) {
+                continue;
+            }
+            let missile_start = missile_starts[m].0;
+            let missile_end = self.missiles[m].state.position;
+            let rel_start = core::array::from_fn(|k| interceptor_start[k] - missile_start[k]);
+            let rel_end = core::array::from_fn(|k| state.position[k] - missile_end[k]);
+            let Some(s) = fuze_detonation(arming_time, state.time_since_launch, &rel_start, &rel_end, dt) else {
+                continue;
+            };
+            self.interceptors[i].state.inert = true;
+            if closest_approach(&rel_start, &rel_end).1 > self.intercept_distance {
+                events.push(SimEvent::Dud {
+                    time: end_time,
+                    interceptor: i,
+                });
+                continue;
+            }
+            let position = core::array::from_fn(|k| missile_start[k] + (missile_end[k] - missile_start[k]) * s);
+            self.record_intercept(end_time, i, m, position);
+            let missile = &mut self.missiles[m].state;
+            missile.position[2] = 0.0;
+            missile.active = false;
+            missile.intercepted = true;
+            events.push(SimEvent::Intercept {
+                time: end_time,
+                interceptor: i,
+                missile: m,
+            });
+        }
+
+        // 迎撃成功判定 (更新後の全位置を用いて解決し、判定中に状態を変更しない)
+        // 近接信管を持つ迎撃ミサイルは距離のみでは判定しない
+        let interceptor_states: Vec<_> = self.interceptors.iter().map(|intc| intc.state.clone()).collect();
+        let targets: Vec<Option<usize>> = self
+            .interceptors
+            .iter()
+            .zip(&self.interceptor_targets)
+            .map(|(intc, target)| (intc.state.launched && intc.params.arming_time.is_none()).then_some(*target))
+            .collect();
+        let missile_states: Vec<_> = self.missiles.iter().map(|m| m.state.clone()).collect();
+        for interception in resolve_interceptions(
+            &interceptor_states,
+            &targets,
+            &missile_states,
+            self.intercept_distance,
+            self.resolution_mode,
+        ) {
+            // 迎撃成功 → ミサイルを地表衝突扱い (z=0) にして以降の更新を止める
+            let position = self.missiles[interception.missile].state.position;
+            self.record_intercept(end_time, interception.interceptor, interception.missile, position);
+            let missile = &mut self.missiles[interception.missile].state;
+            missile.position[2] = 0.0;
+            missile.active = false;
+            missile.intercepted = true;
+            events.push(SimEvent::Intercept {
+                time: end_time,
+                interceptor: interception.interceptor,
+                missile: interception.missile,
+            });
+        }
+
+        // 時間経過
+        self.time = end_time;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::interceptor::{AimPoint, GLimit, GuidanceConstants, InterceptorState};
+    use crate::models::missile::MissileState;
+    use crate::utils::yaml_parser::{FlyoutConfig, InitialConditions, OutputConfig};
+
+    fn missile_params() -> MissileParams {
+        MissileParams {
+            area: 1.0,
+            rho0: 1.225,
+            h: 8500.0,
+            g: 9.81,
+            alpha_filter: 1.0,
+            density_floor: 1e-5,
+            ..Default::default()
+        }
+    }
+
+    fn radar_params() -> RadarParams {
+        RadarParams {
+            direction: [1.0, 0.0, 0.0],
+            range: 100000.0,
+            azimuth_range: 45.0,
+            elevation_range: 30.0,
+            period: 0.1,
+            ..Default::default()
+        }
+    }
+
+    fn interceptor_params() -> InterceptorParams {
+        InterceptorParams {
+            alpha_filter: 1.0,
+            guidance_constants: GuidanceConstants { n: 0.0 },
+            ..Default::default()
+        }
+    }
+
+    fn missile_state(position: [f64; 3], velocity: [f64; 3]) -> MissileState {
+        MissileState {
+            mass: 1000.0,
+            position,
+            velocity,
+            ..Default::default()
+        }
+    }
+
+    fn interceptor_state(position: [f64; 3]) -> InterceptorState {
+        InterceptorState {
+            mass: 500.0,
+            position,
+            ..Default::default()
+        }
+    }
+
+    fn scenario(missiles: Vec<MissileState>, interceptors: Vec<InterceptorState>) -> Scenario {
+        Scenario {
+            initial_conditions: InitialConditions { missiles, interceptors },
+            defended_point: None,
+            launcher: None,
+            resolution_mode: ResolutionMode::Simultaneous,
+            impact_precedence: ImpactPrecedence::Impact,
+            c2_latency: 0.0,
+            max_interceptors_per_target: None,
+            damage_scaling: DamageScaling::default(),
+            seed: 0,
+            radars: vec![],
+            flyout: None,
+            output: OutputConfig::default(),
+            max_expected_speed: None,
+        }
+    }
+
+    fn simulation(missiles: Vec<MissileState>, interceptors: Vec<InterceptorState>) -> Simulation {
+        let scenario = scenario(missiles, interceptors);
+        Simulation::new(&missile_params(), &radar_params(), &interceptor_params(), &scenario, 0.1)
+    }
+
+    fn run(sim: &mut Simulation) -> Vec<SimEvent> {
+        let mut events = Vec::new();
+        while sim.is_running() && sim.time < 100.0 {
+            events.extend(sim.step());
+        }
+        events
+    }
+
+    #[test]
+    fn test_event_sequence_launch_then_impact() {
+        // 高度 100 m から自由落下 (t = sqrt(2h/g) ≈ 4.5 s)、迎撃ミサイルは静止したまま
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([50000.0, 0.0, 0.0])],
+        );
+
+        let events = run(&mut sim);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            SimEvent::Launch {
+                time: 0.0,
+                interceptor: 0,
+                missile: 0
+            }
+        );
+        assert_eq!(events[1].name(), "impact");
+        assert_eq!(events[1].missile(), Some(0));
+        let expected = (2.0 * 100.0 / 9.81f64).sqrt();
+        assert!((events[1].time() - expected).abs() <= 0.1 + 1e-9, "impact at {}", events[1].time());
+    }
+
+    #[test]
+    fn test_event_sequence_launch_then_intercept() {
+        // 迎撃ミサイルがミサイルの直近にいるため最初のステップで迎撃
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([1010.0, 0.0, 100.0])],
+        );
+
+        let events = run(&mut sim);
+
+        assert_eq!(
+            events,
+            vec![
+                SimEvent::Launch {
+                    time: 0.0,
+                    interceptor: 0,
+                    missile: 0
+                },
+                SimEvent::Intercept {
+                    time: 0.1,
+                    interceptor: 0,
+                    missile: 0
+                },
+            ]
+        );
+        assert!(sim.missiles[0].state.intercepted);
+    }
+
+    #[test]
+    fn test_impact_prediction_is_cached_until_control_changes() {
+        let mut sim = simulation(vec![missile_state([1000.0, 0.0, 5000.0], [100.0, 0.0, 0.0])], vec![]);
+        sim.missiles[0].params.cd = 0.5;
+        sim.defended_point = Some(DefendedPoint {
+            position: [0.0, 0.0, 0.0],
+            radius: 5000.0,
+            defense_line: None,
+        });
+        sim.step();
+        let first = sim.impact_predictions[0].expect("impact is predicted for a detected missile");
+        assert!(first.is_some());
+        // 制御指令がなければ以降のステップは予測を再利用する
+        sim.step();
+        assert_eq!(sim.impact_predictions[0], Some(first));
+
+        // 推力の指令で予測を破棄し、次のステップで計算し直す
+        sim.apply_control(&[ControlCommand::MissileThrust { missile: 0, thrust: 20000.0 }]);
+        assert_eq!(sim.impact_predictions[0], None);
+        sim.step();
+        assert!(sim.impact_predictions[0].is_some_and(|impact| impact != first));
+    }
+
+    #[test]
+    fn test_control_callback_zero_thrust_halts_acceleration() {
+        // 重力なしで水平に推進するミサイル
+        let launch = || {
+            let mut sim = simulation(vec![missile_state([1000.0, 0.0, 5000.0], [200.0, 0.0, 0.0])], vec![]);
+            sim.missiles[0].params.g = 0.0;
+            sim.missiles[0].params.alpha = 0.001;
+            sim.missiles[0].state.thrust = 10000.0;
+            sim
+        };
+        let mut free = launch();
+        let mut controlled = launch();
+        for _ in 0..10 {
+            free.step();
+        }
+        assert!(free.missiles[0].state.velocity[0] > 210.0);
+
+        // 毎ステップ推力0を指令すると加速せず、燃料も消費しない
+        let mut calls = 0;
+        let mut cut_thrust = |sim: &Simulation| {
+            calls += 1;
+            assert_eq!(sim.missiles.len(), 1);
+            vec![ControlCommand::MissileThrust { missile: 0, thrust: 0.0 }]
+        };
+        for _ in 0..10 {
+            controlled.step_with_control(&mut cut_thrust);
+        }
+        assert_eq!(calls, 10);
+        let state = &controlled.missiles[0].state;
+        assert_eq!(state.velocity, [200.0, 0.0, 0.0]);
+        assert_eq!(state.mass, missile_state([0.0; 3], [0.0; 3]).mass);
+        assert!((state.position[0] - 1200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_guidance_override_applies_for_one_step() {
+        let mut sim = simulation(
+            vec![missile_state([50000.0, 0.0, 5000.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([0.0, 0.0, 1000.0])],
+        );
+        sim.step();
+        assert!(sim.interceptors[0].state.launched);
+        let before = sim.interceptors[0].state.velocity;
+        sim.step_with_control(&mut |_: &Simulation| {
+            vec![ControlCommand::GuidanceOverride {
+                interceptor: 0,
+                acceleration: [0.0, 10.0, 0.0],
+            }]
+        });
+        let state = &sim.interceptors[0].state;
+        assert!(state.velocity[1] - before[1] > 0.0);
+        assert_eq!(state.guidance_override, None);
+    }
+
+    /// 目標 (ミサイル0) を別の手段で撃破した後の1ステップのイベントと、そのときの迎撃ミサイル
+    fn events_after_target_killed(other_missile: [f64; 3], retarget_range: Option<f64>) -> (Vec<SimEvent>, Interceptor, usize) {
+        let mut sim = simulation(
+            vec![
+                missile_state([3000.0, 0.0, 1000.0], [0.0, 0.0, 0.0]),
+                missile_state(other_missile, [0.0, 0.0, 0.0]),
+            ],
+            vec![interceptor_state([0.0, 0.0, 1000.0])],
+        );
+        sim.interceptors[0].params.retarget_range = retarget_range;
+        assert_eq!(launches(&sim.step()), vec![(0, 0)]);
+
+        // 他の迎撃ミサイルに撃破された
+        let target = &mut sim.missiles[0].state;
+        target.active = false;
+        target.intercepted = true;
+        let events = sim.step();
+        (events, sim.interceptors[0].clone(), sim.interceptor_targets[0])
+    }
+
+    #[test]
+    fn test_interceptor_retargets_reachable_live_missile() {
+        let (events, interceptor, target) = events_after_target_killed([4000.0, 500.0, 1000.0], Some(10000.0));
+        assert_eq!(
+            events,
+            vec![SimEvent::Retarget {
+                time: 0.1,
+                interceptor: 0,
+                missile: 1
+            }]
+        );
+        assert_eq!(target, 1);
+        assert!(!interceptor.state.inert);
+
+        // 再割り当てを設定しなければ撃破された目標を追い続ける
+        let (events, interceptor, target) = events_after_target_killed([4000.0, 500.0, 1000.0], None);
+        assert!(events.is_empty());
+        assert_eq!(target, 0);
+        assert!(!interceptor.state.inert);
+    }
+
+    #[test]
+    fn test_retarget_resets_miss_distance_and_guidance_state() {
+        let mut sim = simulation(
+            vec![
+                missile_state([3000.0, 0.0, 1000.0], [0.0, 0.0, 0.0]),
+                missile_state([4000.0, 500.0, 1000.0], [0.0, 0.0, 0.0]),
+            ],
+            vec![interceptor_state([0.0, 0.0, 1000.0])],
+        );
+        sim.interceptors[0].params.retarget_range = Some(10000.0);
+        // 誘導指令を 10 s 間保持する
+        sim.interceptors[0].params.guidance_rate_hz = Some(0.1);
+        sim.step();
+        assert!(sim.min_miss_distances[0].is_some());
+
+        // 前の目標に対する誘導指令を保持した状態で目標が撃破される
+        sim.interceptors[0].state.guidance_command = Some([0.0, 0.0, -50.0]);
+        sim.missiles[0].state.active = false;
+        sim.step();
+        assert_eq!(sim.interceptor_targets[0], 1);
+        // 保持していた指令を捨てて新しい目標に対する指令を計算する
+        assert_ne!(sim.interceptors[0].state.guidance_command, Some([0.0, 0.0, -50.0]));
+        // 最接近は新しい目標との距離で記録し直す
+        let miss = sim.min_miss_distances[0].expect("miss distance to the new target");
+        let rel: [f64; 3] =
+            core::array::from_fn(|k| sim.interceptors[0].state.position[k] - sim.missiles[1].state.position[k]);
+        assert!(miss.distance > 3500.0, "distance {}", miss.distance);
+        assert!(miss.distance <= rel.iter().map(|x| x * x).sum::<f64>().sqrt() + 1e-9);
+    }
+
+    /// 目標あたり1発に制限し、2発目の迎撃ミサイルを発射した時刻
+    /// 1発目は誘導加速度の上限 max_acceleration [m/s^2] (1 s 継続でホーミング過負荷) で横方向の目標を追う
+    fn reengagement_time(max_acceleration: f64) -> Option<f64> {
+        let interceptor = InterceptorState {
+            velocity: [100.0, 0.0, 0.0],
+            ..interceptor_state([0.0, 0.0, 1000.0])
+        };
+        let mut sim = simulation(
+            vec![missile_state([5000.0, 5000.0, 1000.0], [0.0, 0.0, 0.0])],
+            vec![interceptor.clone(), interceptor],
+        );
+        sim.missiles[0].params.g = 0.0;
+        sim.max_interceptors_per_target = Some(1);
+        for intc in sim.interceptors.iter_mut() {
+            intc.params.guidance_constants.n = 200.0;
+            intc.params.g_limit = Some(GLimit {
+                max_acceleration,
+                overload_time: 1.0,
+            });
+        }
+        let mut events = Vec::new();
+        for _ in 0..30 {
+            events.extend(sim.step());
+        }
+        events.iter().find_map(|e| match e {
+            SimEvent::Launch { time, interceptor: 1, .. } => Some(*time),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_homing_overload_triggers_reengagement() {
+        // 飽和が 1 s 続いた時点で迎撃失敗を見込み、同じ目標へ2発目を発射する
+        let time = reengagement_time(100.0).expect("second interceptor must be launched");
+        assert!((1.0..1.5).contains(&time), "time {}", time);
+        // 飽和しなければ1発目が交戦中のまま2発目は発射しない
+        assert_eq!(reengagement_time(1.0e6), None);
+    }
+
+    #[test]
+    fn test_interceptor_goes_inert_without_reachable_target() {
+        let (events, interceptor, target) = events_after_target_killed([60000.0, 0.0, 5000.0], Some(10000.0));
+        assert_eq!(
+            events,
+            vec![SimEvent::Dud {
+                time: 0.1,
+                interceptor: 0
+            }]
+        );
+        assert_eq!(target, 0);
+        assert!(interceptor.state.inert);
+    }
+
+    /// 推力で加速しながら燃料を消費する迎撃ミサイルで、x 方向 distance 先のミサイルを迎撃した記録
+    fn powered_intercept(distance: f64) -> InterceptRecord {
+        let mut sim = simulation(
+            vec![missile_state([distance, 0.0, 1000.0], [0.0, 0.0, 0.0])],
+            vec![InterceptorState {
+                thrust: 5000.0,
+                velocity: [100.0, 0.0, 0.0],
+                ..interceptor_state([0.0, 0.0, 1000.0])
+            }],
+        );
+        // 静止目標 (重力なし) へ直進する
+        sim.missiles[0].params.g = 0.0;
+        sim.interceptors[0].params.alpha = 0.002; // 10 kg/s
+        run(&mut sim);
+        sim.summary().intercepts[0]
+    }
+
+    #[test]
+    fn test_intercept_reports_remaining_mass() {
+        let early = powered_intercept(2000.0);
+        let late = powered_intercept(6000.0);
+        assert!(early.time < late.time);
+        assert!(early.remaining_mass_fraction > late.remaining_mass_fraction);
+        // 10 kg/s で 500 kg から消費: 1 - 10 t / 500
+        assert!((early.remaining_mass_fraction - (1.0 - 10.0 * early.time / 500.0)).abs() < 1e-9);
+        assert!(early.thrusting && late.thrusting);
+    }
+
+    fn impact_event(velocity: [f64; 3]) -> SimEvent {
+        let mut sim = simulation(vec![missile_state([1000.0, 0.0, 100.0], velocity)], vec![]);
+        run(&mut sim).into_iter().find(|e| e.name() == "impact").unwrap()
+    }
+
+    #[test]
+    fn test_summary_lists_impact_damage() {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([50000.0, 0.0, 0.0])],
+        );
+        assert!(sim.summary().impact_damages.is_empty());
+        run(&mut sim);
+
+        let damages = sim.summary().impact_damages;
+        assert_eq!(damages.len(), 1);
+        assert_eq!(damages[0].missile, 0);
+        assert_eq!(damages[0].position[2], 0.0);
+        assert!(damages[0].energy > 0.0);
+        assert_eq!(damages[0].damage_radius, DamageScaling::default().damage_radius(damages[0].energy));
+    }
+
+    /// 合成した実行結果 (miss: 最小ミス距離、None は未発射)
+    fn outcome(miss: Option<f64>, intercepted: bool, leaked: bool) -> RunSummary {
+        RunSummary {
+            min_miss_distances: vec![miss.map(|distance| MissDistance { distance, time: 1.0 }), None],
+            intercepts: if intercepted {
+                vec![InterceptRecord {
+                    time: 1.0,
+                    interceptor: 0,
+                    missile: 0,
+                    position: [0.0; 3],
+                    timing: InterceptTiming::InTime,
+                    remaining_mass_fraction: 1.0,
+                    thrusting: false,
+                }]
+            } else {
+                vec![]
+            },
+            late_intercepts: 0,
+            impact_damages: if leaked {
+                vec![ImpactDamage {
+                    missile: 0,
+                    position: [0.0; 3],
+                    energy: 1.0,
+                    damage_radius: 1.0,
+                }]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    #[test]
+    fn test_ensemble_stats_from_synthetic_outcomes() {
+        let mut outcomes: Vec<RunSummary> = (1..=20)
+            .map(|k| outcome(Some(k as f64), k <= 15, k > 18))
+            .collect();
+        // 迎撃を試みなかった (発射しなかった) 実行
+        outcomes.push(outcome(None, false, true));
+        outcomes.push(outcome(None, false, false));
+
+        let stats = EnsembleStats::from(outcomes.as_slice());
+        assert_eq!(stats.runs, 22);
+        assert_eq!(stats.attempts, 20);
+        // 1..=20 の平均 10.5、中央値 10.5、95パーセンタイル 1 + 0.95 * 19 = 19.05
+        assert!((stats.mean_miss_distance.unwrap() - 10.5).abs() < 1e-12);
+        assert!((stats.median_miss_distance.unwrap() - 10.5).abs() < 1e-12);
+        assert!((stats.p95_miss_distance.unwrap() - 19.05).abs() < 1e-12);
+        assert_eq!(stats.intercept_probability, Some(0.75));
+        assert_eq!(stats.leaker_rate, Some(3.0 / 22.0));
+    }
+
+    #[test]
+    fn test_ensemble_stats_without_attempts() {
+        assert_eq!(percentile(&[], 0.5), None);
+        assert_eq!(percentile(&[4.0], 0.95), Some(4.0));
+        assert_eq!(percentile(&[1.0, 2.0, 3.0], 0.5), Some(2.0));
+
+        let outcomes = vec![outcome(None, false, true), outcome(None, false, false)];
+        let stats = EnsembleStats::from(outcomes.as_slice());
+        assert_eq!(stats.attempts, 0);
+        assert_eq!(stats.mean_miss_distance, None);
+        assert_eq!(stats.median_miss_distance, None);
+        assert_eq!(stats.p95_miss_distance, None);
+        assert_eq!(stats.intercept_probability, None);
+        assert_eq!(stats.leaker_rate, Some(0.5));
+
+        let empty = EnsembleStats::from(&[][..]);
+        assert_eq!(empty.runs, 0);
+        assert_eq!(empty.leaker_rate, None);
+    }
+
+    #[test]
+    fn test_steep_impact_angle_near_vertical() {
+        let SimEvent::GroundImpact { time, position, velocity, impact_angle, .. } = impact_event([1.0, 0.0, -300.0])
+        else {
+            panic!("not an impact event");
+        };
+        assert!(impact_angle.to_degrees() > 89.0, "angle {}", impact_angle.to_degrees());
+        assert!(velocity[2] < -300.0);
+        assert_eq!(position[2], 0.0);
+        // 約 0.33 s で接地 (ステップ境界ではなく補間した時刻)
+        assert!((time - 100.0 / 300.0).abs() < 0.01, "time {}", time);
+    }
+
+    #[test]
+    fn test_shallow_impact_angle_near_horizontal() {
+        let SimEvent::GroundImpact { impact_angle, .. } = impact_event([3000.0, 0.0, -1.0]) else {
+            panic!("not an impact event");
+        };
+        assert!(impact_angle.to_degrees() < 5.0, "angle {}", impact_angle.to_degrees());
+        assert!(impact_angle > 0.0);
+    }
+
+    #[test]
+    fn test_relative_geometries_for_engaged_pairs() {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([50000.0, 0.0, 100.0])],
+        );
+        // 発射前は対象なし
+        assert!(sim.relative_geometries().is_empty());
+
+        sim.step();
+        let geometries = sim.relative_geometries();
+        assert_eq!(geometries.len(), 1);
+        let (interceptor, missile, geom) = geometries[0];
+        assert_eq!((interceptor, missile), (0, 0));
+        assert!(geom.range > 48000.0);
+    }
+
+    #[test]
+    fn test_interceptor_launched_from_moving_platform() {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![InterceptorState {
+                velocity: [0.0, 0.0, 50.0],
+                ..interceptor_state([50000.0, 0.0, 0.0])
+            }],
+        );
+        sim.launcher = Some(Launcher {
+            position: [50000.0, 0.0, 0.0],
+            velocity: [10.0, 0.0, 0.0],
+        });
+
+        sim.step();
+        // 発射速度 + 母体速度 (重力・推力・誘導なし)
+        assert_eq!(sim.interceptors[0].state.velocity, [10.0, 0.0, 50.0]);
+        assert_eq!(sim.launcher.as_ref().unwrap().position, [50001.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_conservation_checks_pass_on_gravity_coast() {
+        let mut sim = simulation(vec![missile_state([0.0, 0.0, 10000.0], [200.0, 50.0, 300.0])], vec![]);
+        sim.enable_conservation_checks(1e-4);
+        let events = run(&mut sim);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name(), "impact");
+    }
+
+    #[test]
+    fn test_conservation_checks_pass_on_coast_with_horizontal_gravity() {
+        let mut sim = simulation(vec![missile_state([0.0, 0.0, 10000.0], [200.0, 50.0, 300.0])], vec![]);
+        sim.missiles[0].params.gravity_vector = Some([3.0, -4.0, -9.81]);
+        sim.enable_conservation_checks(1e-4);
+        let events = run(&mut sim);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name(), "impact");
+        // 水平重力により x 方向の速度が増え、y 方向の速度が減っている
+        assert!(sim.missiles[0].state.velocity[0] > 200.0 + 10.0);
+        assert!(sim.missiles[0].state.velocity[1] < 50.0 - 10.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "運動量")]
+    fn test_conservation_checks_trip_on_broken_integration() {
+        let mut sim = simulation(vec![missile_state([0.0, 0.0, 10000.0], [200.0, 50.0, 300.0])], vec![]);
+        sim.enable_conservation_checks(1e-4);
+        for _ in 0..10 {
+            sim.step();
+        }
+        // 積分器の履歴を壊す (前回の微分値が 0 になり、重力加速度が 1.5 倍で積分される)
+        sim.missile_integrators[0][2] = AdamsBashforthIntegrator::new();
+        sim.step();
+    }
+
+    fn positions(sim: &Simulation) -> Vec<[f64; 3]> {
+        sim.missiles
+            .iter()
+            .map(|m| m.state.position)
+            .chain(sim.interceptors.iter().map(|i| i.state.position))
+            .collect()
+    }
+
+    #[test]
+    fn test_step_back_restores_intermediate_state() {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 5000.0], [100.0, 0.0, 50.0])],
+            vec![InterceptorState {
+                velocity: [0.0, 0.0, 50.0],
+                ..interceptor_state([50000.0, 0.0, 0.0])
+            }],
+        );
+        sim.set_history_depth(1);
+        assert!(!sim.step_back());
+
+        sim.step();
+        let intermediate = sim.snapshot();
+        let intermediate_positions = positions(&sim);
+        sim.step();
+        let second_positions = positions(&sim);
+        let second_velocity = sim.missiles[0].state.velocity;
+
+        // 1ステップ戻すと中間状態に一致し、深さ1なので2回目は戻れない
+        assert!(sim.step_back());
+        assert_eq!(positions(&sim), intermediate_positions);
+        assert_eq!(sim.time, intermediate.time);
+        assert_eq!(sim.missiles[0].state.velocity, intermediate.missiles[0].state.velocity);
+        assert!(!sim.step_back());
+
+        // 積分器履歴も戻っているため、再実行は元の2ステップ目と完全に一致する
+        sim.step();
+        assert_eq!(positions(&sim), second_positions);
+        assert_eq!(sim.missiles[0].state.velocity, second_velocity);
+    }
+
+    #[test]
+    fn test_history_depth_limits_rewind() {
+        let mut sim = simulation(vec![missile_state([0.0, 0.0, 5000.0], [100.0, 0.0, 0.0])], vec![]);
+        sim.set_history_depth(3);
+        for _ in 0..5 {
+            sim.step();
+        }
+        assert_eq!(sim.history.len(), 3);
+        let mut rewound = 0;
+        while sim.step_back() {
+            rewound += 1;
+        }
+        assert_eq!(rewound, 3);
+        assert!((sim.time - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_c2_latency_delays_launch_after_first_detection() {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 5000.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([50000.0, 0.0, 0.0])],
+        );
+        sim.c2_latency = 2.0;
+
+        let mut launch = None;
+        while launch.is_none() && sim.time < 10.0 {
+            launch = sim.step().into_iter().find(|e| e.name() == "launch");
+        }
+        // 初探知は t=0、発射は約 2 s 後
+        assert_eq!(sim.first_detection_time[0], Some(0.0));
+        let launch_time = launch.unwrap().time();
+        assert!((launch_time - 2.0).abs() < 0.1 + 1e-9, "launch at {}", launch_time);
+    }
+
+    fn launches(events: &[SimEvent]) -> Vec<(usize, usize)> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                SimEvent::Launch { interceptor, missile, .. } => Some((*interceptor, *missile)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_max_interceptors_per_target_holds_extra_interceptor() {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 5000.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([50000.0, 0.0, 0.0]), interceptor_state([50000.0, 100.0, 0.0])],
+        );
+        sim.max_interceptors_per_target = Some(1);
+
+        let mut events = Vec::new();
+        for _ in 0..10 {
+            events.extend(sim.step());
+        }
+        // 2発目は交戦中の目標に割り当てず保留する
+        assert_eq!(launches(&events), vec![(0, 0)]);
+        assert!(!sim.interceptors[1].state.launched);
+    }
+
+    #[test]
+    fn test_flyout_table_skips_unreachable_targets() {
+        let launch_state = InterceptorState {
+            thrust: 50000.0,
+            velocity: [10.0, 0.0, 0.0],
+            ..interceptor_state([0.0, 0.0, 0.0])
+        };
+        let params = InterceptorParams {
+            alpha: 0.0001,
+            cd: 0.3,
+            area: 0.1,
+            ..interceptor_params()
+        };
+        // 到達範囲表はシナリオの生成条件から作る
+        let flyout: FlyoutConfig = serde_yaml::from_str(
+            "elevations: [10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0]\ntimes: [10.0, 20.0]\naltitude_step: 1000.0\n",
+        )
+        .unwrap();
+        let scenario = Scenario {
+            flyout: Some(flyout),
+            ..scenario(
+                vec![
+                    missile_state([300000.0, 0.0, 5000.0], [0.0, 0.0, 0.0]),
+                    missile_state([5000.0, 0.0, 3000.0], [0.0, 0.0, 0.0]),
+                ],
+                vec![launch_state],
+            )
+        };
+        let mut sim = Simulation::new(&missile_params(), &radar_params(), &params, &scenario, 0.05);
+        assert_eq!(sim.flyout_table.as_ref().map(|table| table.horizon()), Some(20.0));
+
+        // 遠方のミサイルには発射せず、届くミサイルに発射する
+        assert_eq!(launches(&sim.step()), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_max_interceptors_per_target_assigns_next_target() {
+        let mut sim = simulation(
+            vec![
+                missile_state([1000.0, 0.0, 5000.0], [0.0, 0.0, 0.0]),
+                missile_state([2000.0, 0.0, 5000.0], [0.0, 0.0, 0.0]),
+            ],
+            vec![interceptor_state([50000.0, 0.0, 0.0]), interceptor_state([50000.0, 100.0, 0.0])],
+        );
+        sim.max_interceptors_per_target = Some(1);
+        assert_eq!(launches(&sim.step()), vec![(0, 0), (1, 1)]);
+
+        // 上限なしでは両方とも最優先の目標に割り当てる
+        let mut unlimited = simulation(
+            vec![
+                missile_state([1000.0, 0.0, 5000.0], [0.0, 0.0, 0.0]),
+                missile_state([2000.0, 0.0, 5000.0], [0.0, 0.0, 0.0]),
+            ],
+            vec![interceptor_state([50000.0, 0.0, 0.0]), interceptor_state([50000.0, 100.0, 0.0])],
+        );
+        assert_eq!(launches(&unlimited.step()), vec![(0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn test_seed_is_echoed_into_run_metadata() {
+        use crate::utils::event_log::{EventLog, EventLogFormat};
+
+        let mut sim = simulation(vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])], vec![]);
+        sim.seed = 12345;
+        let metadata = sim.run_metadata(2000.0);
+        assert_eq!(metadata, RunMetadata { seed: 12345, dt: 0.1, duration: 2000.0 });
+
+        let mut sidecar = Vec::new();
+        metadata.write_csv(&mut sidecar).unwrap();
+        assert!(String::from_utf8(sidecar).unwrap().ends_with("\n12345,0.1,2000\n"));
+        let log = EventLog::with_metadata(Vec::new(), EventLogFormat::Jsonl, &metadata).unwrap();
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        assert!(text.starts_with("{\"event\":\"run\",\"seed\":12345,"));
+    }
+
+    #[test]
+    fn test_seed_initializes_rng() {
+        let seeded = |seed: u64| {
+            let scenario = Scenario {
+                seed,
+                ..scenario(vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])], vec![])
+            };
+            Simulation::new(&missile_params(), &radar_params(), &interceptor_params(), &scenario, 0.1)
+        };
+        let mut a = seeded(7);
+        assert_eq!(a.seed, 7);
+        assert_eq!(a.rng, SeededRng::new(7));
+        assert_ne!(seeded(8).rng, a.rng);
+
+        // 巻き戻すと乱数系列も戻る
+        a.set_history_depth(1);
+        let before = a.rng.clone();
+        a.step();
+        a.rng.next_u64();
+        assert!(a.step_back());
+        assert_eq!(a.rng, before);
+    }
+
+    /// 高度 5 m から 1000 m/s で水平に進みつつ降下するミサイル (約 0.05 s で着弾) と、
+    /// 地表の x 座標 interceptor_x に静止した発射済み迎撃ミサイルの1ステップ目のイベント
+    fn grazing_step_events(interceptor_x: f64, precedence: ImpactPrecedence) -> (Vec<SimEvent>, Simulation) {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 5.0], [1000.0, 0.0, -100.0])],
+            vec![InterceptorState {
+                launched: true,
+                ..interceptor_state([interceptor_x, 0.0, 0.0])
+            }],
+        );
+        sim.impact_precedence = precedence;
+        let events = sim.step();
+        (events, sim)
+    }
+
+    #[test]
+    fn test_intercept_before_impact_is_recorded_as_intercept() {
+        // 約 0.03 s で迎撃判定距離に入り、その後 0.05 s で着弾する
+        let (events, sim) = grazing_step_events(1080.0, ImpactPrecedence::Earliest);
+        let names: Vec<_> = events.iter().map(|e| e.name()).collect();
+        assert_eq!(names, ["intercept"]);
+        assert!(sim.missiles[0].state.intercepted);
+        assert!(!sim.missiles[0].state.active);
+        // 迎撃はステップ終了時刻ではなく、迎撃判定距離に入った時刻で記録する
+        let SimEvent::Intercept { time, .. } = events[0] else { panic!("intercept event expected") };
+        assert!((time - 0.03).abs() < 0.005, "time {}", time);
+        assert_eq!(sim.intercepts[0].time, time);
+    }
+
+    #[test]
+    fn test_impact_before_intercept_is_recorded_as_impact() {
+        // 0.05 s で着弾し、迎撃判定距離に入るのは約 0.08 s
+        let (events, sim) = grazing_step_events(1130.0, ImpactPrecedence::Earliest);
+        let names: Vec<_> = events.iter().map(|e| e.name()).collect();
+        assert_eq!(names, ["impact"]);
+        assert!(!sim.missiles[0].state.intercepted);
+
+        // 迎撃優先なら着弾後の進入でも迎撃とする
+        let (events, sim) = grazing_step_events(1130.0, ImpactPrecedence::Intercept);
+        assert_eq!(events.last().unwrap().name(), "intercept");
+        assert!(sim.missiles[0].state.intercepted);
+    }
+
+    #[test]
+    fn test_impact_precedence_keeps_impact_by_default() {
+        let (events, sim) = grazing_step_events(1080.0, ImpactPrecedence::default());
+        assert_eq!(events.last().unwrap().name(), "impact");
+        assert!(!sim.missiles[0].state.intercepted);
+    }
+
+    #[test]
+    fn test_min_miss_distance_matches_analytic_closest_approach() {
+        // 相対速度 300 m/s で x 方向に 3015 m 離れ、y 方向に 100 m ずれてすれ違う
+        // (解析解: t = 10.05 s で最小距離 100 m、迎撃判定距離 50 m より遠いため迎撃しない)
+        let mut sim = simulation(
+            vec![missile_state([0.0, 0.0, 1000.0], [-100.0, 0.0, 0.0])],
+            vec![InterceptorState {
+                velocity: [200.0, 0.0, 0.0],
+                ..interceptor_state([-3015.0, 100.0, 1000.0])
+            }],
+        );
+        sim.missiles[0].params.g = 0.0;
+        assert_eq!(sim.summary().min_miss_distances, vec![None]);
+
+        let mut events = Vec::new();
+        for _ in 0..200 {
+            events.extend(sim.step());
+        }
+
+        assert!(events.iter().all(|e| e.name() != "intercept"));
+        let miss = sim.summary().min_miss_distances[0].unwrap();
+        assert!((miss.distance - 100.0).abs() < 1e-6, "distance {}", miss.distance);
+        assert!((miss.time - 10.05).abs() < 1e-6, "time {}", miss.time);
+    }
+
+    /// 水平距離 1000 m で迎撃される交戦を、防衛線 defense_line [m] のもとで実行した集計
+    fn summary_with_defense_line(defense_line: f64) -> RunSummary {
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([1010.0, 0.0, 100.0])],
+        );
+        sim.defended_point = Some(DefendedPoint {
+            position: [0.0, 0.0, 0.0],
+            radius: 5000.0,
+            defense_line: Some(defense_line),
+        });
+        run(&mut sim);
+        sim.summary()
+    }
+
+    #[test]
+    fn test_intercept_before_defense_line_is_in_time() {
+        let summary = summary_with_defense_line(999.0);
+        assert_eq!(summary.intercepts.len(), 1);
+        assert_eq!(summary.intercepts[0].timing, InterceptTiming::InTime);
+        assert_eq!(summary.late_intercepts, 0);
+    }
+
+    #[test]
+    fn test_intercept_past_defense_line_counts_as_leaker() {
+        let summary = summary_with_defense_line(1001.0);
+        assert_eq!(summary.intercepts.len(), 1);
+        assert_eq!(summary.intercepts[0].timing, InterceptTiming::TooLate);
+        assert_eq!(summary.late_intercepts, 1);
+    }
+
+    /// y 方向に 30 m ずれて相対速度 300 m/s ですれ違う交戦 (解析解: t = 10.05 s で最接近) のイベント
+    fn fuzed_flyby_events(arming_time: Option<f64>) -> (Vec<SimEvent>, Simulation) {
+        let mut sim = simulation(
+            vec![missile_state([0.0, 0.0, 1000.0], [-100.0, 0.0, 0.0])],
+            vec![InterceptorState {
+                velocity: [200.0, 0.0, 0.0],
+                ..interceptor_state([-3015.0, 30.0, 1000.0])
+            }],
+        );
+        sim.missiles[0].params.g = 0.0;
+        sim.interceptors[0].params.arming_time = arming_time;
+        let mut events = Vec::new();
+        for _ in 0..150 {
+            events.extend(sim.step());
+        }
+        (events, sim)
+    }
+
+    #[test]
+    fn test_fuze_detonates_at_closest_approach() {
+        // 距離判定のみでは迎撃判定距離 50 m に入った t ≈ 9.92 s のステップで迎撃
+        let (events, _) = fuzed_flyby_events(None);
+        let intercept = events.iter().find(|e| e.name() == "intercept").unwrap();
+        assert!((intercept.time() - 10.0).abs() < 1e-9, "intercept at {}", intercept.time());
+
+        // 近接信管は距離変化率の符号が変わる (最接近を含む) ステップで起爆する
+        let (events, sim) = fuzed_flyby_events(Some(1.0));
+        let intercept = events.iter().find(|e| e.name() == "intercept").unwrap();
+        assert!((intercept.time() - 10.1).abs() < 1e-9, "intercept at {}", intercept.time());
+        let record = &sim.summary().intercepts[0];
+        assert!((record.position[0] - (-1005.0)).abs() < 1e-6, "position {:?}", record.position);
+        assert!(sim.interceptors[0].state.inert);
+    }
+
+    #[test]
+    fn test_fuze_does_not_detonate_before_arming() {
+        // 最接近 (t = 10.05 s) の時点で安全解除されていないため起爆せず、ミサイルは飛行を続ける
+        let (events, sim) = fuzed_flyby_events(Some(10.5));
+        assert!(events.iter().all(|e| e.name() != "intercept" && e.name() != "dud"));
+        assert!(sim.missiles[0].state.active);
+        assert!(!sim.interceptors[0].state.inert);
+    }
+
+    /// シミュレーションの1ステップごとに step だけ進む模擬時計
+    struct StepClock {
+        now: Duration,
+        step: Duration,
+    }
+
+    impl Clock for StepClock {
+        fn now(&self) -> Duration {
+            self.now
+        }
+
+        fn on_step(&mut self) {
+            self.now += self.step;
+        }
+    }
+
+    #[test]
+    fn test_simulation_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Simulation>();
+    }
+
+    fn falling_missile_with_clock(step: Duration) -> Simulation {
+        // 高度 100 m から自由落下 (約 4.5 s で着弾)
+        let mut sim = simulation(
+            vec![missile_state([1000.0, 0.0, 100.0], [0.0, 0.0, 0.0])],
+            vec![interceptor_state([50000.0, 0.0, 0.0])],
+        );
+        sim.clock = Box::new(StepClock {
+            now: Duration::ZERO,
+            step,
+        });
+        sim
+    }
+
+    #[test]
+    fn test_run_with_timeout_stops_when_wall_budget_exceeded() {
+        // 1ステップごとに 1 s 経過する時計で上限 4.5 s: 5ステップ目で上限を超え、打ち切る
+        let mut sim = falling_missile_with_clock(Duration::from_secs(1));
+        let (status, events) = sim.run_with_timeout(100.0, Duration::from_millis(4500));
+        assert_eq!(status, RunStatus::TimedOut);
+        assert!((sim.time - 0.5).abs() < 1e-9);
+        assert!(sim.is_running());
+        assert!(events.iter().all(|event| !matches!(event, SimEvent::GroundImpact { .. })));
+    }
+
+    #[test]
+    fn test_run_with_timeout_completes_within_budget() {
+        let mut sim = falling_missile_with_clock(Duration::from_millis(1));
+        let (status, events) = sim.run_with_timeout(100.0, Duration::from_secs(5));
+        assert_eq!(status, RunStatus::Completed);
+        assert!(!sim.is_running());
+        assert!(events.iter().any(|event| matches!(event, SimEvent::GroundImpact { .. })));
+
+        // 指定時刻で止まる
+        let mut sim = falling_missile_with_clock(Duration::from_millis(1));
+        let (status, _) = sim.run_with_timeout(1.0, Duration::from_secs(5));
+        assert_eq!(status, RunStatus::Completed);
+        assert!(sim.is_running());
+        assert!((sim.time - 1.0).abs() < 1e-6);
+    }
+
+    /// 探知確率の上限 pd_max のレーダで 200 ステップの間に探知したステップの列
+    fn detection_history(pd_max: f64, seed: u64) -> Vec<bool> {
+        let scenario = Scenario {
+            seed,
+            ..scenario(vec![missile_state([10000.0, 0.0, 30000.0], [0.0, 0.0, 0.0])], vec![])
+        };
+        let radar = RadarParams {
+            pd_max,
+            ..radar_params()
+        };
+        let mut sim = Simulation::new(&missile_params(), &radar, &interceptor_params(), &scenario, 0.1);
+        (0..200)
+            .map(|_| {
+                sim.step();
+                sim.detected[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_radar_step_applies_detection_probability() {
+        assert!(detection_history(1.0, 1).iter().all(|&d| d));
+        let history = detection_history(0.5, 1);
+        let rate = history.iter().filter(|&&d| d).count() as f64 / history.len() as f64;
+        assert!((0.4..0.6).contains(&rate), "rate {}", rate);
+        // 同じシードなら同じ探知系列、異なるシードなら異なる系列
+        assert_eq!(detection_history(0.5, 1), history);
+        assert_ne!(detection_history(0.5, 2), history);
+    }
+
+    #[test]
+    fn test_radar_step_hands_over_to_track_radar() {
+        use crate::models::radar::RadarRole;
+
+        let track = RadarParams {
+            position: [5000.0, 0.0, 0.0],
+            role: RadarRole::Track,
+            measurement_sigma: 5.0,
+            ..radar_params()
+        };
+        let scenario = Scenario {
+            radars: vec![track],
+            ..scenario(vec![missile_state([10000.0, 0.0, 30000.0], [0.0, 0.0, 0.0])], vec![])
+        };
+        let search = RadarParams {
+            measurement_sigma: 100.0,
+            ..radar_params()
+        };
+        let mut sim = Simulation::new(&missile_params(), &search, &interceptor_params(), &scenario, 0.1);
+        assert_eq!(sim.radars.len(), 2);
+        assert_eq!(sim.track_assignments, vec![None]);
+
+        // 主レーダ (捜索) が探知して追尾レーダへ引き継ぎ、以降は追尾レーダが追尾を続ける
+        for _ in 0..5 {
+            sim.step();
+            assert!(sim.detected[0]);
+            assert_eq!(sim.track_assignments, vec![Some(1)]);
+        }
+    }
+
+    #[test]
+    fn test_tracked_target_coasts_when_undetected() {
+        use crate::models::radar::DetectionHysteresis;
+
+        let radar = RadarParams {
+            hysteresis: Some(DetectionHysteresis {
+                confirm_m: 1,
+                confirm_n: 1,
+                drop_m: 3,
+                drop_n: 3,
+            }),
+            ..radar_params()
+        };
+        let scenario = scenario(vec![missile_state([95000.0, 0.0, 30000.0], [1000.0, 0.0, 0.0])], vec![]);
+        let mut sim = Simulation::new(&missile_params(), &radar, &interceptor_params(), &scenario, 0.1);
+
+        // 探知範囲外へ出た後もヒステリシスで追尾が続く間は、前回の計測を推定値として外挿する
+        let mut coasted = 0;
+        for _ in 0..30 {
+            // 探知と推定はミサイルの運動を更新した後、時刻を進める前に行われる
+            let time = sim.time;
+            sim.step();
+            let truth = sim.missiles[0].state.position;
+            let in_range = truth.iter().map(|x| x * x).sum::<f64>().sqrt() <= radar.range;
+            if sim.detected[0] && !in_range {
+                let estimate = sim.target_estimates[0].expect("tracked target must have an estimate");
+                assert!(estimate.time < time);
+                assert_ne!(estimate.position_at(time), truth);
+                coasted += 1;
+            }
+        }
+        assert_eq!(coasted, 2);
+        assert_eq!(sim.target_estimates[0], None);
+    }
+
+    /// 重力で加速しながら落下する目標を計測遅れ latency のレーダで追尾し、
+    /// 誘導に用いる目標位置の推定値と真値の z 方向の差を返す
+    fn estimate_error_under_latency(latency: f64) -> f64 {
+        let radar = RadarParams {
+            sensor_latency: latency,
+            ..radar_params()
+        };
+        let scenario = scenario(vec![missile_state([10000.0, 0.0, 30000.0], [-300.0, 0.0, 0.0])], vec![]);
+        let mut sim = Simulation::new(&missile_params(), &radar, &interceptor_params(), &scenario, 0.1);
+        let mut time = 0.0;
+        for _ in 0..20 {
+            time = sim.time;
+            sim.step();
+        }
+        let estimate = sim.target_estimates[0].expect("target must be tracked");
+        // 推定は運動の更新後、時刻を進める前に行われるため、同じステップの真値と比べる
+        estimate.position_at(time)[2] - sim.missiles[0].state.position[2]
+    }
+
+    #[test]
+    fn test_sensor_latency_offsets_estimate_under_acceleration() {
+        // 計測遅れがなければ推定値は最新の計測 (真値) と一致する
+        assert_eq!(estimate_error_under_latency(0.0), 0.0);
+        // 遅れた計測を差分速度で外挿するため、加速 (重力) の分だけ真値からずれる
+        // (1/2 g L^2 + g dt L / 2 ~ 1.5 m)
+        let error = estimate_error_under_latency(0.5);
+        assert!((0.5..5.0).contains(&error), "error {}", error);
+    }
+
+    /// 計測遅れ latency [s] のレーダで等速の横行目標を PIP 誘導で追尾したときのミス距離
+    fn latency_miss_distance(latency: f64, target_extrapolation_time: Option<f64>) -> f64 {
+        let radar = RadarParams {
+            sensor_latency: latency,
+            ..radar_params()
+        };
+        let interceptor = InterceptorParams {
+            guidance_constants: GuidanceConstants { n: 100.0 },
+            aim_point: AimPoint::PredictedInterceptPoint,
+            target_extrapolation_time,
+            ..interceptor_params()
+        };
+        let scenario = scenario(
+            vec![missile_state([4000.0, -2000.0, 1000.0], [0.0, 400.0, 0.0])],
+            vec![InterceptorState {
+                velocity: [300.0, 0.0, 0.0],
+                ..interceptor_state([0.0, 0.0, 1000.0])
+            }],
+        );
+        let mut sim = Simulation::new(&missile_params(), &radar, &interceptor, &scenario, 0.01);
+        sim.missiles[0].params.g = 0.0;
+        for _ in 0..2000 {
+            sim.step();
+        }
+        sim.summary().min_miss_distances[0].expect("interceptor must approach the target").distance
+    }
+
+    #[test]
+    fn test_latency_compensated_once_in_simulation() {
+        let latency = 1.0;
+        let reference = latency_miss_distance(0.0, None);
+        let compensated = latency_miss_distance(latency, None);
+        let raw = latency_miss_distance(latency, Some(0.0));
+        let doubled = latency_miss_distance(latency, Some(2.0 * latency));
+        // 計測からの経過時間だけ外挿すると遅れのない場合とほぼ同じミス距離になる
+        assert!((compensated - reference).abs() < 5.0, "compensated {} / reference {}", compensated, reference);
+        // 補償しない場合も、二重に補償した場合もミス距離が大きくなる
+        assert!(raw > 2.0 * compensated, "raw {} / compensated {}", raw, compensated);
+        assert!(doubled > 2.0 * compensated, "doubled {} / compensated {}", doubled, compensated);
+    }
+
+    #[test]
+    fn test_revisit_age_advances_by_step() {
+        // レーダ周期 (0.1 s) と異なるステップ幅 0.02 s で、1ステップに1目標しか更新できない
+        let radar = RadarParams {
+            max_track_updates: Some(1),
+            ..radar_params()
+        };
+        let scenario = scenario(
+            vec![
+                missile_state([10000.0, 0.0, 30000.0], [0.0, 0.0, 0.0]),
+                missile_state([10000.0, 1000.0, 30000.0], [0.0, 0.0, 0.0]),
+            ],
+            vec![],
+        );
+        let mut sim = Simulation::new(&missile_params(), &radar, &interceptor_params(), &scenario, 0.02);
+        sim.step();
+        // 更新されなかった目標の経過時間はステップ幅だけ進む
+        assert_eq!(sim.radar_state.update_count(0) + sim.radar_state.update_count(1), 1);
+        let waiting = if sim.radar_state.update_count(0) == 0 { 0 } else { 1 };
+        assert!((sim.radar_state.revisit_priority(waiting, 0.0) - 0.02).abs() < 1e-12);
+        // 更新数の上限はステップごとに適用され、両目標とも再訪問される
+        for _ in 0..9 {
+            sim.step();
+        }
+        let counts = (sim.radar_state.update_count(0), sim.radar_state.update_count(1));
+        assert_eq!(counts.0 + counts.1, 10);
+        assert!(counts.0 >= 4 && counts.1 >= 4, "updates {:?}", counts);
+    }
+
+    #[test]
+    fn test_run_ensemble_varies_seed_per_run() {
+        // 計測誤差のあるレーダで、実行ごとに異なる乱数系列から迎撃する
+        let radar = RadarParams {
+            measurement_sigma: 20.0,
+            ..radar_params()
+        };
+        let interceptor = InterceptorParams {
+            guidance_constants: GuidanceConstants { n: 100.0 },
+            aim_point: AimPoint::PredictedInterceptPoint,
+            ..interceptor_params()
+        };
+        let missile = MissileParams { g: 0.0, ..missile_params() };
+        let scenario = Scenario {
+            seed: 10,
+            ..scenario(
+                vec![missile_state([4000.0, -2000.0, 1000.0], [0.0, 400.0, 0.0])],
+                vec![InterceptorState {
+                    velocity: [300.0, 0.0, 0.0],
+                    ..interceptor_state([0.0, 0.0, 1000.0])
+                }],
+            )
+        };
+        let stats = run_ensemble(&missile, &radar, &interceptor, &scenario, 0.01, 20.0, 5);
+
+        // i 回目の実行は seed + i の単独実行と一致する
+        let outcomes: Vec<RunSummary> = (10..15)
+            .map(|seed| {
+                let mut sim =
+                    Simulation::new(&missile, &radar, &interceptor, &Scenario { seed, ..scenario.clone() }, 0.01);
+                while sim.is_running() && sim.time < 20.0 - 1e-9 {
+                    sim.step();
+                }
+                sim.summary()
+            })
+            .collect();
+        assert_eq!(stats, EnsembleStats::from(outcomes.as_slice()));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.attempts, 5);
+        // 乱数系列が実行ごとに異なるため、ミス距離がばらつく
+        assert_ne!(stats.median_miss_distance, stats.p95_miss_distance);
+    }
+}
@@ -0,0 +1,48 @@
+use std::time::{Duration, Instant};
+
+/// 実時間の取得元 (テストでは模擬時計に差し替える)
+pub trait Clock {
+    /// 任意の基準時点からの経過実時間
+    fn now(&self) -> Duration;
+
+    /// シミュレーションを1ステップ進めるごとに呼ばれる (実時計では何もしない)。
+    /// 模擬時計はここで1ステップ分の実時間を進める
+    fn on_step(&mut self) {}
+}
+
+/// std::time::Instant による実時間
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    origin: Instant, // 基準時点
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock { origin: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_is_monotonic() {
+        let clock = SystemClock::new();
+        let t0 = clock.now();
+        let t1 = clock.now();
+        assert!(t1 >= t0);
+    }
+}
@@ -0,0 +1,166 @@
+use std::io::{self, Write};
+
+use crate::simulation::SimEvent;
+
+/// イベントログの出力形式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventLogFormat {
+    /// CSV (ヘッダー: time[s],event,interceptor_id,missile_id)
+    Csv,
+    /// 1行1オブジェクトのJSON
+    Jsonl,
+}
+
+/// 実行を再現するための設定
+/// CSV 出力ではヘッダーの前に書くと CSV として読めなくなるため、別ファイル (サイドカー) に書き出す
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunMetadata {
+    pub seed: u64,     // 乱数シード
+    pub dt: f64,       // 時間刻み [s]
+    pub duration: f64, // 計算時間 [s]
+}
+
+impl RunMetadata {
+    /// サイドカー用のCSV (ヘッダー行と値の行) を書き込む
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "seed,dt,duration")?;
+        writeln!(writer, "{},{},{}", self.seed, self.dt, self.duration)
+    }
+
+    /// JSONオブジェクト (改行なし)
+    pub fn json(&self) -> String {
+        format!(
+            "{{\"event\":\"run\",\"seed\":{},\"dt\":{},\"duration\":{}}}",
+            self.seed, self.dt, self.duration
+        )
+    }
+}
+
+/// 状態CSVとは別にイベントを書き出すログ
+pub struct EventLog<W: Write> {
+    writer: W,
+    format: EventLogFormat,
+}
+
+impl<W: Write> EventLog<W> {
+    /// ログを作成する (CSVの場合はヘッダーを書き込む)
+    pub fn new(mut writer: W, format: EventLogFormat) -> io::Result<Self> {
+        if format == EventLogFormat::Csv {
+            writeln!(writer, "time[s],event,interceptor_id,missile_id")?;
+        }
+        Ok(EventLog { writer, format })
+    }
+
+    /// 実行設定を記録したログを作成する
+    /// JSONLは最初の行に event="run" のオブジェクトを書き込む。
+    /// CSVはヘッダーのみとし、実行設定は RunMetadata::write_csv でサイドカーファイルに書き出す
+    pub fn with_metadata(mut writer: W, format: EventLogFormat, metadata: &RunMetadata) -> io::Result<Self> {
+        if format == EventLogFormat::Jsonl {
+            writeln!(writer, "{}", metadata.json())?;
+        }
+        Self::new(writer, format)
+    }
+
+    /// イベントを1件書き込む (関係しないエンティティは空欄 / null)
+    pub fn write(&mut self, event: &SimEvent) -> io::Result<()> {
+        match self.format {
+            EventLogFormat::Csv => {
+                let id = |id: Option<usize>| id.map_or(String::new(), |id| id.to_string());
+                writeln!(
+                    self.writer,
+                    "{:.3},{},{},{}",
+                    event.time(),
+                    event.name(),
+                    id(event.interceptor()),
+                    id(event.missile())
+                )
+            }
+            EventLogFormat::Jsonl => {
+                let id = |id: Option<usize>| id.map_or("null".to_string(), |id| id.to_string());
+                writeln!(
+                    self.writer,
+                    "{{\"time\":{:.3},\"event\":\"{}\",\"interceptor_id\":{},\"missile_id\":{}}}",
+                    event.time(),
+                    event.name(),
+                    id(event.interceptor()),
+                    id(event.missile())
+                )
+            }
+        }
+    }
+
+    /// 複数のイベントを順に書き込む
+    pub fn write_all(&mut self, events: &[SimEvent]) -> io::Result<()> {
+        events.iter().try_for_each(|event| self.write(event))
+    }
+
+    /// 書き込み先を取り出す
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events() -> Vec<SimEvent> {
+        vec![
+            SimEvent::Launch {
+                time: 0.0,
+                interceptor: 0,
+                missile: 1,
+            },
+            SimEvent::GroundImpact {
+                time: 4.5,
+                missile: 2,
+                position: [100.0, 0.0, 0.0],
+                velocity: [0.0, 0.0, -50.0],
+                impact_angle: std::f64::consts::FRAC_PI_2,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_csv_event_log() {
+        let mut log = EventLog::new(Vec::new(), EventLogFormat::Csv).unwrap();
+        log.write_all(&events()).unwrap();
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        assert_eq!(
+            text,
+            "time[s],event,interceptor_id,missile_id\n0.000,launch,0,1\n4.500,impact,,2\n"
+        );
+    }
+
+    #[test]
+    fn test_jsonl_event_log() {
+        let mut log = EventLog::new(Vec::new(), EventLogFormat::Jsonl).unwrap();
+        log.write_all(&events()).unwrap();
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        assert_eq!(
+            text,
+            "{\"time\":0.000,\"event\":\"launch\",\"interceptor_id\":0,\"missile_id\":1}\n\
+             {\"time\":4.500,\"event\":\"impact\",\"interceptor_id\":null,\"missile_id\":2}\n"
+        );
+    }
+
+    #[test]
+    fn test_metadata_records_seed() {
+        let metadata = RunMetadata {
+            seed: 42,
+            dt: 0.1,
+            duration: 2000.0,
+        };
+        // CSVのイベントログはヘッダーから始まり、実行設定はサイドカーに書く
+        let log = EventLog::with_metadata(Vec::new(), EventLogFormat::Csv, &metadata).unwrap();
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        assert_eq!(text, "time[s],event,interceptor_id,missile_id\n");
+        let mut sidecar = Vec::new();
+        metadata.write_csv(&mut sidecar).unwrap();
+        assert_eq!(String::from_utf8(sidecar).unwrap(), "seed,dt,duration\n42,0.1,2000\n");
+
+        let log = EventLog::with_metadata(Vec::new(), EventLogFormat::Jsonl, &metadata).unwrap();
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        assert_eq!(text, "{\"event\":\"run\",\"seed\":42,\"dt\":0.1,\"duration\":2000}\n");
+    }
+}
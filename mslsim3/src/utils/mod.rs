@@ -1 +1,3 @@
 pub mod yaml_parser;
+pub mod event_log;
+pub mod clock;
@@ -5,78 +5,77 @@ use std::io::Read;
 use std::error::Error;
 
 // =======================
-// Missile params
+// パラメータ・状態 (各モデルの定義を使用)
 // =======================
-#[derive(Debug, Deserialize, Clone)]
-pub struct MissileParams {
-    pub alpha: f64,
-    pub cd: f64,
-    pub area: f64,
-    pub rho0: f64,
-    pub h: f64,
-    pub g: f64,
-    pub alpha_filter: f64,
-}
-
-#[derive(Debug, Deserialize, Clone)]
-pub struct MissileState {
-    pub mass: f64,
-    pub thrust: f64,
-    pub theta: f64,
-    pub psi: f64,
-    pub position: [f64; 3],
-    pub velocity: [f64; 3],
-}
+pub use crate::models::interceptor::{GuidanceConstants, ImpactPrecedence, InterceptorParams, InterceptorState, Launcher,
+    ResolutionMode};
+pub use crate::models::missile::{MissileParams, MissileState};
+pub use crate::models::radar::RadarParams;
+pub use crate::models::fire_control::{DamageScaling, DefendedPoint, FlyoutConfig};
 
 // =======================
-// Radar params
+// シナリオ
 // =======================
 #[derive(Debug, Deserialize, Clone)]
-pub struct RadarParams {
-    pub position: [f64; 3],
-    pub direction: [f64; 3],
-    pub range: f64,
-    pub azimuth_range: f64,
-    pub elevation_range: f64,
-    pub period: f64,
+pub struct Scenario {
+    pub initial_conditions: InitialConditions,
+    pub defended_point: Option<DefendedPoint>, // 防護地点 (None: 探知した全ミサイルと交戦)
+    pub launcher: Option<Launcher>, // 移動する発射母体 (None: 固定発射機)
+    #[serde(default)]
+    pub resolution_mode: ResolutionMode, // 同一ステップ内の迎撃判定の解決方法
+    #[serde(default)]
+    pub impact_precedence: ImpactPrecedence, // 同一ステップ内の着弾と迎撃の優先規則
+    #[serde(default)]
+    pub c2_latency: f64, // 初探知から発射判断までの指揮統制の遅れ [s]
+    #[serde(default)]
+    pub max_interceptors_per_target: Option<usize>, // 1目標に割り当てる迎撃ミサイル数の上限 (None: 制限なし)
+    #[serde(default)]
+    pub damage_scaling: DamageScaling, // 着弾の運動エネルギーから被害半径を求めるスケーリング則
+    #[serde(default)]
+    pub seed: u64, // 乱数シード (出力に記録し、実行を再現できるようにする)
+    #[serde(default)]
+    pub radars: Vec<RadarParams>, // radar_params に加えるレーダ (捜索レーダから追尾レーダへの引き継ぎに使用)
+    #[serde(default)]
+    pub flyout: Option<FlyoutConfig>, // 迎撃ミサイルの到達範囲表の生成条件 (None: 到達性で候補を絞らない)
+    #[serde(default)]
+    pub output: OutputConfig, // 追加で出力するファイルの選択
+    #[serde(default)]
+    pub max_expected_speed: Option<f64>, // 時間刻みの安定性チェックに用いる想定最大速度 [m/s] (None: ミサイルの初速の最大値)
 }
 
-// =======================
-// Interceptor params
-// =======================
-#[derive(Debug, Deserialize, Clone)]
-pub struct GuidanceConstants {
-    pub n: f64,
+impl Scenario {
+    /// 時間刻みの安定性チェックに用いる想定最大速度 [m/s]
+    pub fn max_expected_speed(&self) -> f64 {
+        self.max_expected_speed.unwrap_or_else(|| {
+            self.initial_conditions
+                .missiles
+                .iter()
+                .map(|state| state.velocity.iter().map(|v| v * v).sum::<f64>().sqrt())
+                .fold(0.0, f64::max)
+        })
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct InterceptorParams {
-    pub alpha: f64,
-    pub cd: f64,
-    pub area: f64,
-    pub g: f64,
-    pub thrust: f64,
-    pub alpha_filter: f64,
-    pub guidance_constants: GuidanceConstants,
+/// 追加で出力するファイルの選択 (省略時はすべて出力する)
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct OutputConfig {
+    #[serde(default = "enabled")]
+    pub relative_geometry: bool, // 迎撃ミサイルと目標の相対幾何 (誘導調整用)
+    #[serde(default = "enabled")]
+    pub closest_approach: bool, // 迎撃ミサイルごとの目標への最接近
 }
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct InterceptorState {
-    pub mass: f64,
-    pub thrust: f64,
-    pub theta: f64,
-    pub psi: f64,
-    pub position: [f64; 3],
-    pub velocity: [f64; 3],
-    pub launched: bool,
+fn enabled() -> bool {
+    true
 }
 
-// =======================
-// シナリオ
-// =======================
-#[derive(Debug, Deserialize, Clone)]
-pub struct Scenario {
-    pub initial_conditions: InitialConditions,
+impl Default for OutputConfig {
+    fn default() -> Self {
+        OutputConfig {
+            relative_geometry: true,
+            closest_approach: true,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -94,4 +93,32 @@ pub fn parse_yaml<T: for<'de> Deserialize<'de>>(path: &str) -> Result<T, Box<dyn
     file.read_to_string(&mut contents)?;
     let data: T = serde_yaml::from_str(&contents)?;
     Ok(data)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INITIAL_CONDITIONS: &str = "initial_conditions:\n  missiles: []\n  interceptors: []\n";
+
+    #[test]
+    fn test_output_config_defaults_to_all_files() {
+        let scenario: Scenario = serde_yaml::from_str(INITIAL_CONDITIONS).unwrap();
+        assert_eq!(scenario.output, OutputConfig::default());
+
+        let yaml = format!("{}output:\n  closest_approach: false\n", INITIAL_CONDITIONS);
+        let scenario: Scenario = serde_yaml::from_str(&yaml).unwrap();
+        assert!(!scenario.output.closest_approach);
+        assert!(scenario.output.relative_geometry);
+    }
+
+    #[test]
+    fn test_max_expected_speed_defaults_to_fastest_missile() {
+        let yaml = "initial_conditions:\n  missiles:\n    - {mass: 1.0, thrust: 0.0, theta: 0.0, psi: 0.0, position: [0.0, 0.0, 0.0], velocity: [300.0, 0.0, 400.0]}\n    - {mass: 1.0, thrust: 0.0, theta: 0.0, psi: 0.0, position: [0.0, 0.0, 0.0], velocity: [100.0, 0.0, 0.0]}\n  interceptors: []\n";
+        let scenario: Scenario = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(scenario.max_expected_speed(), 500.0);
+
+        let scenario: Scenario = serde_yaml::from_str(&format!("{}max_expected_speed: 3000.0\n", yaml)).unwrap();
+        assert_eq!(scenario.max_expected_speed(), 3000.0);
+    }
+}